@@ -1,3 +1,80 @@
+use sha2::{Digest, Sha256};
+use std::{env, fs, path::Path};
+
 fn main() {
+    pin_core();
     tauri_build::build()
 }
+
+/// Pins what a tvault-core sidecar must match so the app refuses a swapped one:
+///
+/// - `TVAULT_CORE_SHA256`: SHA-256 of the bundled (unsigned) binary, or an
+///   explicit value from the build environment.
+/// - `TVAULT_CORE_TEAM_ID` (macOS): the Apple Team ID the sidecar is signed
+///   with. Bundling re-signs the sidecar, which changes its bytes, so signed
+///   releases are recognised by their code signature instead of the checksum.
+///
+/// Release builds that end up with neither fail here instead of shipping an
+/// app that rejects every core; debug builds get a warning, since they reject
+/// every core as well.
+fn pin_core() {
+    let target = env::var("TARGET").unwrap_or_default();
+    let sha256 = pin_checksum(&target);
+    let team_id = if target.contains("apple") { pin_team_id() } else { None };
+
+    if sha256.is_some() || team_id.is_some() {
+        return;
+    }
+    let hint = format!(
+        "tvault-core is not pinned: put the sidecar at binaries/tvault-core-{target} \
+         or set TVAULT_CORE_SHA256 (or TVAULT_CORE_TEAM_ID for signed macOS builds)"
+    );
+    if env::var("PROFILE").as_deref() == Ok("release") {
+        panic!("{hint}");
+    }
+    // debug builds trust no unpinned core either, so say so now rather than at the first seal
+    println!("cargo:warning={hint}; every core will be rejected at run time");
+}
+
+fn pin_checksum(target: &str) -> Option<String> {
+    println!("cargo:rerun-if-env-changed=TVAULT_CORE_SHA256");
+    let digest = match env::var("TVAULT_CORE_SHA256") {
+        Ok(pinned) => pinned.trim().to_lowercase(),
+        Err(_) => {
+            let ext = if target.contains("windows") { ".exe" } else { "" };
+            let sidecar = Path::new("binaries").join(format!("tvault-core-{target}{ext}"));
+            println!("cargo:rerun-if-changed={}", sidecar.display());
+            let bytes = fs::read(&sidecar).ok()?;
+            Sha256::digest(&bytes).iter().map(|b| format!("{b:02x}")).collect()
+        }
+    };
+    println!("cargo:rustc-env=TVAULT_CORE_SHA256={digest}");
+    Some(digest)
+}
+
+/// `TVAULT_CORE_TEAM_ID`, else `APPLE_TEAM_ID` (set for the release job), else
+/// the Team ID in parentheses at the end of the configured signing identity.
+fn pin_team_id() -> Option<String> {
+    println!("cargo:rerun-if-env-changed=TVAULT_CORE_TEAM_ID");
+    println!("cargo:rerun-if-env-changed=APPLE_TEAM_ID");
+    println!("cargo:rerun-if-changed=tauri.conf.json");
+    let team_id = env::var("TVAULT_CORE_TEAM_ID")
+        .or_else(|_| env::var("APPLE_TEAM_ID"))
+        .ok()
+        .or_else(signing_identity_team_id)?;
+    let team_id = team_id.trim().to_string();
+    // it is spliced into a code requirement at run time
+    if team_id.len() != 10 || !team_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        panic!("invalid Apple Team ID {team_id:?}");
+    }
+    println!("cargo:rustc-env=TVAULT_CORE_TEAM_ID={team_id}");
+    Some(team_id)
+}
+
+fn signing_identity_team_id() -> Option<String> {
+    let conf = fs::read_to_string("tauri.conf.json").ok()?;
+    let line = conf.lines().find(|l| l.contains("\"signingIdentity\""))?;
+    let (_, identity) = line.split_once(':')?;
+    let inner = identity.rsplit_once('(')?.1;
+    Some(inner.split_once(')')?.0.to_string())
+}
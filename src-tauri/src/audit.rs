@@ -0,0 +1,449 @@
+//! audit.rs — append-only, hash-chained record of vault operations
+//!
+//! Every seal, unseal, reseal and delete appends one JSON line
+//! to `audit.jsonl` in the app data dir: who ran it, on which container,
+//! how it ended, the integrity provider and how long it took. Credentials and
+//! core output never reach the log.
+//!
+//! Each entry holds the hash of the one before it (`prev`) and its own `hash`:
+//!
+//! `hex(SHA-256("tvault-audit-v1" ‖ entry as JSON with an empty hash))`
+//!
+//! so editing, inserting, removing or reordering lines breaks the chain from
+//! that point, which `verify_audit_log` reports. The chain alone cannot tell
+//! dropped newest lines, or a log rewritten and re-hashed from scratch, apart
+//! from a genuine one, so every append also writes the newest `seq` and `hash`
+//! to `audit-head.json` in the app config dir, away from the log, and
+//! verification checks the log still ends there. The line is synced first, so
+//! a crash between the two leaves the anchor one entry behind, which passes.
+
+use std::{
+  fmt::Write as _,
+  fs::{self, OpenOptions},
+  io::Write,
+  path::{Path, PathBuf},
+  sync::Mutex,
+  time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager, Runtime, State, Wry};
+
+use crate::logging;
+
+pub const AUDIT_FILE: &str = "audit.jsonl";
+pub const HEAD_FILE: &str = "audit-head.json";
+const DOMAIN: &[u8] = b"tvault-audit-v1";
+/// `prev` of the first entry.
+const GENESIS: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+const CSV_HEADER: &str = "seq,at,user,op,container,container_id,outcome,integrity,duration_ms,prev,hash";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AuditEntry {
+  /// 1 for the first entry, one up for each after it.
+  pub seq:          u64,
+  pub at:           String,
+  /// OS account the app runs under.
+  pub user:         Option<String>,
+  /// seal | unseal | reseal | delete
+  pub op:           String,
+  pub container:    String,
+  /// As reported by tvault-core, when it names one.
+  pub container_id: Option<String>,
+  /// ok | failed | cancelled | timed_out, or refused when it stopped before
+  /// the core ran.
+  pub outcome:      String,
+  /// none | hmac | ed25519, when known.
+  pub integrity:    Option<String>,
+  pub duration_ms:  u64,
+  pub prev:         String,
+  pub hash:         String,
+}
+
+/// What callers report; the log adds the rest.
+pub(crate) struct Event {
+  pub op:           &'static str,
+  pub container:    String,
+  pub container_id: Option<String>,
+  pub outcome:      String,
+  pub integrity:    Option<String>,
+  pub duration:     Duration,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct AuditVerification {
+  pub intact:      bool,
+  /// Entries that chain correctly before the first break.
+  pub entries:     usize,
+  /// 1-based line of the first entry that breaks the chain.
+  pub broken_line: Option<usize>,
+  pub reason:      Option<String>,
+}
+
+/// `seq` and `hash` of the newest entry, as anchored outside the log.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct Head {
+  seq:  u64,
+  hash: String,
+}
+
+pub struct AuditLog {
+  file:   Option<PathBuf>,
+  /// Where the head is anchored.
+  anchor: Option<PathBuf>,
+  /// `seq` and `hash` of the newest entry.
+  head:   Mutex<(u64, String)>,
+}
+
+impl AuditLog {
+  pub fn open(file: Option<PathBuf>, anchor: Option<PathBuf>) -> Self {
+    let head = file
+      .as_deref()
+      .and_then(|f| fs::read_to_string(f).ok())
+      .and_then(|raw| raw.lines().rev().find_map(|l| serde_json::from_str::<AuditEntry>(l).ok()))
+      .map(|last| (last.seq, last.hash))
+      .unwrap_or((0, GENESIS.to_string()));
+    AuditLog { file, anchor, head: Mutex::new(head) }
+  }
+
+  pub fn for_app<R: Runtime>(app: &AppHandle<R>) -> Self {
+    let path = app.path();
+    Self::open(path.app_data_dir().ok().map(|d| d.join(AUDIT_FILE)), path.app_config_dir().ok().map(|d| d.join(HEAD_FILE)))
+  }
+
+  /// Chains `event` to the newest entry and appends it.
+  pub(crate) fn append(&self, event: Event) -> Result<AuditEntry, String> {
+    let Some(file) = &self.file else { return Err("no audit log file".into()) };
+    let mut head = self.head.lock().unwrap();
+    let mut entry = AuditEntry {
+      seq:          head.0 + 1,
+      at:           logging::timestamp(SystemTime::now()),
+      user:         std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok(),
+      op:           event.op.to_string(),
+      container:    event.container,
+      container_id: event.container_id,
+      outcome:      event.outcome,
+      integrity:    event.integrity,
+      duration_ms:  event.duration.as_millis() as u64,
+      prev:         head.1.clone(),
+      hash:         String::new(),
+    };
+    entry.hash = digest(&entry);
+    let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+
+    if let Some(dir) = file.parent() {
+      fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let mut out = OpenOptions::new().create(true).append(true).open(file).map_err(|e| e.to_string())?;
+    writeln!(out, "{line}").and_then(|_| out.sync_data()).map_err(|e| e.to_string())?;
+    *head = (entry.seq, entry.hash.clone());
+    self.write_anchor(&Head { seq: entry.seq, hash: entry.hash.clone() })?;
+    Ok(entry)
+  }
+
+  pub fn verify(&self) -> AuditVerification {
+    let _head = self.head.lock().unwrap();
+    let anchored = self.anchor.as_deref().and_then(|f| fs::read(f).ok()).and_then(|raw| serde_json::from_slice::<Head>(&raw).ok());
+    verify_head(&self.read(), anchored.as_ref())
+  }
+
+  /// Writes the log to `path` as JSON Lines (as stored) or CSV.
+  pub fn export(&self, path: &Path, format: &str) -> Result<(), String> {
+    let raw = {
+      let _head = self.head.lock().unwrap();
+      self.read()
+    };
+    let out = match format {
+      "jsonl" => raw,
+      "csv" => to_csv(&raw)?,
+      other => return Err(format!("unknown export format {other}; use jsonl or csv")),
+    };
+    fs::write(path, out).map_err(|e| format!("cannot write {}: {e}", path.display()))
+  }
+
+  fn read(&self) -> String {
+    self.file.as_deref().and_then(|f| fs::read_to_string(f).ok()).unwrap_or_default()
+  }
+
+  fn write_anchor(&self, head: &Head) -> Result<(), String> {
+    let Some(anchor) = &self.anchor else { return Ok(()) };
+    if let Some(dir) = anchor.parent() {
+      fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let tmp = anchor.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec(head).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, anchor).map_err(|e| e.to_string())
+  }
+}
+
+fn digest(entry: &AuditEntry) -> String {
+  let unhashed = AuditEntry { hash: String::new(), ..entry.clone() };
+  let mut h = Sha256::new();
+  h.update(DOMAIN);
+  h.update(serde_json::to_vec(&unhashed).unwrap_or_default());
+  h.finalize().iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Checks numbering, links and hashes line by line; stops at the first break.
+fn verify_chain(raw: &str) -> AuditVerification {
+  let mut prev = GENESIS.to_string();
+  let mut entries = 0;
+  for (i, line) in raw.lines().enumerate() {
+    let broken = |reason: String| AuditVerification { intact: false, entries, broken_line: Some(i + 1), reason: Some(reason) };
+    let entry = match serde_json::from_str::<AuditEntry>(line) {
+      Ok(entry) => entry,
+      Err(e) => return broken(format!("not an audit entry: {e}")),
+    };
+    if entry.seq != entries as u64 + 1 {
+      return broken(format!("expected entry {}, found {}", entries + 1, entry.seq));
+    }
+    if entry.prev != prev {
+      return broken("does not follow the entry before it".into());
+    }
+    if entry.hash != digest(&entry) {
+      return broken("contents do not match its hash".into());
+    }
+    prev = entry.hash;
+    entries += 1;
+  }
+  AuditVerification { intact: true, entries, broken_line: None, reason: None }
+}
+
+/// The chain check, then whether the log still ends at the anchored head.
+fn verify_head(raw: &str, anchored: Option<&Head>) -> AuditVerification {
+  let chain = verify_chain(raw);
+  if !chain.intact {
+    return chain;
+  }
+  let last = raw.lines().last().and_then(|l| serde_json::from_str::<AuditEntry>(l).ok());
+  let broken = |line: Option<usize>, reason: String| AuditVerification { intact: false, entries: chain.entries, broken_line: line, reason: Some(reason) };
+  match (anchored, last) {
+    (None, None) => chain,
+    // appended, but the anchor was not written; the chain vouches for the link
+    (None, Some(last)) if last.seq == 1 => chain,
+    (Some(head), Some(last)) if last.seq == head.seq + 1 && last.prev == head.hash => chain,
+    (None, Some(_)) => broken(None, "the head anchor is missing, so removed newest entries would go unnoticed".into()),
+    (Some(head), last) if last.as_ref().map_or(0, |e| e.seq) < head.seq => {
+      broken(Some(chain.entries + 1), format!("the log ends at entry {} of {}; the newest were removed", chain.entries, head.seq))
+    }
+    (Some(head), Some(last)) if last.seq == head.seq && last.hash == head.hash => chain,
+    _ => broken(Some(chain.entries), "does not end at the anchored head".into()),
+  }
+}
+
+fn to_csv(raw: &str) -> Result<String, String> {
+  let mut out = format!("{CSV_HEADER}\n");
+  for (i, line) in raw.lines().enumerate() {
+    let e: AuditEntry = serde_json::from_str(line).map_err(|err| format!("line {} is not an audit entry: {err}", i + 1))?;
+    let fields = [
+      e.seq.to_string(),
+      e.at,
+      e.user.unwrap_or_default(),
+      e.op,
+      e.container,
+      e.container_id.unwrap_or_default(),
+      e.outcome,
+      e.integrity.unwrap_or_default(),
+      e.duration_ms.to_string(),
+      e.prev,
+      e.hash,
+    ];
+    let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+    let _ = writeln!(out, "{}", row.join(","));
+  }
+  Ok(out)
+}
+
+/// Quoted when it holds a comma, quote or line break; a leading `=+-@` is
+/// prefixed with `'` so spreadsheets do not run it as a formula.
+fn csv_field(field: &str) -> String {
+  let field = if field.starts_with(['=', '+', '-', '@']) { format!("'{field}") } else { field.to_string() };
+  if field.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", field.replace('"', "\"\""))
+  } else {
+    field
+  }
+}
+
+/// The id tvault-core reports for a container, from a result or cached info.
+pub(crate) fn container_id(result: Option<&Value>, info: Option<Value>) -> Option<String> {
+  let id = |v: &Value| ["container_id", "id"].iter().find_map(|k| v.get(*k)?.as_str().map(str::to_string));
+  result.and_then(id).or_else(|| info.as_ref().and_then(id))
+}
+
+/// Appends `event` when the app keeps an audit log; a failed write is logged, not raised.
+pub(crate) fn record<R: Runtime>(app: &AppHandle<R>, event: Event) {
+  if let Some(audit) = app.try_state::<AuditLog>() {
+    if let Err(e) = audit.append(event) {
+      log::error!(error = e.as_str(); "cannot write the audit log");
+    }
+  }
+}
+
+/* ─────────── Commands ─────────── */
+
+/// Walks the chain and reports the first entry that was tampered with.
+#[tauri::command]
+pub fn verify_audit_log(audit: State<'_, AuditLog>) -> AuditVerification {
+  audit.verify()
+}
+
+/// `format` is `jsonl` or `csv`.
+#[tauri::command]
+pub fn export_audit_log(audit: State<'_, AuditLog>, path: String, format: String) -> Result<(), String> {
+  audit.export(Path::new(&path), &format)
+}
+
+pub fn init(app: &AppHandle<Wry>) {
+  app.manage(AuditLog::for_app(app));
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn temp_log(name: &str) -> (PathBuf, AuditLog) {
+    let file = std::env::temp_dir().join(format!("tvault-audit-{name}-{}.jsonl", std::process::id()));
+    let _ = fs::remove_file(&file);
+    let _ = fs::remove_file(anchor(&file));
+    (file.clone(), AuditLog::open(Some(file.clone()), Some(anchor(&file))))
+  }
+
+  fn anchor(file: &Path) -> PathBuf {
+    file.with_extension("head.json")
+  }
+
+  fn event(op: &'static str, container: &str, outcome: &str) -> Event {
+    Event {
+      op,
+      container: container.into(),
+      container_id: None,
+      outcome: outcome.into(),
+      integrity: Some("hmac".into()),
+      duration: Duration::from_millis(1500),
+    }
+  }
+
+  #[test]
+  fn entries_chain_across_reopening() {
+    let (file, log) = temp_log("chain");
+    let first = log.append(event("seal", "/v/box.tvlt", "ok")).unwrap();
+    assert_eq!((first.seq, first.prev.as_str(), first.duration_ms), (1, GENESIS, 1500));
+
+    let log = AuditLog::open(Some(file.clone()), Some(anchor(&file)));
+    let second = log.append(event("unseal", "/v/box.tvlt", "failed")).unwrap();
+    assert_eq!((second.seq, second.prev), (2, first.hash));
+    assert_eq!(log.verify(), AuditVerification { intact: true, entries: 2, broken_line: None, reason: None });
+    let _ = fs::remove_file(&file);
+    let _ = fs::remove_file(anchor(&file));
+  }
+
+  #[test]
+  fn dropped_or_rewritten_newest_entries_miss_the_anchor() {
+    let (file, log) = temp_log("head");
+    for op in ["seal", "unseal", "delete"] {
+      log.append(event(op, "/v/box.tvlt", "ok")).unwrap();
+    }
+    let raw = fs::read_to_string(&file).unwrap();
+    let lines: Vec<&str> = raw.lines().collect();
+
+    // a valid shorter chain
+    fs::write(&file, lines[..2].join("\n")).unwrap();
+    let check = log.verify();
+    assert_eq!((check.intact, check.entries, check.broken_line), (false, 2, Some(3)));
+    // the whole log re-hashed from scratch, one entry left out
+    let (rewritten, forged) = temp_log("head-forged");
+    forged.append(event("seal", "/v/box.tvlt", "ok")).unwrap();
+    forged.append(event("unseal", "/v/box.tvlt", "ok")).unwrap();
+    forged.append(event("seal", "/v/box.tvlt", "ok")).unwrap();
+    fs::copy(&rewritten, &file).unwrap();
+    let check = log.verify();
+    assert_eq!((check.intact, check.broken_line), (false, Some(3)));
+    assert_eq!(check.reason.as_deref(), Some("does not end at the anchored head"));
+
+    fs::write(&file, raw).unwrap();
+    assert!(log.verify().intact);
+    fs::remove_file(anchor(&file)).unwrap();
+    assert_eq!(log.verify().broken_line, None);
+    assert!(!log.verify().intact);
+    for f in [file, rewritten] {
+      let _ = fs::remove_file(anchor(&f));
+      let _ = fs::remove_file(f);
+    }
+  }
+
+  #[test]
+  fn edits_removals_and_reordering_are_detected() {
+    let (file, log) = temp_log("tamper");
+    for op in ["seal", "unseal", "reseal"] {
+      log.append(event(op, "/v/box.tvlt", "ok")).unwrap();
+    }
+    let lines: Vec<String> = fs::read_to_string(&file).unwrap().lines().map(str::to_string).collect();
+
+    let edited = lines.join("\n").replacen("\"outcome\":\"ok\"", "\"outcome\":\"failed\"", 2);
+    let check = verify_chain(&edited);
+    assert_eq!((check.intact, check.entries, check.broken_line), (false, 0, Some(1)));
+    assert_eq!(check.reason.as_deref(), Some("contents do not match its hash"));
+
+    let removed = [lines[0].as_str(), lines[2].as_str()].join("\n");
+    assert_eq!(verify_chain(&removed).broken_line, Some(2));
+    let swapped = [lines[1].as_str(), lines[0].as_str()].join("\n");
+    assert_eq!(verify_chain(&swapped).broken_line, Some(1));
+    let injected = format!("{}\n{{\"seq\":2}}", lines[0]);
+    assert_eq!(verify_chain(&injected).entries, 1);
+    let _ = fs::remove_file(anchor(&file));
+    let _ = fs::remove_file(&file);
+  }
+
+  #[test]
+  fn an_anchor_one_entry_behind_is_an_interrupted_append() {
+    let (file, log) = temp_log("behind");
+    log.append(event("seal", "/v/box.tvlt", "ok")).unwrap();
+    let after_first = fs::read(anchor(&file)).unwrap();
+    log.append(event("unseal", "/v/box.tvlt", "ok")).unwrap();
+    let after_second = fs::read(anchor(&file)).unwrap();
+    log.append(event("reseal", "/v/box.tvlt", "ok")).unwrap();
+
+    // the third line was synced but its anchor never written
+    fs::write(anchor(&file), &after_second).unwrap();
+    assert!(log.verify().intact);
+    // two behind is not one interrupted append
+    fs::write(anchor(&file), &after_first).unwrap();
+    assert_eq!(log.verify().reason.as_deref(), Some("does not end at the anchored head"));
+    // nor is a first entry missing its anchor once more entries follow it
+    fs::remove_file(anchor(&file)).unwrap();
+    assert!(!log.verify().intact);
+    let _ = fs::remove_file(&file);
+  }
+
+  #[test]
+  fn exports_csv_with_escaping() {
+    let (file, log) = temp_log("csv");
+    log.append(event("delete", "/v/a, \"b\".tvlt", "ok")).unwrap();
+    log.append(event("unseal", "=cmd|calc", "failed")).unwrap();
+    let out = file.with_extension("csv");
+    log.export(&out, "csv").unwrap();
+    let csv = fs::read_to_string(&out).unwrap();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], CSV_HEADER);
+    assert!(rows[1].contains(",delete,\"/v/a, \"\"b\"\".tvlt\",,ok,hmac,1500,"));
+    assert!(rows[2].contains(",unseal,'=cmd|calc,,failed,"));
+    assert!(log.export(&out, "xml").is_err());
+    let _ = fs::remove_file(anchor(&file));
+    let _ = fs::remove_file(&file);
+    let _ = fs::remove_file(&out);
+  }
+
+  #[test]
+  fn container_id_prefers_the_result() {
+    assert_eq!(container_id(Some(&json!({ "id": "r1" })), Some(json!({ "id": "c1" }))), Some("r1".into()));
+    assert_eq!(container_id(Some(&json!({ "name": "box" })), Some(json!({ "container_id": "c1" }))), Some("c1".into()));
+    assert_eq!(container_id(None, None), None);
+  }
+}
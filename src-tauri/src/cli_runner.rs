@@ -1,34 +1,65 @@
 //! cli_runner.rs — GUI ↔ tvault-core (encrypt/decrypt bridge)
 
 use std::{
-  env::current_exe,
-  io::{BufRead, BufReader},
+  collections::{HashMap, VecDeque},
+  fmt,
   path::PathBuf,
   process::{Command, Stdio},
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex as StdMutex,
+  },
+  time::{Duration, Instant},
 };
 
-use serde::Deserialize;
+use once_cell::sync::Lazy;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tauri::{AppHandle, Emitter, Wry};
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::{
+  io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+  process::{Child, ChildStderr, ChildStdout},
+  sync::{mpsc, oneshot},
+};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{
+  audit,
+  info_cache::InfoCache,
+  key_file::{self, RecordedKeyFile},
+  logging::SECRET_FLAGS,
+  passphrase,
+  progress::{ProgressSample, ProgressTracker},
+  scheduler::{Priority, Scheduler},
+  signing,
+  trust_store::{self, TrustStore},
+  watchdog::{Limits, Stall, TimeoutError, Timeouts, Watchdog},
+  sidecar::{locate_binary, CoreBinary},
+};
 
 /* ─────────── Encrypt/Decrypt Arguments ─────────── */
 
+// Credentials are `SecretString`s: wiped when the args are dropped, and
+// printed as `[REDACTED]` by `Debug`.
+
 #[derive(Deserialize)]
 pub struct EncryptArgs {
   name:                Option<String>,
   container_path:      String,
   folder_path:         String,
   compression_type:    String,
-  passphrase:          String,            // collected entropy
+  passphrase:          SecretString,      // collected entropy
+  key_files:           Option<Vec<String>>, // their hashes join the passphrase
   token_type:          String,            // master | share | none
   token_save_type:     String,            // file | stdout
   token_save_path:     Option<String>,
   number_of_shares:    Option<u8>,
   threshold:           Option<u8>,
-  integrity_provider:  String,            // none | hmac
-  additional_password: Option<String>,
+  integrity_provider:  String,            // none | hmac | ed25519
+  additional_password: Option<SecretString>,
+  signing_key_id:      Option<String>,    // ed25519: key from the key store
+  signing_passphrase:  Option<SecretString>, // ed25519: unlocks that key
   comment:             Option<String>,
   tags:                Option<String>,
 }
@@ -37,13 +68,15 @@ pub struct EncryptArgs {
 pub struct DecryptArgs {
   container_path:      String,
   folder_path:         String,
-  token:               Option<String>,           // direct passphrase (no tokens)
-  master_token:        Option<String>,           // master token for master+password flow
+  token:               Option<SecretString>,     // direct passphrase (no tokens)
+  key_files:           Option<Vec<String>>,      // combined with the passphrase
+  master_token:        Option<SecretString>,     // decoded master token, alone or with the password
   token_reader_type:   Option<String>,          // flag | file
   token_format:        Option<String>,          // json | plaintext
-  token_flag:          Option<String>,          // value when type=flag
+  token_flag:          Option<SecretString>,    // value when type=flag
   token_path:          Option<String>,          // path when type=file
-  additional_password: Option<String>,          // integrity current passphrase
+  additional_password: Option<SecretString>,    // integrity current passphrase
+  public_key:          Option<String>,          // ed25519 signer to verify against
 }
 
 #[derive(Deserialize)]
@@ -58,504 +91,991 @@ pub struct ResealArgs {
   new_path:            Option<String>,
   folder_path:         String,
   name:                Option<String>,
-  passphrase:          Option<String>,
+  passphrase:          Option<SecretString>,
+  current_key_files:   Option<Vec<String>>,      // the container was opened with (type=none)
+  key_files:           Option<Vec<String>>,      // sealed with from now on; none given keeps the current ones
   comment:             Option<String>,
   tags:                Option<String>,
   integrity_provider:  Option<String>,   // none | hmac | ed25519
-  current_integrity_password: Option<String>,
-  new_integrity_password: Option<String>,
-  master_token:        Option<String>,
-  shares:              Option<Vec<String>>,
+  current_integrity_password: Option<SecretString>,
+  new_integrity_password: Option<SecretString>,
+  master_token:        Option<SecretString>,
+  shares:              Option<Vec<SecretString>>,
   token_type:          Option<String>,
   token_json_path:     Option<String>,
+  signing_key_id:      Option<String>,
+  signing_passphrase:  Option<SecretString>,
+  /// What the container opens with when it differs from `passphrase`: the
+  /// password combined with `current_key_files`.
+  #[serde(skip)]
+  current_passphrase:  Option<SecretString>,
 }
 
 /* ─────────── Public Commands ─────────── */
 
+// Commands that start an operation return its sequence number for
+// `cancel_operation`; while it waits for a slot, `{prefix}-queued` carries it.
+
 #[tauri::command]
-pub async fn run_encrypt(app: AppHandle<Wry>, args: EncryptArgs) -> Result<(), String> {
-  validate_encrypt(&args)?;
-  let cmd = build_seal_cmd(args)?;
-  spawn_process(app, cmd, "encrypt")
+pub async fn run_encrypt<R: Runtime>(app: AppHandle<R>, mut args: EncryptArgs) -> Result<u64, String> {
+  let prepared = prepare_seal(&app, &mut args).await;
+  let (core, inv, op) = refused(&app, "seal", &args.container_path, Some(&args.integrity_provider), prepared)?;
+  run_operation(app, core, inv, op).await
+}
+
+async fn prepare_seal<R: Runtime>(app: &AppHandle<R>, args: &mut EncryptArgs) -> Result<(CoreBinary, CoreInvocation, Op), String> {
+  validate_encrypt(args)?;
+  let key_files = args.key_files.take().unwrap_or_default();
+  // a key file carries the strength, whatever the password is
+  if key_files.is_empty() {
+    passphrase::enforce_policy(app, "passphrase", args.passphrase.expose_secret())?;
+  }
+  if let Some(add) = expose(&args.additional_password).filter(|_| args.integrity_provider == "hmac") {
+    passphrase::enforce_policy(app, "integrity password", add)?;
+  }
+  args.passphrase = key_file::combine(args.passphrase.expose_secret(), &key_files)?.into();
+  let recorded = key_file::records(&key_files)?;
+  let core = locate_binary(app)?;
+  let mut inv = build_seal_cmd(args);
+  if args.integrity_provider == "ed25519" {
+    inv.stdin = Some(signing_key(app, &args.signing_key_id, &args.signing_passphrase).await?);
+  }
+  let op = Op::bulk("encrypt", [&args.container_path]).audited("seal", Some(&args.integrity_provider)).recording(&args.container_path, recorded);
+  Ok((core, inv, op))
 }
 
 #[tauri::command]
-pub async fn run_decrypt(app: AppHandle<Wry>, args: DecryptArgs) -> Result<(), String> {
+pub async fn run_decrypt<R: Runtime>(app: AppHandle<R>, mut args: DecryptArgs) -> Result<u64, String> {
+  let prepared = prepare_unseal(&app, &mut args).await;
+  let (core, inv, op) = refused(&app, "unseal", &args.container_path, unseal_integrity(&args), prepared)?;
+  run_operation(app, core, inv, op).await
+}
+
+async fn prepare_unseal<R: Runtime>(app: &AppHandle<R>, args: &mut DecryptArgs) -> Result<(CoreBinary, CoreInvocation, Op), String> {
+  if let Some(files) = args.key_files.take().filter(|f| !f.is_empty()) {
+    args.token = Some(key_file::combine(expose(&args.token).unwrap_or_default(), &files)?.into());
+  }
+  let core = locate_binary(app)?;
+  if trust_store::requires_trusted_signer(app) {
+    let info = info_once(app, core.clone(), args.container_path.clone(), Priority::Interactive).await?;
+    if let Some(err) = info.get("error") {
+      return Err(err["message"].as_str().unwrap_or("cannot read container info").to_string());
+    }
+    if !trust_store::is_signed(&info["data"]) {
+      return Err("refusing to unseal: container is not signed".into());
+    }
+    trust_store::require_trusted(app, args.public_key.as_deref(), "unseal")?;
+  }
   // Ensure target folder exists; create recursively
   let folder = args.folder_path.clone();
   if let Err(e) = std::fs::create_dir_all(&folder) {
     // If cannot create, still try to run — CLI may create itself
     let _ = app.emit("decrypt-stderr", format!("failed to create folder {folder}: {e}"));
   }
-  let cmd = build_unseal_cmd(args)?;
-  spawn_process(app, cmd, "decrypt")
+  let op = Op::bulk("decrypt", [&args.container_path]).audited("unseal", unseal_integrity(args)).checking(args.public_key.clone());
+  Ok((core, build_unseal_cmd(args), op))
+}
+
+fn unseal_integrity(args: &DecryptArgs) -> Option<&'static str> {
+  if args.public_key.is_some() { Some("ed25519") } else { args.additional_password.as_ref().map(|_| "hmac") }
+}
+
+#[tauri::command]
+pub async fn run_container_info<R: Runtime>(app: AppHandle<R>, args: ContainerInfoArgs) -> Result<u64, String> {
+  if let Some(data) = cached_info(&app, &args.path) {
+    let _ = app.emit("info-result", info_data(&app, &args.path, data));
+    let _ = app.emit("info-done", true);
+    // nothing is running, so there is nothing to cancel under this number
+    return Ok(OP_SEQ.fetch_add(1, Ordering::SeqCst));
+  }
+  let core = locate_binary(&app)?;
+  run_operation(app, core, build_container_info_cmd(&args), Op::info(&args.path)).await
 }
 
+/// `{path, data}` on success, `{path, error, stderr?}` when the core reports an error.
 #[tauri::command]
-pub async fn run_container_info(app: AppHandle<Wry>, args: ContainerInfoArgs) -> Result<(), String> {
-  let path_ctx = args.path.clone();
-  let cmd = build_container_info_cmd(args)?;
-  // custom spawn to attach context.path into result
-  spawn_process_with_context(app, cmd, "info", Some(path_ctx))
+pub async fn container_info_once<R: Runtime>(app: AppHandle<R>, args: ContainerInfoArgs) -> Result<serde_json::Value, String> {
+  let core = locate_binary(&app)?;
+  info_once(&app, core, args.path, Priority::Interactive).await
 }
 
+/// Info for many containers at once, in the order given. Runs behind interactive
+/// requests; each entry is shaped like `container_info_once`, failures included.
 #[tauri::command]
-pub async fn container_info_once(args: ContainerInfoArgs) -> Result<serde_json::Value, String> {
-  use std::io::Read;
-  let path_ctx = args.path.clone();
-  let mut cmd = build_container_info_cmd(args)?;
-  #[cfg(target_os = "windows")]
-  { cmd.creation_flags(0x08000000); } // CREATE_NO_WINDOW
-  let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn().map_err(|e| e.to_string())?;
-  let mut stdout = String::new();
-  if let Some(mut out) = child.stdout.take() { let _ = out.read_to_string(&mut stdout); }
-  let _ = child.wait();
-
-  // Try parse as JSON object, or slice {...}
-  let classify_emit = |val: Value| -> Value {
-    let is_err = val.get("code").is_some() && val.get("message").is_some();
-    if is_err { serde_json::json!({ "path": path_ctx, "error": val }) }
-    else { serde_json::json!({ "path": path_ctx, "data": val }) }
-  };
+pub async fn container_info_many<R: Runtime>(app: AppHandle<R>, paths: Vec<String>) -> Result<Vec<serde_json::Value>, String> {
+  let core = locate_binary(&app)?;
+  fan_out(paths, INFO_BATCH_CONCURRENCY, move |path: String| {
+    let (app, core) = (app.clone(), core.clone());
+    async move {
+      info_once(&app, core, path.clone(), Priority::Bulk).await.unwrap_or_else(|message| serde_json::json!({
+        "path": path,
+        "error": { "code": "invalid_output", "message": message },
+      }))
+    }
+  })
+  .await
+}
 
-  if let Ok(val) = serde_json::from_str::<Value>(&stdout) {
-    return Ok(classify_emit(val));
+async fn info_once<R: Runtime>(app: &AppHandle<R>, core: CoreBinary, path: String, priority: Priority) -> Result<Value, String> {
+  if let Some(data) = cached_info(app, &path) {
+    return Ok(info_data(app, &path, data));
+  }
+  let inv = build_container_info_cmd(&ContainerInfoArgs { path: path.clone() });
+  // before the core reads the file, so a rewrite while it runs shows as a mismatch
+  let stamp = app.try_state::<InfoCache>().and_then(|cache| cache.stamp(&path));
+  let run = run_collect(app, core, inv, vec![PathBuf::from(&path)], priority, timeouts(app).info).await?;
+  if let Some(err) = run.timeout {
+    return Ok(with_stderr(serde_json::json!({ "path": path, "error": err }), &run.stderr));
   }
-  if let (Some(s), Some(e)) = (stdout.find('{'), stdout.rfind('}')) {
-    if e >= s { if let Ok(val) = serde_json::from_str::<Value>(&stdout[s..=e]) { return Ok(classify_emit(val)); } }
+  match run.result {
+    Some(val) if is_core_error(&val) => Ok(with_stderr(serde_json::json!({ "path": path, "error": val }), &run.stderr)),
+    Some(val) => {
+      if let (Some(stamp), Some(cache)) = (stamp, app.try_state::<InfoCache>()) {
+        cache.put(stamp, val.clone());
+      }
+      Ok(info_data(app, &path, val))
+    }
+    None if run.stderr.trim().is_empty() => Err("invalid info output".into()),
+    None => Err(format!("invalid info output: {}", run.stderr.trim())),
   }
-  Err("invalid info output".into())
+}
+
+fn cached_info<R: Runtime>(app: &AppHandle<R>, path: &str) -> Option<Value> {
+  app.try_state::<InfoCache>()?.get(path)
+}
+
+/// `{path, data}`, plus who signed the container when the app keeps a trust store.
+fn info_data<R: Runtime>(app: &AppHandle<R>, path: &str, data: Value) -> Value {
+  let signer = trust_store::annotate(app, &data);
+  let mut payload = serde_json::json!({ "path": path, "data": data });
+  if let Some(signer) = signer {
+    payload["signer"] = serde_json::to_value(signer).unwrap_or_default();
+  }
+  payload
+}
+
+fn with_stderr(mut payload: Value, stderr: &str) -> Value {
+  if !stderr.trim().is_empty() {
+    payload["stderr"] = Value::String(stderr.trim_end().to_string());
+  }
+  payload
 }
 
 #[tauri::command]
-pub async fn run_reseal(app: AppHandle<Wry>, args: ResealArgs) -> Result<(), String> {
-  let cmd = build_reseal_cmd(args)?;
-  spawn_process(app, cmd, "reseal")
+pub async fn run_reseal<R: Runtime>(app: AppHandle<R>, mut args: ResealArgs) -> Result<u64, String> {
+  let prepared = prepare_reseal(&app, &mut args).await;
+  let (core, inv, op) = refused(&app, "reseal", &args.current_path, args.integrity_provider.as_deref(), prepared)?;
+  run_operation(app, core, inv, op).await
+}
+
+async fn prepare_reseal<R: Runtime>(app: &AppHandle<R>, args: &mut ResealArgs) -> Result<(CoreBinary, CoreInvocation, Op), String> {
+  // neither list given leaves what was recorded for the container
+  let known_files = args.current_key_files.is_some() || args.key_files.is_some();
+  let current_files = args.current_key_files.take().unwrap_or_default();
+  let new_files = args.key_files.take().unwrap_or_else(|| current_files.clone());
+  // only passwords chosen now are held to the policy: a passphrase-only
+  // container is opened with the passphrase it is resealed with
+  if args.token_type.as_deref() != Some("none") && new_files.is_empty() {
+    if let Some(pass) = expose(&args.passphrase) {
+      passphrase::enforce_policy(app, "passphrase", pass)?;
+    }
+  }
+  let new_integrity = expose(&args.new_integrity_password).filter(|p| Some(*p) != expose(&args.current_integrity_password));
+  if let Some(pass) = new_integrity.filter(|_| args.integrity_provider.as_deref() == Some("hmac")) {
+    passphrase::enforce_policy(app, "integrity password", pass)?;
+  }
+  let password = Zeroizing::new(expose(&args.passphrase).unwrap_or_default().to_string());
+  if args.token_type.as_deref() == Some("none") && current_files != new_files {
+    args.current_passphrase = Some(key_file::combine(&password, &current_files)?.into());
+  }
+  if !new_files.is_empty() {
+    args.passphrase = Some(key_file::combine(&password, &new_files)?.into());
+  }
+  let recorded = if known_files { Some(key_file::records(&new_files)?) } else { None };
+  let signed = args.integrity_provider.as_deref() == Some("ed25519");
+  // a container the policy would refuse to unseal must not be written
+  if trust_store::requires_trusted_signer(app) {
+    let public_key = args.signing_key_id.as_deref().filter(|_| signed).and_then(|id| signing::public_key(app, id));
+    trust_store::require_trusted(app, public_key.as_deref(), "reseal")?;
+  }
+  let core = locate_binary(app)?;
+  let mut inv = build_reseal_cmd(args);
+  if signed {
+    inv.stdin = Some(signing_key(app, &args.signing_key_id, &args.signing_passphrase).await?);
+  }
+  let targets = [Some(&args.current_path), args.new_path.as_ref()];
+  let mut op = Op::bulk("reseal", targets.into_iter().flatten()).audited("reseal", args.integrity_provider.as_deref());
+  if let Some(recorded) = recorded {
+    op = op.recording(args.new_path.as_ref().unwrap_or(&args.current_path), recorded);
+  }
+  Ok((core, inv, op))
+}
+
+/// Unlocks the chosen signing key for a signed seal or reseal, off
+/// the async workers since Argon2 takes a while.
+async fn signing_key<R: Runtime>(app: &AppHandle<R>, id: &Option<String>, passphrase: &Option<SecretString>) -> Result<String, String> {
+  let (Some(id), Some(pass)) = (id.clone(), passphrase.clone()) else {
+    return Err("signing_key_id and signing_passphrase are required when integrity_provider=ed25519".into());
+  };
+  let app = app.clone();
+  tauri::async_runtime::spawn_blocking(move || signing::core_private_key(&app, &id, pass.expose_secret())).await.map_err(|e| e.to_string())?
+}
+
+/// Audits a command that stopped before its operation was queued as
+/// `refused`, then hands its error on.
+fn refused<R: Runtime, T>(app: &AppHandle<R>, op: &'static str, container: &str, integrity: Option<&str>, prepared: Result<T, String>) -> Result<T, String> {
+  if prepared.is_err() {
+    let container_id = audit::container_id(None, cached_info(app, container));
+    let (container, integrity) = (container.to_string(), integrity.map(str::to_string));
+    audit::record(app, audit::Event { op, container, container_id, outcome: "refused".into(), integrity, duration: Duration::ZERO });
+  }
+  prepared
+}
+
+fn expose(secret: &Option<SecretString>) -> Option<&str> {
+  secret.as_ref().map(|s| s.expose_secret())
+}
+
+/// Shares as one flag value; the joined copy is wiped once it is in the argv.
+fn joined(shares: &[SecretString]) -> Zeroizing<String> {
+  Zeroizing::new(shares.iter().map(|s| s.expose_secret()).collect::<Vec<_>>().join("|"))
+}
+
+/* ─────────── CLI Invocation Building ─────────── */
+
+/// Everything tvault-core is started with. Built by pure functions so the
+/// argv can be covered by snapshot tests without spawning anything.
+///
+/// The argv and stdin carry credentials: they are wiped on drop and left out
+/// of `Debug`.
+#[derive(PartialEq)]
+#[cfg_attr(test, derive(Serialize))]
+pub(crate) struct CoreInvocation {
+  argv:  Vec<String>,
+  env:   Vec<(String, String)>,
+  stdin: Option<String>,
+}
+
+impl CoreInvocation {
+  pub(crate) fn new<I: IntoIterator<Item = S>, S: Into<String>>(argv: I) -> Self {
+    CoreInvocation { argv: argv.into_iter().map(Into::into).collect(), env: Vec::new(), stdin: None }
+  }
+
+  pub(crate) fn arg(&mut self, a: impl Into<String>) {
+    self.argv.push(a.into());
+  }
+
+  pub(crate) fn args<I: IntoIterator<Item = S>, S: Into<String>>(&mut self, args: I) {
+    self.argv.extend(args.into_iter().map(Into::into));
+  }
+
+  fn command(&self, core: &CoreBinary) -> Command {
+    let mut c = core.command();
+    c.args(&self.argv);
+    c.envs(self.env.iter().map(|(k, v)| (k, v)));
+    c
+  }
 }
 
-/* ─────────── CLI Command Building ─────────── */
+impl Drop for CoreInvocation {
+  fn drop(&mut self) {
+    self.argv.zeroize();
+    for (k, v) in &mut self.env {
+      k.zeroize();
+      v.zeroize();
+    }
+    self.stdin.zeroize();
+  }
+}
+
+impl fmt::Debug for CoreInvocation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let argv: Vec<String> = self
+      .argv
+      .iter()
+      .map(|a| match SECRET_FLAGS.iter().find(|flag| a.starts_with(*flag)) {
+        Some(flag) => format!("{flag}[REDACTED]"),
+        None => a.clone(),
+      })
+      .collect();
+    let env: Vec<&str> = self.env.iter().map(|(k, _)| k.as_str()).collect();
+    f.debug_struct("CoreInvocation").field("argv", &argv).field("env", &env).field("stdin", &self.stdin.as_ref().map(|_| "[REDACTED]")).finish()
+  }
+}
 
-fn build_seal_cmd(a: EncryptArgs) -> Result<Command, String> {
-  let mut c = Command::new(locate_binary()?);
+fn build_seal_cmd(a: &EncryptArgs) -> CoreInvocation {
   // tvault-core new CLI: seal container ... subcommands
+  let mut c = CoreInvocation::new(["seal", "container"]);
+  if let Some(name) = &a.name { c.arg(format!("-name={name}")); }
   c.args([
-    "seal",
-    "container",
-  ]);
-  if let Some(name) = a.name { c.arg(format!("-name={name}")); }
-  c.args([
-    &format!("-new-path={}", a.container_path),
-    &format!("-folder-path={}", a.folder_path),
-    &format!("-passphrase={}", a.passphrase),
+    format!("-new-path={}", a.container_path),
+    format!("-folder-path={}", a.folder_path),
+    format!("-passphrase={}", a.passphrase.expose_secret()),
   ]);
-  if let Some(cmt) = a.comment { c.arg(format!("-comment={cmt}")); }
-  if let Some(tgs) = a.tags { c.arg(format!("-tags={tgs}")); }
+  if let Some(cmt) = &a.comment { c.arg(format!("-comment={cmt}")); }
+  if let Some(tgs) = &a.tags { c.arg(format!("-tags={tgs}")); }
   c.args([
-    "compression",
-    &format!("-type={}", a.compression_type),
-    "token",
-    &format!("-type={}", a.token_type),
-    "token-writer",
-    &format!("-type={}", a.token_save_type),
-    "-format=json",
+    "compression".to_string(),
+    format!("-type={}", a.compression_type),
+    "token".to_string(),
+    format!("-type={}", a.token_type),
+    "token-writer".to_string(),
+    format!("-type={}", a.token_save_type),
+    "-format=json".to_string(),
   ]);
-  if let Some(p) = a.token_save_path {
+  if let Some(p) = &a.token_save_path {
     c.arg(format!("-path={p}"));
   }
   if a.token_type == "share" {
-      let n = a.number_of_shares.unwrap_or(5);
-      let k = a.threshold.unwrap_or(3);
-    c.args(["shamir", &format!("-is-enabled={}", true.to_string()), &format!("-shares={n}"), &format!("-threshold={k}")]);
+    let n = a.number_of_shares.unwrap_or(5);
+    let k = a.threshold.unwrap_or(3);
+    c.args(["shamir".to_string(), "-is-enabled=true".to_string(), format!("-shares={n}"), format!("-threshold={k}")]);
   } else {
-    c.args(["shamir", &format!("-is-enabled={}", false.to_string())]);
+    c.args(["shamir", "-is-enabled=false"]);
   }
-  c.args(["integrity-provider", &format!("-type={}", a.integrity_provider)]);
+  c.args(["integrity-provider".to_string(), format!("-type={}", a.integrity_provider)]);
   if a.integrity_provider == "hmac" {
-      if let Some(add) = a.additional_password {
+    if let Some(add) = expose(&a.additional_password) {
       c.arg(format!("-new-passphrase={add}"));
-      }
+    }
+  } else if a.integrity_provider == "ed25519" {
+    // the unlocked key follows on stdin
+    c.arg("-private-key=stdin");
   }
   c.args(["log-writer", "-type=stdout", "-format=json"]);
-  Ok(c)
+  c
 }
 
-fn build_unseal_cmd(a: DecryptArgs) -> Result<Command, String> {
-  let mut c = Command::new(locate_binary()?);
-  c.args([
-    "unseal",
-    "container",
-    &format!("-current-path={}", a.container_path),
-    &format!("-folder-path={}", a.folder_path),
+fn build_unseal_cmd(a: &DecryptArgs) -> CoreInvocation {
+  let mut c = CoreInvocation::new([
+    "unseal".to_string(),
+    "container".to_string(),
+    format!("-current-path={}", a.container_path),
+    format!("-folder-path={}", a.folder_path),
   ]);
 
-  // If both master token and password are provided, use both parameters
-  if a.token.is_some() && a.master_token.is_some() {
-    if let (Some(pass), Some(master)) = (a.token.as_ref(), a.master_token.as_ref()) {
-      c.arg(format!("-passphrase={pass}"));
-      c.args(["token-reader", "-type=flag", "-format=plaintext", &format!("-flag={}", master)]);
-    }
+  if let (Some(pass), Some(master)) = (expose(&a.token), expose(&a.master_token)) {
+    // If both master token and password are provided, use both parameters
+    c.arg(format!("-passphrase={pass}"));
+    c.args(["token-reader".to_string(), "-type=flag".to_string(), "-format=plaintext".to_string(), format!("-flag={master}")]);
+  } else if let Some(master) = expose(&a.master_token) {
+    // master token only
+    c.args(["token-reader".to_string(), "-type=flag".to_string(), "-format=plaintext".to_string(), format!("-flag={master}")]);
   } else if let Some(reader_type) = a.token_reader_type.as_deref() {
     // Use token-reader for shares or master token only
     match (reader_type, a.token_format.as_deref()) {
       ("flag", Some(fmt)) => {
-        let flag = a.token_flag.unwrap_or_default();
-        c.args(["token-reader", "-type=flag", &format!("-format={}", fmt), &format!("-flag={}", flag)]);
+        let flag = expose(&a.token_flag).unwrap_or_default();
+        c.args(["token-reader".to_string(), "-type=flag".to_string(), format!("-format={fmt}"), format!("-flag={flag}")]);
       }
       ("file", Some(fmt)) => {
-        let path = a.token_path.unwrap_or_default();
-        c.args(["token-reader", "-type=file", &format!("-format={}", fmt), &format!("-path={}", path)]);
+        let path = a.token_path.as_deref().unwrap_or_default();
+        c.args(["token-reader".to_string(), "-type=file".to_string(), format!("-format={fmt}"), format!("-path={path}")]);
       }
       _ => {
         // If format is not specified but token exists, use passphrase
-        if let Some(pass) = a.token { 
-          c.arg(format!("-passphrase={pass}")); 
+        if let Some(pass) = expose(&a.token) {
+          c.arg(format!("-passphrase={pass}"));
         }
       }
     }
-  } else if a.token.is_some() {
+  } else if let Some(pass) = expose(&a.token) {
     // If only password is provided (type=none), use passphrase
-    if let Some(pass) = a.token { 
-      c.arg(format!("-passphrase={pass}"));
-      // For type=none, password must be passed both in passphrase and flag
-      c.args(["token-reader", "-type=flag", "-format=plaintext", &format!("-flag={}", pass)]);
-    }
+    c.arg(format!("-passphrase={pass}"));
+    // For type=none, password must be passed both in passphrase and flag
+    c.args(["token-reader".to_string(), "-type=flag".to_string(), "-format=plaintext".to_string(), format!("-flag={pass}")]);
   }
 
-  if let Some(p) = a.additional_password {
-    c.args(["integrity-provider", &format!("-current-passphrase={p}")]);
+  if let Some(key) = &a.public_key {
+    c.args(["integrity-provider".to_string(), "-type=ed25519".to_string(), format!("-public-key={key}")]);
+  } else if let Some(p) = expose(&a.additional_password) {
+    c.args(["integrity-provider".to_string(), format!("-current-passphrase={p}")]);
   }
 
   c.args(["log-writer", "-type=stdout", "-format=json"]);
-  Ok(c)
+  c
 }
 
-fn build_container_info_cmd(a: ContainerInfoArgs) -> Result<Command, String> {
-  let mut c = Command::new(locate_binary()?);
-  c.args([
-    "container",
-    "info",
-    &format!("-path={}", a.path),
-    "info-writer",
-    "-type=stdout",
-    "-format=json",
-    "log-writer",
-    "-type=stdout",
-    "-format=json",
-  ]);
-  Ok(c)
+fn build_container_info_cmd(a: &ContainerInfoArgs) -> CoreInvocation {
+  CoreInvocation::new([
+    "container".to_string(),
+    "info".to_string(),
+    format!("-path={}", a.path),
+    "info-writer".to_string(),
+    "-type=stdout".to_string(),
+    "-format=json".to_string(),
+    "log-writer".to_string(),
+    "-type=stdout".to_string(),
+    "-format=json".to_string(),
+  ])
 }
 
-fn build_reseal_cmd(a: ResealArgs) -> Result<Command, String> {
-  let mut c = Command::new(locate_binary()?);
-  c.args([
-    "reseal",
-    "container",
-    &format!("-current-path={}", a.current_path),
-    &format!("-folder-path={}", a.folder_path),
-  ]);
-  
-  let mut cmd_args = vec![
+fn build_reseal_cmd(a: &ResealArgs) -> CoreInvocation {
+  let mut c = CoreInvocation::new([
     "reseal".to_string(),
     "container".to_string(),
     format!("-current-path={}", a.current_path),
     format!("-folder-path={}", a.folder_path),
-  ];
-  
-  if let Some(ref newp) = a.new_path { 
-    c.arg(format!("-new-path={newp}")); 
-    cmd_args.push(format!("-new-path={newp}"));
-  }
-  if let Some(ref name) = a.name { 
-    c.arg(format!("-name={name}")); 
-    cmd_args.push(format!("-name={name}"));
-  }
-  if let Some(ref pf) = a.passphrase { 
-    c.arg(format!("-passphrase={pf}")); 
-    cmd_args.push(format!("-passphrase={pf}"));
-  }
-  if let Some(ref cmt) = a.comment { 
-    c.arg(format!("-comment={cmt}")); 
-    cmd_args.push(format!("-comment={cmt}"));
-  }
-  if let Some(ref tags) = a.tags { 
-    c.arg(format!("-tags={tags}")); 
-    cmd_args.push(format!("-tags={tags}"));
-  }
-
-  if let Some(ref token_type) = a.token_type {
-    match token_type.as_str() {
-      "master" => {
-        if let Some(ref master_token) = a.master_token {
-          c.args(["token-reader", "-type=flag", "-format=plaintext", &format!("-flag={}", master_token)]);
-          cmd_args.push("token-reader".to_string());
-          cmd_args.push("-type=flag".to_string());
-          cmd_args.push("-format=plaintext".to_string());
-          cmd_args.push(format!("-flag={}", master_token));
-        }
-      },
-      "share" => {
-        if let Some(ref token_json_path) = a.token_json_path {
-          c.args(["token-reader", "-type=file", "-format=json", &format!("-path={}", token_json_path)]);
-          cmd_args.push("token-reader".to_string());
-          cmd_args.push("-type=file".to_string());
-          cmd_args.push("-format=json".to_string());
-          cmd_args.push(format!("-path={}", token_json_path));
-        } else if let Some(ref shares) = a.shares {
-          let shares_str = shares.join("|");
-          c.args(["token-reader", "-type=flag", "-format=plaintext", &format!("-flag={}", shares_str)]);
-          cmd_args.push("token-reader".to_string());
-          cmd_args.push("-type=flag".to_string());
-          cmd_args.push("-format=plaintext".to_string());
-          cmd_args.push(format!("-flag={}", shares_str));
-        }
-      },
-      "none" => {
-        if let Some(ref pf) = a.passphrase {
-          c.args(["token-reader", "-type=flag", "-format=plaintext", &format!("-flag={}", pf)]);
-          cmd_args.push("token-reader".to_string());
-          cmd_args.push("-type=flag".to_string());
-          cmd_args.push("-format=plaintext".to_string());
-          cmd_args.push(format!("-flag={}", pf));
-        }
-        println!("[tvault] Using passphrase-only mode (type=none)");
-      },
-      _ => {}
+  ]);
+
+  if let Some(newp) = &a.new_path { c.arg(format!("-new-path={newp}")); }
+  if let Some(name) = &a.name { c.arg(format!("-name={name}")); }
+  if let Some(pf) = expose(&a.passphrase) { c.arg(format!("-passphrase={pf}")); }
+  if let Some(cmt) = &a.comment { c.arg(format!("-comment={cmt}")); }
+  if let Some(tags) = &a.tags { c.arg(format!("-tags={tags}")); }
+
+  let flag_reader = |c: &mut CoreInvocation, flag: &str| {
+    c.args(["token-reader".to_string(), "-type=flag".to_string(), "-format=plaintext".to_string(), format!("-flag={flag}")]);
+  };
+  match a.token_type.as_deref() {
+    Some("master") => {
+      if let Some(master_token) = expose(&a.master_token) {
+        flag_reader(&mut c, master_token);
+      }
     }
+    Some("share") => {
+      if let Some(token_json_path) = &a.token_json_path {
+        c.args(["token-reader".to_string(), "-type=file".to_string(), "-format=json".to_string(), format!("-path={token_json_path}")]);
+      } else if let Some(shares) = &a.shares {
+        flag_reader(&mut c, &joined(shares));
+      }
+    }
+    Some("none") => {
+      if let Some(pf) = expose(&a.current_passphrase).or(expose(&a.passphrase)) {
+        flag_reader(&mut c, pf);
+      }
+      log::debug!("reseal: passphrase-only mode (type=none)");
+    }
+    _ => {}
   }
 
-  if let Some(ref ip) = a.integrity_provider {
-    if ip != "none" {
-      c.args(["integrity-provider"]);
-      cmd_args.push("integrity-provider".to_string());
-    }
+  let signed = a.integrity_provider.as_deref() == Some("ed25519");
+  if a.integrity_provider.as_deref().is_some_and(|ip| ip != "none") {
+    c.arg("integrity-provider");
   }
-  if let Some(ref cur) = a.current_integrity_password { 
-    c.arg(format!("-current-passphrase={cur}")); 
-    cmd_args.push(format!("-current-passphrase={cur}"));
+  if signed {
+    c.args(["-type=ed25519", "-private-key=stdin"]);
   }
-  if let Some(ref new) = a.new_integrity_password { 
-    c.arg(format!("-new-passphrase={new}")); 
-    cmd_args.push(format!("-new-passphrase={new}"));
-  } else if let Some(ref cur) = a.current_integrity_password {
-    c.arg(format!("-new-passphrase={cur}")); 
-    cmd_args.push(format!("-new-passphrase={cur}"));
+  if let Some(cur) = expose(&a.current_integrity_password) {
+    c.arg(format!("-current-passphrase={cur}"));
+  }
+  // keep the current integrity password unless a new one was given
+  if let Some(new) = expose(&a.new_integrity_password).or(expose(&a.current_integrity_password)).filter(|_| !signed) {
+    c.arg(format!("-new-passphrase={new}"));
   }
   c.args(["log-writer", "-type=stdout", "-format=json"]);
-  cmd_args.push("log-writer".to_string());
-  cmd_args.push("-type=stdout".to_string());
-  cmd_args.push("-format=json".to_string());
-  
-  Ok(c)
+  c
 }
 
-/* ─────────── Process Execution & Events ─────────── */
+/* ─────────── Operations ─────────── */
 
-fn spawn_process(app: AppHandle<Wry>, mut cmd: Command, prefix: &'static str) -> Result<(), String> {
-  #[cfg(target_os = "windows")]
-  { cmd.creation_flags(0x08000000); } // CREATE_NO_WINDOW
-  let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn().map_err(|e| e.to_string())?;
-  let stdout = child.stdout.take().ok_or("cannot capture stdout")?;
-  let stderr = child.stderr.take().ok_or("cannot capture stderr")?;
+/// One step of a running tvault-core process.
+pub(crate) enum Frame {
+  Stdout(String),
+  Stderr(String),
+  Progress(ProgressSample),
+  Result(Value),
+  Exit(bool),
+}
+
+enum Cancel {
+  /// Still waiting for the scheduler.
+  Queued(oneshot::Sender<()>),
+  Process(oneshot::Sender<()>),
+}
+
+/// How many tvault-core operations may run at once.
+const MAX_CONCURRENT: usize = 3;
+/// Info requests `container_info_many` keeps in flight; below `MAX_CONCURRENT`
+/// so a batch never occupies every slot.
+const INFO_BATCH_CONCURRENCY: usize = 2;
+
+/// Running or queued operations by sequence number, with their event prefix.
+static RUNNING: Lazy<StdMutex<HashMap<u64, (&'static str, Cancel)>>> = Lazy::new(Default::default);
+static OP_SEQ: AtomicU64 = AtomicU64::new(0);
+static SCHEDULER: Lazy<Scheduler> = Lazy::new(|| Scheduler::new(MAX_CONCURRENT));
+
+/// How an operation is announced to the UI and scheduled.
+struct Op {
+  prefix:       &'static str,
+  priority:     Priority,
+  /// Containers the operation reads or writes; held exclusively while it runs.
+  paths:        Vec<PathBuf>,
+  /// Attached to results and errors as `path` (info only).
+  context_path: Option<String>,
+  /// Audit log name and integrity provider; the entry is for the first path.
+  audit:        Option<(&'static str, Option<String>)>,
+  /// Public key the core checks the signature against; announced as the signer on success.
+  signer_key:   Option<String>,
+  /// Container and the key files it is sealed with; recorded on success.
+  key_files:    Option<(String, Vec<RecordedKeyFile>)>,
+}
+
+impl Op {
+  fn bulk<'a>(prefix: &'static str, paths: impl IntoIterator<Item = &'a String>) -> Self {
+    Op { prefix, priority: Priority::Bulk, paths: paths.into_iter().map(PathBuf::from).collect(), context_path: None, audit: None, signer_key: None, key_files: None }
+  }
+
+  fn info(path: &str) -> Self {
+    Op { prefix: "info", priority: Priority::Interactive, paths: vec![PathBuf::from(path)], context_path: Some(path.to_string()), audit: None, signer_key: None, key_files: None }
+  }
+
+  /// Records the outcome in the audit log as `op`.
+  fn audited(self, op: &'static str, integrity: Option<&str>) -> Self {
+    Op { audit: Some((op, integrity.map(str::to_string))), ..self }
+  }
+
+  /// Emits `{prefix}-signer` when the core succeeds with this key to check against.
+  fn checking(self, public_key: Option<String>) -> Self {
+    Op { signer_key: public_key, ..self }
+  }
+
+  /// Records `key_files` for `container` when the core succeeds.
+  fn recording(self, container: &str, key_files: Vec<RecordedKeyFile>) -> Self {
+    Op { key_files: Some((container.to_string(), key_files)), ..self }
+  }
+}
+
+/// Cancels operation `seq`, as returned by the command that started it.
+#[tauri::command]
+pub async fn cancel_operation(seq: u64) -> Result<bool, String> {
+  let entry = RUNNING.lock().unwrap().remove(&seq);
+  Ok(match entry {
+    Some((_, cancel)) => stop(cancel),
+    None => false,
+  })
+}
+
+#[derive(Serialize)]
+pub struct QueueStatus {
+  pub(crate) running: usize,
+  pub(crate) queued:  usize,
+}
 
+/// Operations running and waiting for a slot, across all kinds.
+#[tauri::command]
+pub fn queue_status() -> QueueStatus {
+  let (running, queued) = SCHEDULER.load();
+  QueueStatus { running, queued }
+}
+
+/// Kinds with an operation running or queued, for diagnostics.
+pub(crate) fn running_kinds() -> Vec<&'static str> {
+  let mut kinds: Vec<&'static str> = RUNNING.lock().unwrap().values().map(|(prefix, _)| *prefix).collect();
+  kinds.sort_unstable();
+  kinds
+}
+
+/// Asks the operation to stop; the core is killed if it is still running after the grace period.
+fn stop(cancel: Cancel) -> bool {
+  match cancel {
+    // the process task terminates, waits out the grace period, then kills
+    Cancel::Queued(tx) | Cancel::Process(tx) => tx.send(()).is_ok(),
+  }
+}
+
+/// Stops operation `seq` unless it already finished or was cancelled.
+fn stop_running(seq: u64) {
+  let entry = RUNNING.lock().unwrap().remove(&seq);
+  if let Some((_, cancel)) = entry {
+    stop(cancel);
+  }
+}
+
+pub(crate) fn timeouts<R: Runtime>(app: &AppHandle<R>) -> Timeouts {
+  app.try_state::<Timeouts>().map(|t| t.inner().clone()).unwrap_or_default()
+}
+
+async fn start_operation<R: Runtime>(app: &AppHandle<R>, core: CoreBinary, inv: CoreInvocation) -> Result<(mpsc::UnboundedReceiver<Frame>, Cancel), String> {
+  let (rx, cancel) = spawn_process(core, inv, timeouts(app).grace).await?;
+  Ok((rx, Cancel::Process(cancel)))
+}
+
+/// Emits `{prefix}-queued` with the queue position and `seq` while waiting for a slot, then
+/// streams `{prefix}-stdout|stderr|progress` while the operation runs and emits
+/// `{prefix}-result` or `{prefix}-error`, then `{prefix}-signer` once a checked
+/// signature held; `{prefix}-done` is always the last event.
+/// Returns the sequence number `cancel_operation` takes.
+async fn run_operation<R: Runtime>(app: AppHandle<R>, core: CoreBinary, inv: CoreInvocation, op: Op) -> Result<u64, String> {
+  let Op { prefix, priority, paths, context_path, audit, signer_key, key_files } = op;
+  let touched = paths.clone();
+  let seq = OP_SEQ.fetch_add(1, Ordering::SeqCst);
+  let (queued_tx, mut queued_rx) = oneshot::channel();
+  RUNNING.lock().unwrap().insert(seq, (prefix, Cancel::Queued(queued_tx)));
+
+  let on_queued = {
+    let (app, path) = (app.clone(), context_path.clone());
+    move |position: usize| {
+      let payload = match &path {
+        Some(p) => serde_json::json!({ "path": p, "position": position, "seq": seq }),
+        None => serde_json::json!({ "position": position, "seq": seq }),
+      };
+      let _ = app.emit(&format!("{prefix}-queued"), payload);
+    }
+  };
+  log::info!(op = prefix, seq = seq, paths:? = paths; "queued");
+  let permit = tokio::select! {
+    permit = SCHEDULER.acquire(paths, priority, on_queued) => permit,
+    Ok(()) = &mut queued_rx => {
+      log::info!(op = prefix, seq = seq; "cancelled while queued");
+      record_audit(&app, audit, &touched, None, "cancelled", Duration::ZERO);
+      let _ = app.emit(&format!("{prefix}-done"), false);
+      return Ok(seq);
+    }
+  };
+
+  // info results are cached against the container as it is before the core reads it
+  let stamp = context_path.as_deref().and_then(|p| app.try_state::<InfoCache>()?.stamp(p));
+  log::debug!(op = prefix, seq = seq; "starting {inv:?}");
+  let (mut rx, cancel) = match start_operation(&app, core, inv).await {
+    Ok(started) => started,
+    Err(e) => {
+      log::error!(op = prefix, seq = seq, error = e.as_str(); "cannot start tvault-core");
+      RUNNING.lock().unwrap().remove(&seq);
+      record_audit(&app, audit, &touched, None, "failed", Duration::ZERO);
+      return Err(e);
+    }
+  };
+  let started = Instant::now();
+  let cancelled = {
+    let mut running = RUNNING.lock().unwrap();
+    match running.get_mut(&seq) {
+      Some(entry) => {
+        entry.1 = cancel;
+        None
+      }
+      // cancelled while starting
+      None => Some(cancel),
+    }
+  };
+  if let Some(cancel) = cancelled {
+    stop(cancel);
+  }
+
+  let watchdog = Watchdog::start(timeouts(&app).for_prefix(prefix));
   tauri::async_runtime::spawn(async move {
-      let reader = BufReader::new(stdout);
-      let err_reader = BufReader::new(stderr);
-      let mut all_stdout_lines: Vec<String> = Vec::new();
       let mut err_acc = String::new();
-      let mut last_json: Option<Value> = None;
+      let mut last: Option<Value> = None;
+      let mut ok = false;
+      let mut progress = ProgressTracker::default();
+      let mut stalled: Option<Stall> = None;
 
-      for line in reader.lines().flatten() {
-          let _ = app.emit(&format!("{prefix}-stdout"), line.clone());
-          if let Some(p) = line.strip_prefix("PROGRESS ") {
-              if let Ok(n) = p.trim().parse::<u8>() {
-                  let _ = app.emit(&format!("{prefix}-progress"), n);
+      loop {
+          // once stopped, wait for the core to go away without re-arming the limits
+          let next = match stalled {
+              Some(_) => Ok(rx.recv().await),
+              None => watchdog.recv(&mut rx).await,
+          };
+          let frame = match next {
+              Ok(Some(frame)) => frame,
+              Ok(None) => break,
+              Err(stall) => {
+                  log::warn!(op = prefix, seq = seq, stall:? = stall; "stalled; stopping the core");
+                  stalled = Some(stall);
+                  stop_running(seq);
+                  continue;
               }
-              continue;
-          }
-          // try parse line-by-line JSON; keep last json object
-          match serde_json::from_str::<Value>(&line) {
-              Ok(val) => {
-                  if val.is_object() || val.is_array() {
-                      last_json = Some(val);
-                  } else {
-                      // it's a scalar (likely a JSON string) — accumulate for later
-                      all_stdout_lines.push(line.clone());
-                  }
+          };
+          match frame {
+              Frame::Stdout(line) => {
+                  log::debug!(op = prefix, seq = seq, line = line.as_str(); "core stdout");
+                  let _ = app.emit(&format!("{prefix}-stdout"), line);
+              }
+              Frame::Stderr(line) => {
+                  log::debug!(op = prefix, seq = seq, line = line.as_str(); "core stderr");
+                  err_acc.push_str(&line);
+                  err_acc.push('\n');
+                  let _ = app.emit(&format!("{prefix}-stderr"), line);
               }
-              Err(_) => {
-                  all_stdout_lines.push(line.clone());
+              Frame::Progress(sample) => {
+                  let before = progress.percent();
+                  let detail = progress.update(sample, Instant::now());
+                  if progress.percent() != before {
+                      let _ = app.emit(&format!("{prefix}-progress"), progress.percent());
+                  }
+                  if let Some(detail) = detail {
+                      let _ = app.emit(&format!("{prefix}-progress-detail"), detail);
+                  }
               }
+              Frame::Result(val) => last = Some(val),
+              Frame::Exit(status) => ok = status,
           }
       }
 
-      for line in err_reader.lines().flatten() {
-          err_acc.push_str(&line);
-          err_acc.push('\n');
-          let _ = app.emit(&format!("{prefix}-stderr"), line);
+      if let Some(detail) = progress.flush() {
+          let _ = app.emit(&format!("{prefix}-progress-detail"), detail);
       }
 
-      let ok = child.wait().map(|s| s.success()).unwrap_or(false);
-      let _ = app.emit(&format!("{prefix}-done"), ok);
-
-      // Prefer last parsed JSON line, else try whole-stdout accumulation
-      if let Some(val) = last_json {
+      if let Some(stall) = stalled {
+          let err = TimeoutError::new(stall, progress.phase(), watchdog.limits());
+          let payload = match &context_path {
+              Some(p) => serde_json::json!({ "path": p, "error": err }),
+              None => serde_json::json!(err),
+          };
+          let _ = app.emit(&format!("{prefix}-error"), payload);
+          ok = false;
+      } else if let Some(val) = last.clone() {
           // classify as error or result based on tvault-core error JSON shape
-          let is_err = val.get("code").is_some() && val.get("message").is_some();
+          let is_err = is_core_error(&val);
+          let event = if is_err { "error" } else { "result" };
           if is_err {
-              let _ = app.emit(&format!("{prefix}-error"), val);
-          } else {
-              let _ = app.emit(&format!("{prefix}-result"), val);
+              log::warn!(op = prefix, seq = seq, error = val.to_string().as_str(); "core reported an error");
           }
-      } else {
-          let joined = all_stdout_lines.join("\n");
-          // Try to cut to the first '{' and last '}' to extract JSON object
-          let json_slice = if let (Some(s), Some(e)) = (joined.find('{'), joined.rfind('}')) {
-              if e >= s { Some(&joined[s..=e]) } else { None }
-          } else { None };
-
-          if let Some(slice) = json_slice {
-              if let Ok(val) = serde_json::from_str::<Value>(slice) {
-                  let is_err = val.get("code").is_some() && val.get("message").is_some();
-                  if is_err {
-                      let _ = app.emit(&format!("{prefix}-error"), val);
-                  } else {
-                      let _ = app.emit(&format!("{prefix}-result"), val);
-                  }
-              }
-          } else if let Ok(val) = serde_json::from_str::<Value>(&joined) {
-              let is_err = val.get("code").is_some() && val.get("message").is_some();
-              if is_err {
-                  let _ = app.emit(&format!("{prefix}-error"), val);
-              } else {
-                  let _ = app.emit(&format!("{prefix}-result"), val);
-              }
+          if let (Some(stamp), false, Some(cache)) = (stamp, is_err, app.try_state::<InfoCache>()) {
+              cache.put(stamp, val.clone());
           }
+          let payload = match &context_path {
+              Some(p) if is_err => serde_json::json!({ "path": p, "error": val }),
+              Some(p) => info_data(&app, p, val),
+              None => val,
+          };
+          let _ = app.emit(&format!("{prefix}-{event}"), payload);
       }
-      if !ok && !err_acc.is_empty() {
+      if stalled.is_none() && !ok && !err_acc.is_empty() {
           let _ = app.emit(&format!("{prefix}-error"), err_acc);
       }
+
+      // a stop that was not the watchdog's came from cancel_operation
+      let cancelled = !RUNNING.lock().unwrap().contains_key(&seq);
+      let outcome = match (ok, stalled.is_some(), cancelled) {
+          (true, _, _) => "ok",
+          (false, true, _) => "timed_out",
+          (false, false, true) => "cancelled",
+          (false, false, false) => "failed",
+      };
+      record_audit(&app, audit, &touched, last.as_ref(), outcome, started.elapsed());
+
+      // seal and reseal rewrite their containers; cached info for them is stale now
+      if ok && matches!(prefix, "encrypt" | "reseal") {
+          if let Some(cache) = app.try_state::<InfoCache>() {
+              for p in &touched {
+                  cache.invalidate(&p.to_string_lossy());
+              }
+          }
+      }
+
+      // a failed seal leaves the container as it was, and so the record
+      if let (true, Some((container, recorded))) = (ok, key_files) {
+          if let Err(e) = key_file::record(&app, &container, recorded) {
+              log::warn!(op = prefix, seq = seq, error = e.as_str(); "cannot record key files");
+          }
+      }
+
+      // the core only succeeds once the signature matched the key it was given
+      if let (true, Some(key), Some(store)) = (ok, &signer_key, app.try_state::<TrustStore>()) {
+          let _ = app.emit(&format!("{prefix}-signer"), store.verified(key));
+      }
+
+      log::info!(op = prefix, seq = seq, ok = ok; "finished");
+      RUNNING.lock().unwrap().remove(&seq);
+      drop(permit);
+      let _ = app.emit(&format!("{prefix}-done"), ok);
   });
 
-  Ok(())
+  Ok(seq)
+}
+
+/// Writes the audit entry of an audited operation; it names the first of its paths.
+fn record_audit<R: Runtime>(app: &AppHandle<R>, audit: Option<(&'static str, Option<String>)>, paths: &[PathBuf], result: Option<&Value>, outcome: &str, duration: Duration) {
+  let (Some((op, integrity)), Some(container)) = (audit, paths.first()) else { return };
+  let container = container.to_string_lossy().into_owned();
+  let container_id = audit::container_id(result, cached_info(app, &container));
+  audit::record(app, audit::Event { op, container, container_id, outcome: outcome.into(), integrity, duration });
+}
+
+/// What a tvault-core run produced, for commands that answer directly instead of streaming events.
+pub(crate) struct Collected {
+  /// Last JSON the core printed.
+  pub result:  Option<Value>,
+  pub stderr:  String,
+  pub ok:      bool,
+  /// Set when a watchdog limit stopped the core.
+  pub timeout: Option<TimeoutError>,
+}
+
+/// Waits for a scheduler slot and exclusive use of `paths`, runs `inv` under
+/// `limits` and collects its output.
+pub(crate) async fn run_collect<R: Runtime>(app: &AppHandle<R>, core: CoreBinary, inv: CoreInvocation, paths: Vec<PathBuf>, priority: Priority, limits: Limits) -> Result<Collected, String> {
+  let _permit = SCHEDULER.acquire(paths, priority, |_| {}).await;
+  let (mut rx, cancel) = start_operation(app, core, inv).await?;
+  let watchdog = Watchdog::start(limits);
+  let mut run = Collected { result: None, stderr: String::new(), ok: false, timeout: None };
+  loop {
+    match watchdog.recv(&mut rx).await {
+      Ok(Some(Frame::Result(val))) => run.result = Some(val),
+      Ok(Some(Frame::Stderr(line))) => {
+        run.stderr.push_str(&line);
+        run.stderr.push('\n');
+      }
+      Ok(Some(Frame::Exit(ok))) => run.ok = ok,
+      Ok(Some(_)) => {}
+      Ok(None) => break,
+      Err(stall) => {
+        stop(cancel);
+        // keep the container locked until the core is really gone
+        while rx.recv().await.is_some() {}
+        run.ok = false;
+        run.timeout = Some(TimeoutError::new(stall, None, limits));
+        break;
+      }
+    }
+  }
+  Ok(run)
+}
+
+/// Maps `f` over `items` with at most `limit` in flight; results keep the input order.
+pub(crate) async fn fan_out<T, O, F, Fut>(items: Vec<T>, limit: usize, f: F) -> Result<Vec<O>, String>
+where
+  T: Send + 'static,
+  O: Send + 'static,
+  F: Fn(T) -> Fut + Send + Sync + 'static,
+  Fut: std::future::Future<Output = O> + Send,
+{
+  let total = items.len();
+  let queue = Arc::new(StdMutex::new(items.into_iter().enumerate().collect::<VecDeque<_>>()));
+  let f = Arc::new(f);
+  let tasks: Vec<_> = (0..limit.min(total))
+    .map(|_| {
+      let (queue, f) = (queue.clone(), f.clone());
+      tauri::async_runtime::spawn(async move {
+        let mut done = Vec::new();
+        loop {
+          let Some((i, item)) = queue.lock().unwrap().pop_front() else { break };
+          done.push((i, f(item).await));
+        }
+        done
+      })
+    })
+    .collect();
+
+  let mut slots: Vec<Option<O>> = (0..total).map(|_| None).collect();
+  for task in tasks {
+    for (i, out) in task.await.map_err(|e| e.to_string())? {
+      slots[i] = Some(out);
+    }
+  }
+  Ok(slots.into_iter().flatten().collect())
 }
 
-fn spawn_process_with_context(app: AppHandle<Wry>, mut cmd: Command, prefix: &'static str, context_path: Option<String>) -> Result<(), String> {
+fn is_core_error(val: &Value) -> bool {
+  val.get("code").is_some() && val.get("message").is_some()
+}
+
+/* ─────────── Process Execution ─────────── */
+
+async fn spawn_process(core: CoreBinary, inv: CoreInvocation, grace: Duration) -> Result<(mpsc::UnboundedReceiver<Frame>, oneshot::Sender<()>), String> {
+  // `core` holds the verified file open until the child has started
+  let mut cmd = tokio::process::Command::from(inv.command(&core));
   #[cfg(target_os = "windows")]
   { cmd.creation_flags(0x08000000); } // CREATE_NO_WINDOW
-  let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn().map_err(|e| e.to_string())?;
+  let stdin = if inv.stdin.is_some() { Stdio::piped() } else { Stdio::null() };
+  let mut child = cmd.stdin(stdin).stdout(Stdio::piped()).stderr(Stdio::piped()).kill_on_drop(true).spawn().map_err(|e| e.to_string())?;
+  if let (Some(input), Some(mut pipe)) = (inv.stdin.as_deref(), child.stdin.take()) {
+    // dropping the pipe afterwards closes the child's stdin
+    if let Err(e) = pipe.write_all(input.as_bytes()).await {
+      // without its input the core cannot do anything useful; kill and reap it
+      let _ = child.kill().await;
+      return Err(format!("cannot write to tvault-core: {e}"));
+    }
+  }
+  // the child has its credentials; wipe ours (std frees its own argv copies unwiped)
+  drop(inv);
+  drop(cmd);
   let stdout = child.stdout.take().ok_or("cannot capture stdout")?;
   let stderr = child.stderr.take().ok_or("cannot capture stderr")?;
 
+  let (tx, rx) = mpsc::unbounded_channel();
+  let (cancel_tx, mut cancel_rx) = oneshot::channel::<()>();
   tauri::async_runtime::spawn(async move {
-      let reader = BufReader::new(stdout);
-      let err_reader = BufReader::new(stderr);
-      let mut all_stdout_lines: Vec<String> = Vec::new();
-      let mut err_acc = String::new();
-      let mut last_json: Option<Value> = None;
-
-      for line in reader.lines().flatten() {
-          let _ = app.emit(&format!("{prefix}-stdout"), line.clone());
-          if let Some(p) = line.strip_prefix("PROGRESS ") {
-              if let Ok(n) = p.trim().parse::<u8>() {
-                  let _ = app.emit(&format!("{prefix}-progress"), n);
-              }
-              continue;
-          }
-          match serde_json::from_str::<Value>(&line) {
-              Ok(val) => {
-                  if val.is_object() || val.is_array() {
-                      last_json = Some(val);
-                  } else {
-                      all_stdout_lines.push(line.clone());
-                  }
-              }
-              Err(_) => {
-                  all_stdout_lines.push(line.clone());
-              }
+      let out = tauri::async_runtime::spawn(read_stdout(stdout, tx.clone()));
+      let err = tauri::async_runtime::spawn(read_stderr(stderr, tx.clone()));
+      let ok = tokio::select! {
+          status = child.wait() => status.map(|s| s.success()).unwrap_or(false),
+          Ok(()) = &mut cancel_rx => {
+              terminate(&mut child, grace).await;
+              false
           }
-      }
+      };
+      let _ = out.await;
+      let _ = err.await;
+      let _ = tx.send(Frame::Exit(ok));
+  });
 
-      for line in err_reader.lines().flatten() {
-          err_acc.push_str(&line);
-          err_acc.push('\n');
-          let _ = app.emit(&format!("{prefix}-stderr"), line);
-      }
+  Ok((rx, cancel_tx))
+}
 
-      let ok = child.wait().map(|s| s.success()).unwrap_or(false);
-      let _ = app.emit(&format!("{prefix}-done"), ok);
+/// SIGTERM first so the core can clean up partial output, SIGKILL after `grace`.
+async fn terminate(child: &mut Child, grace: Duration) {
+  #[cfg(unix)]
+  if let Some(pid) = child.id() {
+    // SAFETY: plain syscall on a pid we spawned and have not reaped yet
+    unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM); }
+    if tokio::time::timeout(grace, child.wait()).await.is_ok() {
+      return;
+    }
+  }
+  #[cfg(not(unix))]
+  let _ = grace; // no graceful signal for a windowless child on Windows
+  let _ = child.kill().await;
+}
 
-      let emit_with_ctx = |val: Value| {
-          if let Some(p) = &context_path {
-              let wrapped = serde_json::json!({ "path": p, "data": val });
-              let _ = app.emit(&format!("{prefix}-result"), wrapped);
-          } else {
-          let _ = app.emit(&format!("{prefix}-result"), val);
-          }
-      };
+async fn read_stdout(stdout: ChildStdout, tx: mpsc::UnboundedSender<Frame>) {
+  let mut lines = BufReader::new(stdout).lines();
+  let mut all_stdout_lines: Vec<String> = Vec::new();
+  let mut last_json: Option<Value> = None;
 
-      if let Some(val) = last_json {
-          let is_err = val.get("code").is_some() && val.get("message").is_some();
-          if is_err {
-              if let Some(p) = &context_path {
-                  let wrapped = serde_json::json!({ "path": p, "error": val });
-                  let _ = app.emit(&format!("{prefix}-error"), wrapped);
-              } else {
-                  let _ = app.emit(&format!("{prefix}-error"), val);
-              }
-          } else {
-              emit_with_ctx(val);
-          }
-      } else {
-          let joined = all_stdout_lines.join("\n");
-          let json_slice = if let (Some(s), Some(e)) = (joined.find('{'), joined.rfind('}')) {
-              if e >= s { Some(&joined[s..=e]) } else { None }
-          } else { None };
-
-          if let Some(slice) = json_slice {
-              if let Ok(val) = serde_json::from_str::<Value>(slice) {
-                  let is_err = val.get("code").is_some() && val.get("message").is_some();
-                  if is_err {
-                      if let Some(p) = &context_path {
-                          let wrapped = serde_json::json!({ "path": p, "error": val });
-                          let _ = app.emit(&format!("{prefix}-error"), wrapped);
-                      } else {
-                          let _ = app.emit(&format!("{prefix}-error"), val);
-                      }
-                  } else {
-                      emit_with_ctx(val);
-                  }
-              }
-          } else if let Ok(val) = serde_json::from_str::<Value>(&joined) {
-              let is_err = val.get("code").is_some() && val.get("message").is_some();
-              if is_err {
-                  if let Some(p) = &context_path {
-                      let wrapped = serde_json::json!({ "path": p, "error": val });
-                      let _ = app.emit(&format!("{prefix}-error"), wrapped);
-                  } else {
-                      let _ = app.emit(&format!("{prefix}-error"), val);
-                  }
-              } else {
-                  emit_with_ctx(val);
-              }
+  while let Ok(Some(line)) = lines.next_line().await {
+      let _ = tx.send(Frame::Stdout(line.clone()));
+      if let Some(p) = line.strip_prefix("PROGRESS ") {
+          if let Some(sample) = ProgressSample::parse(p) {
+              let _ = tx.send(Frame::Progress(sample));
           }
+          continue;
       }
-      if !ok && !err_acc.is_empty() {
-          let _ = app.emit(&format!("{prefix}-error"), err_acc);
+      // try parse line-by-line JSON; keep last json object
+      match serde_json::from_str::<Value>(&line) {
+          Ok(val) if val.is_object() || val.is_array() => last_json = Some(val),
+          // scalars (likely a JSON string) and plain text are accumulated for later
+          _ => all_stdout_lines.push(line),
       }
-  });
+  }
 
-  Ok(())
+  // Prefer last parsed JSON line, else try whole-stdout accumulation
+  if let Some(val) = last_json.or_else(|| extract_json(&all_stdout_lines.join("\n"))) {
+      let _ = tx.send(Frame::Result(val));
+  }
+}
+
+async fn read_stderr(stderr: ChildStderr, tx: mpsc::UnboundedSender<Frame>) {
+  let mut lines = BufReader::new(stderr).lines();
+  while let Ok(Some(line)) = lines.next_line().await {
+      let _ = tx.send(Frame::Stderr(line));
+  }
+}
+
+/// Cuts to the first '{' and last '}' to extract a JSON object from noisy output.
+fn extract_json(joined: &str) -> Option<Value> {
+  match (joined.find('{'), joined.rfind('}')) {
+    (Some(s), Some(e)) if e >= s => serde_json::from_str::<Value>(&joined[s..=e]).ok(),
+    _ => serde_json::from_str::<Value>(joined).ok(),
+  }
 }
 
 /* ─────────── Encrypt Validation ─────────── */
 
 fn validate_encrypt(a: &EncryptArgs) -> Result<(), String> {
+  if a.passphrase.expose_secret().is_empty() && a.key_files.as_ref().is_none_or(Vec::is_empty) {
+      return Err("a passphrase or a key file is required".into());
+  }
   if a.token_save_type == "file" && a.token_save_path.is_none() {
       return Err("token_save_path is required when token_save_type=file".into());
   }
   if a.integrity_provider == "hmac" && a.additional_password.is_none() {
       return Err("additional_password is required when integrity_provider=hmac".into());
   }
+  if a.integrity_provider == "ed25519" && (a.signing_key_id.is_none() || a.signing_passphrase.is_none()) {
+      return Err("signing_key_id and signing_passphrase are required when integrity_provider=ed25519".into());
+  }
   if a.token_type == "share" {
       let k = a.threshold.unwrap_or(3);
       let n = a.number_of_shares.unwrap_or(5);
@@ -572,52 +1092,184 @@ fn validate_encrypt(a: &EncryptArgs) -> Result<(), String> {
   Ok(())
 }
 
-/* ─────────── Locate tvault-core ─────────── */
+/* ─────────── Tests ─────────── */
 
-fn locate_binary() -> Result<PathBuf, String> {
-  use std::fs;
-  #[cfg(unix)] use std::os::unix::fs::PermissionsExt;
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::{json, Map};
 
-  let exe = current_exe().map_err(|e| e.to_string())?;
-  let dir = exe.parent().ok_or("failed to locate dir")?;
+  #[derive(Serialize)]
+  struct Case {
+    case: String,
+    #[serde(flatten)]
+    inv:  CoreInvocation,
+  }
 
-  #[cfg(target_os = "windows")]
-  let candidates = vec![dir.join("tvault-core.exe")];
-
-  #[cfg(not(target_os = "windows"))]
-  let candidates = {
-    let mut v = vec![dir.join("tvault-core"), dir.join("tvault-core-aarch64-apple-darwin")];
-    // dev-run fallback: ../../src-tauri/binaries/...
-    if let Some(project_root) = dir.parent().and_then(|p| p.parent()) {
-      v.push(project_root.join("src-tauri/binaries/tvault-core"));
-      v.push(project_root.join("src-tauri/binaries/tvault-core-aarch64-apple-darwin"));
+  fn merge(parts: &[&Value]) -> Value {
+    let mut m = Map::new();
+    for p in parts {
+      m.extend(p.as_object().unwrap().clone());
     }
-    v
-  };
+    Value::Object(m)
+  }
 
-  let mut tried: Vec<String> = Vec::new();
-  for c in candidates {
-    let disp = c.display().to_string();
-    tried.push(disp.clone());
-    if c.exists() {
-      // try to ensure executable bit; ignore errors on non-unix
-      #[cfg(unix)]
-      if let Ok(meta) = fs::metadata(&c) {
-        let mut perm = meta.permissions();
-        let mode = perm.mode();
-        if mode & 0o111 == 0 {
-          let new_mode = mode | 0o755;
-          perm.set_mode(new_mode);
-          let _ = fs::set_permissions(&c, perm);
-        }
-      }
-      // macOS Gatekeeper quarantine: try to drop attribute, best-effort
-      #[cfg(target_os = "macos")]
-      {
-        let _ = Command::new("/usr/bin/xattr").arg("-d").arg("com.apple.quarantine").arg(&c).output();
-      }
-      return Ok(c);
+  fn table<T, F>(base: Value, axes: &[&[(&str, Value)]], build: F) -> Vec<Case>
+  where
+    T: serde::de::DeserializeOwned,
+    F: Fn(&T) -> CoreInvocation,
+  {
+    let mut rows: Vec<(Vec<&str>, Value)> = vec![(vec![], base)];
+    for axis in axes {
+      rows = rows
+        .iter()
+        .flat_map(|(names, v)| axis.iter().map(move |(n, patch)| ([names.clone(), vec![*n]].concat(), merge(&[v, patch]))))
+        .collect();
+    }
+    rows
+      .into_iter()
+      .map(|(names, v)| Case { case: names.join(" / "), inv: build(&serde_json::from_value(v).unwrap()) })
+      .collect()
+  }
+
+  #[test]
+  fn seal_argv() {
+    let cases = table(
+      json!({
+        "container_path": "/v/box.tvlt",
+        "folder_path": "/data",
+        "compression_type": "zip",
+        "passphrase": "P@ss",
+      }),
+      &[
+        &[
+          ("token=master", json!({ "token_type": "master" })),
+          ("token=share", json!({ "token_type": "share" })),
+          ("token=share 4of7", json!({ "token_type": "share", "number_of_shares": 7, "threshold": 4 })),
+          ("token=none", json!({ "token_type": "none" })),
+        ],
+        &[
+          ("integrity=none", json!({ "integrity_provider": "none" })),
+          ("integrity=hmac", json!({ "integrity_provider": "hmac", "additional_password": "mac" })),
+          ("integrity=ed25519", json!({ "integrity_provider": "ed25519", "signing_key_id": "0123456789abcdef", "signing_passphrase": "sign" })),
+        ],
+        &[
+          ("writer=stdout", json!({ "token_save_type": "stdout" })),
+          ("writer=file", json!({ "token_save_type": "file", "token_save_path": "/t/tokens.json" })),
+        ],
+      ],
+      build_seal_cmd,
+    );
+    insta::assert_yaml_snapshot!(cases);
+  }
+
+  #[test]
+  fn seal_argv_with_metadata() {
+    let a: EncryptArgs = serde_json::from_value(json!({
+      "name": "Team box",
+      "container_path": "/v/box.tvlt",
+      "folder_path": "/data",
+      "compression_type": "none",
+      "passphrase": "P@ss",
+      "token_type": "master",
+      "token_save_type": "stdout",
+      "integrity_provider": "none",
+      "comment": "q3 reports",
+      "tags": "finance,2024",
+    })).unwrap();
+    insta::assert_yaml_snapshot!(build_seal_cmd(&a));
+  }
+
+  #[test]
+  fn unseal_argv() {
+    let cases = table(
+      json!({ "container_path": "/v/box.tvlt", "folder_path": "/mnt/box" }),
+      &[
+        &[
+          ("nothing", json!({})),
+          ("password only (type=none)", json!({ "token": "P@ss" })),
+          ("password + master", json!({ "token": "P@ss", "master_token": "MT" })),
+          ("master only", json!({ "master_token": "MT" })),
+          ("reader=flag plaintext", json!({ "token_reader_type": "flag", "token_format": "plaintext", "token_flag": "s1|s2" })),
+          ("reader=flag json", json!({ "token_reader_type": "flag", "token_format": "json", "token_flag": "{\"k\":1}" })),
+          ("reader=file json", json!({ "token_reader_type": "file", "token_format": "json", "token_path": "/t/tokens.json" })),
+          ("reader=flag no format + password", json!({ "token_reader_type": "flag", "token": "P@ss" })),
+          ("reader=file no format", json!({ "token_reader_type": "file" })),
+        ],
+        &[
+          ("integrity=none", json!({})),
+          ("integrity=hmac", json!({ "additional_password": "mac" })),
+          ("integrity=ed25519", json!({ "public_key": "ed25519:AAAA" })),
+        ],
+      ],
+      build_unseal_cmd,
+    );
+    insta::assert_yaml_snapshot!(cases);
+  }
+
+  #[test]
+  fn reseal_argv() {
+    let cases = table(
+      json!({ "current_path": "/v/box.tvlt", "folder_path": "/mnt/box", "passphrase": "P@ss" }),
+      &[
+        &[
+          ("token unset", json!({})),
+          ("token=master", json!({ "token_type": "master", "master_token": "MT" })),
+          ("token=share file", json!({ "token_type": "share", "token_json_path": "/t/tokens.json", "shares": ["ignored"] })),
+          ("token=share flag", json!({ "token_type": "share", "shares": ["s1", "s2", "s3"] })),
+          ("token=none", json!({ "token_type": "none" })),
+        ],
+        &[
+          ("integrity unset", json!({})),
+          ("integrity=none", json!({ "integrity_provider": "none" })),
+          ("integrity=hmac keep", json!({ "integrity_provider": "hmac", "current_integrity_password": "mac" })),
+          ("integrity=hmac rotate", json!({ "integrity_provider": "hmac", "current_integrity_password": "mac", "new_integrity_password": "mac2" })),
+          ("integrity=hmac to ed25519", json!({ "integrity_provider": "ed25519", "current_integrity_password": "mac", "signing_key_id": "0123456789abcdef" })),
+        ],
+      ],
+      build_reseal_cmd,
+    );
+    insta::assert_yaml_snapshot!(cases);
+  }
+
+  #[test]
+  fn reseal_argv_with_metadata() {
+    let a: ResealArgs = serde_json::from_value(json!({
+      "current_path": "/v/box.tvlt",
+      "new_path": "/v/renamed.tvlt",
+      "folder_path": "/mnt/box",
+      "name": "Renamed",
+      "comment": "moved",
+      "tags": "a,b",
+      "token_type": "master",
+      "master_token": "MT",
+    })).unwrap();
+    insta::assert_yaml_snapshot!(build_reseal_cmd(&a));
+  }
+
+  #[test]
+  fn credentials_stay_out_of_debug_output() {
+    let args: ResealArgs = serde_json::from_value(json!({
+      "current_path": "/v/box.tvlt",
+      "folder_path": "/mnt/box",
+      "passphrase": "P@ss",
+      "token_type": "share",
+      "shares": ["share-one", "share-two"],
+      "integrity_provider": "hmac",
+      "current_integrity_password": "hmac-secret",
+    })).unwrap();
+    let mut inv = build_reseal_cmd(&args);
+    inv.stdin = Some("private key".into());
+    for printed in [format!("{args:?}"), format!("{inv:?}")] {
+      assert!(!["P@ss", "share-one", "hmac-secret", "private key"].iter().any(|s| printed.contains(s)), "{printed}");
     }
+    let printed = format!("{inv:?}");
+    assert!(printed.contains("-passphrase=[REDACTED]") && printed.contains("-flag=[REDACTED]") && printed.contains("-folder-path=/mnt/box"));
+  }
+
+  #[test]
+  fn container_info_argv() {
+    let a: ContainerInfoArgs = serde_json::from_value(json!({ "path": "/v/box.tvlt" })).unwrap();
+    insta::assert_yaml_snapshot!(build_container_info_cmd(&a));
   }
-  Err(format!("tvault-core not found. Tried: {}", tried.join(", ")))
 }
@@ -0,0 +1,84 @@
+//! container.rs — how container files are identified on disk
+//!
+//! The scheduler, the info cache and the key file records all key containers
+//! the same way, so `a.tvlt`, `./a.tvlt` and the same file reached through a
+//! symlinked folder are one container to each of them. The header hash tells
+//! two versions of one container apart; share papers carry the one they were
+//! printed for.
+
+use std::{
+  fs::{self, File},
+  io::Read,
+  path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+const HEADER_BYTES: u64 = 4096;
+/// Extension of container files.
+pub const EXTENSION: &str = "tvlt";
+
+/// The container path with its directory resolved; the file itself may not exist yet.
+pub(crate) fn key(path: &Path) -> PathBuf {
+  let dir = match path.parent() {
+    Some(d) if !d.as_os_str().is_empty() => d,
+    _ => Path::new("."),
+  };
+  match (fs::canonicalize(dir), path.file_name()) {
+    (Ok(dir), Some(name)) => dir.join(name),
+    _ => path.to_path_buf(),
+  }
+}
+
+/// SHA-256 of the first bytes of a container, hex; changes whenever it is resealed.
+pub(crate) fn header_hash(path: &Path) -> Option<String> {
+  let mut buf = Vec::new();
+  File::open(path).ok()?.take(HEADER_BYTES).read_to_end(&mut buf).ok()?;
+  Some(Sha256::digest(&buf).iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Whether `path` is a container file itself: a regular file, not a link, named `*.tvlt`.
+pub(crate) fn is_container_file(path: &Path) -> bool {
+  let named = path.extension().is_some_and(|e| e.eq_ignore_ascii_case(EXTENSION));
+  named && fs::symlink_metadata(path).is_ok_and(|m| m.is_file())
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn spellings_of_one_file_share_a_key() {
+    let dir = std::env::temp_dir().join(format!("tvault-container-key-{}", std::process::id()));
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    let plain = key(&dir.join("box.tvlt"));
+    assert_eq!(key(&dir.join("sub/../box.tvlt")), plain);
+    assert_eq!(key(&dir.join("./box.tvlt")), plain);
+    // not created yet, as for a seal's new path
+    assert!(!plain.exists());
+    assert_eq!(plain.file_name().unwrap(), "box.tvlt");
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn only_tvlt_files_count_as_container_files() {
+    let dir = std::env::temp_dir().join(format!("tvault-container-file-{}", std::process::id()));
+    fs::create_dir_all(dir.join("folder.tvlt")).unwrap();
+    for name in ["box.tvlt", "BOX.TVLT", "notes.txt"] {
+      fs::write(dir.join(name), b"x").unwrap();
+    }
+    assert!(is_container_file(&dir.join("box.tvlt")));
+    assert!(is_container_file(&dir.join("BOX.TVLT")));
+    assert!(!is_container_file(&dir.join("notes.txt")));
+    assert!(!is_container_file(&dir.join("folder.tvlt")));
+    assert!(!is_container_file(&dir.join("missing.tvlt")));
+    #[cfg(unix)]
+    {
+      std::os::unix::fs::symlink(dir.join("notes.txt"), dir.join("link.tvlt")).unwrap();
+      assert!(!is_container_file(&dir.join("link.tvlt")));
+    }
+    let _ = fs::remove_dir_all(&dir);
+  }
+}
@@ -0,0 +1,44 @@
+//! data_file.rs — loading the versioned JSON stores kept in the app data dir
+//!
+//! A store that starts empty over a file it could not load would overwrite
+//! that file on its next save. So a file that cannot be read, does not parse
+//! or has another format version is moved aside to `<name>.corrupt` first, and
+//! when even that fails the store must refuse to save.
+
+use std::{
+  fs, io,
+  path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+
+/// Loads `file`, `None` when there is none yet. `Err` is why the file is
+/// unusable and could not be moved aside; it is still in place.
+pub(crate) fn load<T: DeserializeOwned>(file: &Path, version: u32, version_of: impl Fn(&T) -> u32) -> Result<Option<T>, String> {
+  let problem = match fs::read(file) {
+    Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+    Err(e) => format!("cannot be read: {e}"),
+    Ok(raw) => match serde_json::from_slice::<T>(&raw) {
+      Ok(stored) if version_of(&stored) == version => return Ok(Some(stored)),
+      Ok(stored) => format!("has format version {}, not {version}", version_of(&stored)),
+      Err(e) => format!("does not parse: {e}"),
+    },
+  };
+  let aside = set_aside_path(file);
+  match fs::rename(file, &aside) {
+    Ok(()) => {
+      log::warn!(file:% = file.display(), moved_to:% = aside.display(); "store {problem}; starting empty");
+      Ok(None)
+    }
+    Err(e) => Err(format!("{} {problem}, and it cannot be moved aside ({e}); nothing will be saved over it", file.display())),
+  }
+}
+
+/// `<name>.corrupt`, numbered so an earlier one is never replaced.
+fn set_aside_path(file: &Path) -> PathBuf {
+  let name = file.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+  (0..)
+    .map(|n| file.with_file_name(if n == 0 { format!("{name}.corrupt") } else { format!("{name}.corrupt.{n}") }))
+    .find(|p| !p.exists())
+    .unwrap_or_default()
+}
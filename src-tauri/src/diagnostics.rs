@@ -0,0 +1,298 @@
+//! diagnostics.rs — a support bundle the user reviews before it is saved
+//!
+//! `preview_diagnostics` gathers every file of the bundle and keeps it as a
+//! draft; `export_diagnostics` zips exactly that draft, minus the files the
+//! user chose to leave out. Nothing is gathered twice, so what was reviewed is
+//! what gets written.
+//!
+//! Logs pass through `logging::redact` again and the settings summary is
+//! scrubbed of credential-named keys; container and mount paths are kept,
+//! since they are what support asks about.
+
+use std::{
+  fs::{self, File},
+  io::Write,
+  path::Path,
+  process::Stdio,
+  sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex,
+  },
+  time::{Duration, SystemTime},
+};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::process::Command;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{cli_runner, entropy::EntropyPool, logging, settings, sidecar};
+
+/// Budget for `tvault-core version` and the OS version probe.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// Warnings and errors copied into `errors.txt`.
+const RECENT_ERRORS: usize = 100;
+
+/// The last preview; exports must name its id.
+static DRAFT: Lazy<Mutex<Option<Draft>>> = Lazy::new(Default::default);
+static DRAFT_SEQ: AtomicU64 = AtomicU64::new(1);
+
+struct Draft {
+  id:      u64,
+  entries: Vec<DiagnosticsEntry>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct DiagnosticsEntry {
+  /// Path inside the zip, e.g. `logs/tvault.log`.
+  pub name:    String,
+  pub content: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct DiagnosticsPreview {
+  /// Pass back to `export_diagnostics`.
+  pub id:      u64,
+  pub entries: Vec<DiagnosticsEntry>,
+}
+
+/* ─────────── Gathering ─────────── */
+
+async fn gather<R: Runtime>(app: &AppHandle<R>) -> Vec<DiagnosticsEntry> {
+  let logs: Vec<(String, String)> = logging::log_files()
+    .into_iter()
+    .filter_map(|path| {
+      let name = path.file_name()?.to_string_lossy().into_owned();
+      let raw = fs::read_to_string(&path).ok()?;
+      Some((name, raw.lines().map(logging::redact).collect::<Vec<_>>().join("\n")))
+    })
+    .collect();
+  let errors = recent_errors(logs.iter().flat_map(|(_, text)| text.lines()));
+
+  let core = sidecar::report(app);
+  let core_version = match sidecar::locate_binary(app) {
+    Ok(binary) => first_line(binary.command(), &["version"]).await,
+    Err(_) => None,
+  };
+
+  let state = json!({
+    "queue":            cli_runner::queue_status(),
+    "running":          cli_runner::running_kinds(),
+    "entropy_sessions": app.try_state::<EntropyPool>().map(|pool| pool.open_sessions()),
+  });
+
+  let mut entries = vec![
+    json_entry("system.json", &system(os_version().await)),
+    json_entry("core.json", &json!({ "version": core_version, "sidecar": core })),
+    json_entry("state.json", &state),
+    json_entry("mounts.json", &mounts(settings::get_string(app, "containersPath"), &settings::recent(app))),
+    json_entry("settings.json", &settings_summary(settings::all(app))),
+    DiagnosticsEntry { name: "errors.txt".into(), content: errors },
+  ];
+  entries.extend(logs.into_iter().map(|(name, content)| DiagnosticsEntry { name: format!("logs/{name}"), content }));
+  entries
+}
+
+fn json_entry(name: &str, value: &impl Serialize) -> DiagnosticsEntry {
+  DiagnosticsEntry { name: name.to_string(), content: serde_json::to_string_pretty(value).unwrap_or_default() }
+}
+
+fn system(os_version: Option<String>) -> Value {
+  json!({
+    "app_version":  env!("CARGO_PKG_VERSION"),
+    "os":           std::env::consts::OS,
+    "os_version":   os_version,
+    "family":       std::env::consts::FAMILY,
+    "arch":         std::env::consts::ARCH,
+    "generated_at": logging::timestamp(SystemTime::now()),
+  })
+}
+
+async fn os_version() -> Option<String> {
+  #[cfg(target_os = "linux")]
+  {
+    let release = fs::read_to_string("/etc/os-release").ok()?;
+    release.lines().find_map(|l| l.strip_prefix("PRETTY_NAME=")).map(|v| v.trim_matches('"').to_string())
+  }
+  #[cfg(target_os = "macos")]
+  {
+    first_line(std::process::Command::new("sw_vers"), &["-productVersion"]).await
+  }
+  #[cfg(target_os = "windows")]
+  {
+    first_line(std::process::Command::new("cmd"), &["/C", "ver"]).await
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+  {
+    None
+  }
+}
+
+/// First non-empty stdout line of a short-lived program, if it exits in time.
+async fn first_line(program: std::process::Command, args: &[&str]) -> Option<String> {
+  let mut cmd = Command::from(program);
+  cmd.args(args).stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::null()).kill_on_drop(true);
+  #[cfg(target_os = "windows")]
+  { cmd.creation_flags(0x08000000); } // CREATE_NO_WINDOW
+  let out = tokio::time::timeout(PROBE_TIMEOUT, cmd.output()).await.ok()?.ok()?;
+  if !out.status.success() {
+    return None;
+  }
+  String::from_utf8_lossy(&out.stdout).lines().map(str::trim).find(|l| !l.is_empty()).map(str::to_string)
+}
+
+/// The settings store with credential-named keys redacted.
+fn settings_summary(settings: Map<String, Value>) -> Value {
+  let mut summary = Value::Object(settings);
+  logging::scrub(&mut summary);
+  summary
+}
+
+/// The containers folder and the last mount folder of each recent container.
+fn mounts(containers_path: Option<String>, recent: &[Value]) -> Value {
+  let recent: Vec<Value> = recent
+    .iter()
+    .filter_map(|r| {
+      let container = r.get("path")?.as_str()?;
+      let mount = r.get("lastMountPath").and_then(Value::as_str);
+      Some(json!({
+        "container":        container,
+        "container_exists": Path::new(container).is_file(),
+        "mount_path":       mount,
+        "mount_exists":     mount.map(|m| Path::new(m).is_dir()),
+      }))
+    })
+    .collect();
+  let containers = containers_path.map(|p| json!({ "path": p, "exists": Path::new(&p).is_dir() }));
+  json!({ "containers_path": containers, "recent": recent })
+}
+
+/// The last `RECENT_ERRORS` warning and error lines, oldest first.
+fn recent_errors<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+  let matching: Vec<&str> = lines.filter(|l| matches!(l.split_whitespace().nth(1), Some("WARN" | "ERROR"))).collect();
+  matching[matching.len().saturating_sub(RECENT_ERRORS)..].join("\n")
+}
+
+fn write_zip(path: &Path, entries: &[DiagnosticsEntry]) -> Result<(), String> {
+  let file = File::create(path).map_err(|e| format!("cannot create {}: {e}", path.display()))?;
+  let mut zip = ZipWriter::new(file);
+  let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+  for entry in entries {
+    zip.start_file(entry.name.as_str(), options).map_err(|e| format!("cannot add {}: {e}", entry.name))?;
+    zip.write_all(entry.content.as_bytes()).map_err(|e| format!("cannot add {}: {e}", entry.name))?;
+  }
+  zip.finish().map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+  Ok(())
+}
+
+/// Writes draft `id` without the entries named in `omit`.
+fn export(path: &Path, id: u64, omit: &[String]) -> Result<usize, String> {
+  let entries: Vec<DiagnosticsEntry> = match DRAFT.lock().unwrap().as_ref() {
+    Some(draft) if draft.id == id => draft.entries.iter().filter(|e| !omit.contains(&e.name)).cloned().collect(),
+    _ => return Err("the diagnostics preview has expired; review it again".into()),
+  };
+  write_zip(path, &entries)?;
+  DRAFT.lock().unwrap().take_if(|draft| draft.id == id);
+  Ok(entries.len())
+}
+
+/* ─────────── Commands ─────────── */
+
+/// Gathers the bundle for review; replaces any earlier preview.
+#[tauri::command]
+pub async fn preview_diagnostics<R: Runtime>(app: AppHandle<R>) -> DiagnosticsPreview {
+  let entries = gather(&app).await;
+  let id = DRAFT_SEQ.fetch_add(1, Ordering::SeqCst);
+  *DRAFT.lock().unwrap() = Some(Draft { id, entries: entries.clone() });
+  DiagnosticsPreview { id, entries }
+}
+
+/// Zips the reviewed preview `id` to `path`, leaving out the entries in `omit`.
+#[tauri::command]
+pub fn export_diagnostics(path: String, id: u64, omit: Option<Vec<String>>) -> Result<(), String> {
+  let written = export(Path::new(&path), id, &omit.unwrap_or_default())?;
+  log::info!("diagnostics bundle exported with {written} files");
+  Ok(())
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io::Read;
+
+  fn entry(name: &str, content: &str) -> DiagnosticsEntry {
+    DiagnosticsEntry { name: name.into(), content: content.into() }
+  }
+
+  fn read_zip(path: &Path) -> Vec<(String, String)> {
+    let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+    (0..archive.len())
+      .map(|i| {
+        let mut file = archive.by_index(i).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+        (file.name().to_string(), content)
+      })
+      .collect()
+  }
+
+  #[test]
+  fn exports_the_reviewed_draft_without_omitted_entries() {
+    let dir = std::env::temp_dir().join(format!("tvault-diag-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let zip = dir.join("bundle.zip");
+    *DRAFT.lock().unwrap() = Some(Draft { id: 7, entries: vec![entry("system.json", "{}"), entry("logs/tvault.log", "a\nb")] });
+
+    assert!(export(&zip, 6, &[]).is_err());
+    assert_eq!(export(&zip, 7, &["system.json".into()]), Ok(1));
+    assert_eq!(read_zip(&zip), vec![("logs/tvault.log".to_string(), "a\nb".to_string())]);
+    // a draft is written once
+    assert!(export(&zip, 7, &[]).is_err());
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn reports_whether_mount_folders_exist() {
+    let here = std::env::temp_dir();
+    let recent = vec![
+      json!({ "path": "/nowhere/box.tvlt", "lastMountPath": here }),
+      json!({ "path": "/nowhere/other.tvlt" }),
+      json!({ "lastOpenedAt": 1 }),
+    ];
+    let report = mounts(Some("/nowhere".into()), &recent);
+    assert_eq!(report["containers_path"]["exists"], false);
+    assert_eq!(report["recent"].as_array().unwrap().len(), 2);
+    assert_eq!(report["recent"][0]["container_exists"], false);
+    assert_eq!(report["recent"][0]["mount_exists"], true);
+    assert_eq!(report["recent"][1]["mount_exists"], Value::Null);
+  }
+
+  #[test]
+  fn keeps_only_the_latest_warnings_and_errors() {
+    let log: Vec<String> = (0..RECENT_ERRORS + 10)
+      .flat_map(|i| [format!("T INFO  cli_runner: ok {i}"), format!("T WARN  cli_runner: stalled {i}")])
+      .chain(["T ERROR cli_runner: failed".to_string()])
+      .collect();
+    let errors = recent_errors(log.iter().map(String::as_str));
+    let lines: Vec<&str> = errors.lines().collect();
+    assert_eq!(lines.len(), RECENT_ERRORS);
+    assert_eq!(lines[0], "T WARN  cli_runner: stalled 11");
+    assert_eq!(lines[RECENT_ERRORS - 1], "T ERROR cli_runner: failed");
+  }
+
+  #[test]
+  fn settings_summary_has_no_credentials() {
+    let settings = settings_summary(Map::from_iter([
+      ("containersPath".to_string(), json!("/v")),
+      ("lastPassphrase".to_string(), json!("hunter2")),
+      ("shareDefaults".to_string(), json!({ "count": 5 })),
+    ]));
+    assert_eq!(settings["containersPath"], "/v");
+    assert_eq!(settings["lastPassphrase"], "[REDACTED]");
+    assert_eq!(settings["shareDefaults"], "[REDACTED]");
+  }
+}
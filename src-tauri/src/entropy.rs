@@ -0,0 +1,240 @@
+//! entropy.rs — mouse entropy pools for generated passphrases
+//!
+//! The entropy canvas sends batches of pointer coordinates and timestamps.
+//! Each batch goes into a SHA-512 state together with the arrival time of the
+//! batch, and is credited with what `entropy_health` thinks it is worth (at
+//! most half a bit per byte). Once `TARGET_BITS` are in,
+//! the state is finalized, XORed with OS CSPRNG output and returned as the
+//! passphrase. The webview never sees or produces the random bytes themselves.
+//!
+//! Every create-vault flow collects into its own session, opened by
+//! `start_entropy_session` and bound to the calling window. A session hands out
+//! one passphrase and is closed; idle sessions expire after `SESSION_TTL`, and
+//! past `MAX_SESSIONS` the least recently fed one is dropped.
+
+use std::{
+  collections::HashMap,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use rand_core::{OsRng, RngCore};
+use serde::Serialize;
+use sha2::{Digest, Sha512};
+use tauri::{Emitter, Runtime, State, Window};
+
+use crate::entropy_health::{Health, HealthWarning};
+
+pub const TARGET_BITS: u32 = 512;
+/// Passphrase length before base64.
+const PASSPHRASE_BYTES: usize = 32;
+const DOMAIN: &[u8] = b"tvault-entropy-v1";
+/// Sessions without a batch for this long are dropped.
+const SESSION_TTL: Duration = Duration::from_secs(10 * 60);
+/// Open sessions kept at most; starting another drops the least recently fed.
+const MAX_SESSIONS: usize = 16;
+
+/// Open entropy sessions by id.
+#[derive(Default)]
+pub struct EntropyPool {
+  sessions: Mutex<HashMap<String, Session>>,
+}
+
+struct Session {
+  /// Label of the window that opened it.
+  window:    String,
+  state:     Sha512,
+  health:    Health,
+  bits:      u32,
+  started:   Instant,
+  last_seen: Instant,
+}
+
+impl Session {
+  fn new(window: &str) -> Self {
+    let mut state = Sha512::new();
+    state.update(DOMAIN);
+    let now = Instant::now();
+    Session { window: window.to_string(), state, health: Health::default(), bits: 0, started: now, last_seen: now }
+  }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct EntropyStatus {
+  /// Bits credited so far, capped at `target`.
+  pub bits:       u32,
+  pub target:     u32,
+  /// Set once, on the batch that reaches the target.
+  pub passphrase: Option<String>,
+  /// This batch looked scripted and earned nothing.
+  pub warning:    Option<HealthWarning>,
+}
+
+impl EntropyPool {
+  /// Opens an empty session for `window` and returns its id.
+  pub fn start(&self, window: &str) -> String {
+    let mut sessions = self.sessions.lock().unwrap();
+    sessions.retain(|_, s| s.last_seen.elapsed() < SESSION_TTL);
+    while sessions.len() >= MAX_SESSIONS {
+      let Some(oldest) = sessions.iter().min_by_key(|(_, s)| s.last_seen).map(|(k, _)| k.clone()) else { break };
+      sessions.remove(&oldest);
+    }
+    let mut raw = [0u8; 16];
+    OsRng.fill_bytes(&mut raw);
+    let id: String = raw.iter().map(|b| format!("{b:02x}")).collect();
+    sessions.insert(id.clone(), Session::new(window));
+    id
+  }
+
+  /// Mixes a batch into the session; on the batch that reaches the target the
+  /// passphrase is returned and the session is closed.
+  pub fn add(&self, id: &str, window: &str, bytes: &[u8]) -> Result<EntropyStatus, String> {
+    let mut sessions = self.sessions.lock().unwrap();
+    let session = sessions
+      .get_mut(id)
+      .filter(|s| s.last_seen.elapsed() < SESSION_TTL && s.window == window)
+      .ok_or("entropy session expired or already used")?;
+    let jitter = session.started.elapsed().as_nanos() as u64;
+    session.state.update((bytes.len() as u64).to_le_bytes());
+    session.state.update(bytes);
+    session.state.update(jitter.to_le_bytes());
+    let assessed = session.health.assess(bytes);
+    session.bits += assessed.credit;
+    session.last_seen = Instant::now();
+
+    if session.bits < TARGET_BITS {
+      return Ok(EntropyStatus { bits: session.bits, target: TARGET_BITS, passphrase: None, warning: assessed.warning });
+    }
+    let full = sessions.remove(id).ok_or("entropy session expired or already used")?;
+    Ok(EntropyStatus { bits: TARGET_BITS, target: TARGET_BITS, passphrase: Some(B64.encode(derive(full.state))), warning: None })
+  }
+
+  /// Sessions still waiting for batches.
+  pub fn open_sessions(&self) -> usize {
+    self.sessions.lock().unwrap().values().filter(|s| s.last_seen.elapsed() < SESSION_TTL).count()
+  }
+
+  /// Drops a session the user walked away from.
+  pub fn end(&self, id: &str) -> bool {
+    self.sessions.lock().unwrap().remove(id).is_some()
+  }
+}
+
+/// Pool output XOR OS randomness: either alone is enough.
+fn derive(state: Sha512) -> [u8; PASSPHRASE_BYTES] {
+  let pooled = state.finalize();
+  let mut out = [0u8; PASSPHRASE_BYTES];
+  OsRng.fill_bytes(&mut out);
+  for (o, p) in out.iter_mut().zip(pooled.iter()) {
+    *o ^= p;
+  }
+  out
+}
+
+/* ─────────── Commands ─────────── */
+
+#[tauri::command]
+pub fn start_entropy_session<R: Runtime>(window: Window<R>, pool: State<'_, EntropyPool>) -> String {
+  pool.start(window.label())
+}
+
+/// Accepts a batch from the entropy canvas; `entropy_ready` is emitted to the
+/// window once the passphrase is out (it only travels in the return value).
+#[tauri::command]
+pub fn entropy_batch<R: Runtime>(window: Window<R>, pool: State<'_, EntropyPool>, session: String, bytes: Vec<u8>) -> Result<EntropyStatus, String> {
+  let status = pool.add(&session, window.label(), &bytes)?;
+  if status.passphrase.is_some() {
+    window.emit_to(window.label(), "entropy_ready", &session).ok();
+  }
+  Ok(status)
+}
+
+#[tauri::command]
+pub fn end_entropy_session(pool: State<'_, EntropyPool>, session: String) -> bool {
+  pool.end(&session)
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// `points` triples of an irregular pointer path.
+  fn wiggle(seed: u32, points: usize) -> Vec<u8> {
+    let (mut state, mut x, mut y) = (seed, 0u8, 0u8);
+    (0..points)
+      .flat_map(|i| {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        x = x.wrapping_add((state >> 16) as u8 % 17);
+        y = y.wrapping_add((state >> 24) as u8 % 13);
+        [x, y, (i * 16) as u8]
+      })
+      .collect()
+  }
+
+  #[test]
+  fn collects_until_the_target() {
+    let pool = EntropyPool::default();
+    let id = pool.start("main");
+    let first = pool.add(&id, "main", &wiggle(1, 16)).unwrap();
+    assert!(first.bits > 0 && first.bits <= 24 && first.passphrase.is_none());
+
+    let status = pool.add(&id, "main", &wiggle(2, 400)).unwrap();
+    assert_eq!(status.bits, TARGET_BITS);
+    let pass = B64.decode(status.passphrase.unwrap()).unwrap();
+    assert_eq!(pass.len(), PASSPHRASE_BYTES);
+
+    // one passphrase per session
+    assert_eq!(pool.add(&id, "main", &wiggle(3, 16)).unwrap_err(), "entropy session expired or already used");
+  }
+
+  #[test]
+  fn scripted_input_is_flagged_and_not_credited() {
+    let pool = EntropyPool::default();
+    let id = pool.start("main");
+    let batch = wiggle(4, 16);
+    let credited = pool.add(&id, "main", &batch).unwrap().bits;
+    let replay = pool.add(&id, "main", &batch).unwrap();
+    assert_eq!((replay.bits, replay.warning), (credited, Some(HealthWarning::DuplicateBatch)));
+  }
+
+  #[test]
+  fn sessions_do_not_share_progress() {
+    let pool = EntropyPool::default();
+    let abandoned = pool.start("main");
+    pool.add(&abandoned, "main", &wiggle(5, 300)).unwrap();
+
+    let fresh = pool.start("main");
+    assert!(pool.add(&fresh, "main", &wiggle(6, 16)).unwrap().bits <= 24);
+    assert!(pool.add(&fresh, "other", &wiggle(7, 16)).is_err());
+    assert!(pool.end(&abandoned));
+    assert!(pool.add(&abandoned, "main", &wiggle(8, 16)).is_err());
+  }
+
+  #[test]
+  fn starting_past_the_cap_drops_the_least_recently_fed_session() {
+    let pool = EntropyPool::default();
+    let ids: Vec<String> = (0..MAX_SESSIONS).map(|_| pool.start("main")).collect();
+    // every session but the second is fed again, so that one is the stalest
+    for (i, id) in ids.iter().enumerate().filter(|(i, _)| *i != 1) {
+      pool.add(id, "main", &wiggle(100 + i as u32, 16)).unwrap();
+    }
+    let newest = pool.start("main");
+    assert_eq!(pool.open_sessions(), MAX_SESSIONS);
+    assert!(pool.add(&ids[1], "main", &wiggle(11, 16)).is_err());
+    assert!(pool.add(&ids[0], "main", &wiggle(12, 16)).is_ok());
+    assert!(pool.add(&newest, "main", &wiggle(13, 16)).is_ok());
+  }
+
+  #[test]
+  fn same_mouse_input_never_gives_the_same_passphrase() {
+    let pool = EntropyPool::default();
+    let (a, b) = (pool.start("main"), pool.start("main"));
+    let a = pool.add(&a, "main", &wiggle(9, 400)).unwrap().passphrase;
+    let b = pool.add(&b, "main", &wiggle(9, 400)).unwrap().passphrase;
+    assert!(a.is_some());
+    assert_ne!(a, b);
+  }
+}
@@ -0,0 +1,212 @@
+//! entropy_health.rs — health tests on mouse samples, after NIST SP 800-90B
+//!
+//! The canvas sends `(x, y, t)` byte triples. The tests look at the movement
+//! between consecutive points, `(dx, dy)`, as one sample: a scripted straight
+//! line repeats the same movement, a hand does not.
+//!
+//! * Repetition count test (4.4.1) and adaptive proportion test (4.4.2) run
+//!   continuously over a session, with cutoffs for 1 bit per sample at a false
+//!   alarm rate of 2^-20.
+//! * A most-common-value estimate (6.3.1) of each batch scales its credit:
+//!   full credit (half a bit per byte) from 2 bits per sample, less below.
+//! * A batch seen before earns nothing; the last `MAX_SEEN_BATCHES` are kept.
+//!
+//! A failed test zeroes the batch's credit and raises a warning for the UI.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Identical samples in a row that fail the repetition count test.
+const RCT_CUTOFF: u32 = 21;
+/// Adaptive proportion window, and the count of its first sample that fails it.
+const APT_WINDOW: u32 = 64;
+const APT_CUTOFF: u32 = 51;
+/// Per-sample min-entropy from which a batch earns full credit.
+const FULL_CREDIT_BITS: f64 = 2.0;
+/// Upper 99% bound used by the most-common-value estimate.
+const Z_99: f64 = 2.576;
+const CREDIT_PER_BYTE: f64 = 0.5;
+/// Batch hashes remembered per session; the oldest is forgotten first. A
+/// session fills up in a few dozen batches, so only a stuck one gets here.
+const MAX_SEEN_BATCHES: usize = 1024;
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthWarning {
+  /// The same movement over and over.
+  Repetition,
+  /// One movement dominates the recent samples.
+  Proportion,
+  /// A batch identical to an earlier one.
+  DuplicateBatch,
+}
+
+/// Bits a batch earns, and why it earned none.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Assessment {
+  pub credit:  u32,
+  pub warning: Option<HealthWarning>,
+}
+
+type Sample = (u8, u8);
+
+/// Test state carried across the batches of one session.
+#[derive(Default)]
+pub(crate) struct Health {
+  last_point: Option<Sample>,
+  rct:        Option<(Sample, u32)>,
+  /// First sample of the window, its count, samples seen.
+  apt:        Option<(Sample, u32, u32)>,
+  batches:    HashSet<[u8; 32]>,
+  /// `batches` in the order they were seen, for eviction.
+  seen:       VecDeque<[u8; 32]>,
+}
+
+impl Health {
+  pub fn assess(&mut self, bytes: &[u8]) -> Assessment {
+    let digest: [u8; 32] = Sha256::digest(bytes).into();
+    if !self.batches.insert(digest) {
+      return Assessment { credit: 0, warning: Some(HealthWarning::DuplicateBatch) };
+    }
+    self.seen.push_back(digest);
+    if self.seen.len() > MAX_SEEN_BATCHES {
+      if let Some(oldest) = self.seen.pop_front() {
+        self.batches.remove(&oldest);
+      }
+    }
+    let mut samples = Vec::new();
+    for triple in bytes.chunks_exact(3) {
+      let point = (triple[0], triple[1]);
+      if let Some(last) = self.last_point.replace(point) {
+        samples.push((point.0.wrapping_sub(last.0), point.1.wrapping_sub(last.1)));
+      }
+    }
+
+    let mut warning = None;
+    for s in &samples {
+      warning = warning.or(self.repetition(*s)).or(self.proportion(*s));
+    }
+    if warning.is_some() {
+      return Assessment { credit: 0, warning };
+    }
+    let scale = (min_entropy(&samples) / FULL_CREDIT_BITS).min(1.0);
+    Assessment { credit: (bytes.len() as f64 * CREDIT_PER_BYTE * scale) as u32, warning: None }
+  }
+
+  fn repetition(&mut self, s: Sample) -> Option<HealthWarning> {
+    let run = match self.rct {
+      Some((prev, n)) if prev == s => n + 1,
+      _ => 1,
+    };
+    self.rct = Some((s, run));
+    (run >= RCT_CUTOFF).then_some(HealthWarning::Repetition)
+  }
+
+  fn proportion(&mut self, s: Sample) -> Option<HealthWarning> {
+    let (first, count, seen) = match self.apt {
+      Some((first, count, seen)) if seen < APT_WINDOW => (first, count + u32::from(first == s), seen + 1),
+      _ => (s, 1, 1),
+    };
+    self.apt = Some((first, count, seen));
+    (count >= APT_CUTOFF).then_some(HealthWarning::Proportion)
+  }
+}
+
+/// Most-common-value min-entropy estimate, bits per sample.
+fn min_entropy(samples: &[Sample]) -> f64 {
+  let n = samples.len();
+  if n < 2 {
+    return 0.0;
+  }
+  let mut counts = HashMap::new();
+  for s in samples {
+    *counts.entry(s).or_insert(0u32) += 1;
+  }
+  let p = f64::from(counts.values().copied().max().unwrap_or_default()) / n as f64;
+  let upper = (p + Z_99 * (p * (1.0 - p) / (n - 1) as f64).sqrt()).min(1.0);
+  -upper.log2()
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Triples through `points`, with a steady 16 ms clock.
+  fn batch(points: impl Iterator<Item = (u8, u8)>) -> Vec<u8> {
+    points.enumerate().flat_map(|(i, (x, y))| [x, y, (i * 16) as u8]).collect()
+  }
+
+  /// A wobbly hand-drawn curve.
+  fn hand(seed: u32) -> Vec<u8> {
+    let mut state = seed;
+    batch((0..16).map(move |i| {
+      state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+      ((i * 7 + (state >> 16) % 9) as u8, (i * 3 + (state >> 20) % 11) as u8)
+    }))
+  }
+
+  #[test]
+  fn hand_movement_earns_credit_without_warnings() {
+    let mut health = Health::default();
+    let credited: u32 = (0..8)
+      .map(|seed| {
+        let a = health.assess(&hand(seed));
+        assert_eq!(a.warning, None);
+        a.credit
+      })
+      .sum();
+    assert!(credited >= 8 * 16, "{credited}");
+  }
+
+  #[test]
+  fn a_straight_line_earns_nothing_and_is_flagged() {
+    let mut health = Health::default();
+    let mut offset = 0u8;
+    let mut line = || {
+      let start = offset;
+      offset = offset.wrapping_add(32);
+      batch((0..16).map(move |i| (start.wrapping_add(i * 2), 100)))
+    };
+    assert_eq!(health.assess(&line()).credit, 0);
+    assert_eq!(health.assess(&line()).warning, Some(HealthWarning::Repetition));
+  }
+
+  #[test]
+  fn a_dominant_movement_fails_the_proportion_test() {
+    let mut health = Health::default();
+    let mut x = 0u8;
+    let warned = (0..8).any(|_| {
+      // one step to the right, with a hop per batch to dodge the repetition test
+      let b = batch((0..16).map(|i| {
+        x = x.wrapping_add(if i == 9 { 9 } else { 1 });
+        (x, 50)
+      }));
+      health.assess(&b).warning == Some(HealthWarning::Proportion)
+    });
+    assert!(warned);
+  }
+
+  #[test]
+  fn a_replayed_batch_earns_nothing() {
+    let mut health = Health::default();
+    let b = hand(1);
+    assert!(health.assess(&b).credit > 0);
+    assert_eq!(health.assess(&b), Assessment { credit: 0, warning: Some(HealthWarning::DuplicateBatch) });
+  }
+
+  #[test]
+  fn only_the_most_recent_batches_are_remembered() {
+    let mut health = Health::default();
+    let first = hand(0);
+    for seed in 0..=MAX_SEEN_BATCHES as u32 {
+      health.assess(&hand(seed));
+    }
+    assert_eq!((health.batches.len(), health.seen.len()), (MAX_SEEN_BATCHES, MAX_SEEN_BATCHES));
+    assert!(!health.batches.contains(&<[u8; 32]>::from(Sha256::digest(&first))));
+    assert_eq!(health.assess(&hand(MAX_SEEN_BATCHES as u32)).warning, Some(HealthWarning::DuplicateBatch));
+  }
+}
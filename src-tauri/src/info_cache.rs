@@ -0,0 +1,284 @@
+//! info_cache.rs — parsed `container info` results, persisted in the app data dir
+//!
+//! Entries are keyed by canonical path and only served while the file still has
+//! the size and modification time (and, when enabled, the header hash) it had
+//! when the info was read. That state is stamped before the core is started,
+//! so a container rewritten while its info is being read is never served from
+//! the cache. Operations that rewrite a container invalidate it explicitly;
+//! the least recently used entries go once the cache is full.
+
+use std::{
+  collections::HashMap,
+  fs,
+  path::{Path, PathBuf},
+  sync::Mutex,
+  time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime, State, Wry};
+
+use crate::container;
+
+pub const CACHE_FILE: &str = "container-info-cache.json";
+/// Settings store key: also compare a hash of the container header.
+pub const HASH_HEADERS_SETTING: &str = "infoCacheHashHeaders";
+const MAX_ENTRIES: usize = 512;
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct Fingerprint {
+  size:          u64,
+  mtime_secs:    u64,
+  mtime_nanos:   u32,
+  header_sha256: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Entry {
+  fingerprint: Fingerprint,
+  data:        Value,
+  /// Logical clock of the last hit, for eviction.
+  used:        u64,
+}
+
+/// A container's cache key and on-disk state, taken before reading its info.
+#[derive(Clone, Debug)]
+pub struct Stamp {
+  key:         String,
+  fingerprint: Fingerprint,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Stored {
+  version: u32,
+  clock:   u64,
+  entries: HashMap<String, Entry>,
+}
+
+pub struct InfoCache {
+  /// `None` keeps the cache in memory only.
+  file:         Option<PathBuf>,
+  hash_headers: bool,
+  capacity:     usize,
+  state:        Mutex<Stored>,
+  /// Serialises writes of `file`; holds the clock of the newest state written.
+  written:      Mutex<u64>,
+}
+
+impl InfoCache {
+  /// Loads `file` if it exists; an unreadable or outdated cache starts empty.
+  pub fn open(file: Option<PathBuf>, hash_headers: bool) -> Self {
+    let stored = file
+      .as_deref()
+      .and_then(|f| fs::read(f).ok())
+      .and_then(|raw| serde_json::from_slice::<Stored>(&raw).ok())
+      .filter(|s| s.version == FORMAT_VERSION)
+      .unwrap_or_default();
+    let written = Mutex::new(stored.clock);
+    InfoCache { file, hash_headers, capacity: MAX_ENTRIES, state: Mutex::new(stored), written }
+  }
+
+  /// Cache in the app data dir, honouring the header-hash setting.
+  pub fn for_app<R: Runtime>(app: &AppHandle<R>) -> Self {
+    let file = app.path().app_data_dir().ok().map(|d| d.join(CACHE_FILE));
+    let hash_headers = crate::settings::get(app, HASH_HEADERS_SETTING).and_then(|v| v.as_bool()).unwrap_or(false);
+    Self::open(file, hash_headers)
+  }
+
+  pub fn get(&self, path: &str) -> Option<Value> {
+    let Stamp { key, fingerprint: current } = self.stamp(path)?;
+    let mut st = self.state.lock().unwrap();
+    st.clock += 1;
+    let clock = st.clock;
+    match st.entries.get_mut(&key) {
+      Some(entry) if entry.fingerprint == current => {
+        entry.used = clock;
+        Some(entry.data.clone())
+      }
+      Some(_) => {
+        st.entries.remove(&key);
+        let snapshot = snapshot(&st);
+        drop(st);
+        self.save(snapshot);
+        None
+      }
+      None => None,
+    }
+  }
+
+  /// Remembers `data` for the container as it was when `stamp` was taken.
+  pub fn put(&self, stamp: Stamp, data: Value) {
+    let Stamp { key, fingerprint } = stamp;
+    let mut st = self.state.lock().unwrap();
+    st.clock += 1;
+    let used = st.clock;
+    st.entries.insert(key, Entry { fingerprint, data, used });
+    while st.entries.len() > self.capacity {
+      let Some(oldest) = st.entries.iter().min_by_key(|(_, e)| e.used).map(|(k, _)| k.clone()) else { break };
+      st.entries.remove(&oldest);
+    }
+    let snapshot = snapshot(&st);
+    drop(st);
+    self.save(snapshot);
+  }
+
+  pub fn invalidate(&self, path: &str) {
+    let key = canonical(path);
+    let mut st = self.state.lock().unwrap();
+    if st.entries.remove(&key).is_some() {
+      st.clock += 1;
+      let snapshot = snapshot(&st);
+      drop(st);
+      self.save(snapshot);
+    }
+  }
+
+  /// The container as it is on disk right now; `None` if it cannot be read.
+  pub fn stamp(&self, path: &str) -> Option<Stamp> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    let header_sha256 = if self.hash_headers { Some(container::header_hash(Path::new(path))?) } else { None };
+    let fp = Fingerprint {
+      size: meta.len(),
+      mtime_secs: mtime.as_secs(),
+      mtime_nanos: mtime.subsec_nanos(),
+      header_sha256,
+    };
+    Some(Stamp { key: canonical(path), fingerprint: fp })
+  }
+
+  /// Best-effort write-then-rename so a crash never leaves a torn file. Runs
+  /// outside the state lock; a snapshot older than the last one written is dropped.
+  fn save(&self, snapshot: Stored) {
+    let Some(file) = &self.file else { return };
+    let mut written = self.written.lock().unwrap();
+    if snapshot.clock <= *written {
+      return;
+    }
+    let Ok(raw) = serde_json::to_vec(&snapshot) else { return };
+    *written = snapshot.clock;
+    if let Some(dir) = file.parent() {
+      let _ = fs::create_dir_all(dir);
+    }
+    let tmp = file.with_extension("json.tmp");
+    if fs::write(&tmp, raw).is_ok() {
+      let _ = fs::rename(&tmp, file);
+    }
+  }
+}
+
+fn snapshot(st: &Stored) -> Stored {
+  Stored { version: FORMAT_VERSION, clock: st.clock, entries: st.entries.clone() }
+}
+
+/// Cache key: the path with its directory resolved.
+fn canonical(path: &str) -> String {
+  container::key(Path::new(path)).display().to_string()
+}
+
+/// Drops the cached info for `path`, e.g. after it was replaced outside the app.
+#[tauri::command]
+pub fn invalidate_container_info(cache: State<'_, InfoCache>, path: String) {
+  cache.invalidate(&path);
+}
+
+/// Creates the cache once the app data dir is known.
+pub fn init(app: &AppHandle<Wry>) {
+  app.manage(InfoCache::for_app(app));
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs::File;
+  use serde_json::json;
+
+  struct Dir(PathBuf);
+
+  impl Dir {
+    fn new(name: &str) -> Self {
+      let dir = std::env::temp_dir().join(format!("tvault-info-cache-{name}-{}", std::process::id()));
+      let _ = fs::remove_dir_all(&dir);
+      fs::create_dir_all(&dir).unwrap();
+      Dir(dir)
+    }
+
+    fn file(&self, name: &str, contents: &str) -> String {
+      let p = self.0.join(name);
+      fs::write(&p, contents).unwrap();
+      p.display().to_string()
+    }
+  }
+
+  impl Drop for Dir {
+    fn drop(&mut self) {
+      let _ = fs::remove_dir_all(&self.0);
+    }
+  }
+
+  #[test]
+  fn serves_until_the_file_changes() {
+    let dir = Dir::new("changes");
+    let vault = dir.file("a.tvlt", "v1");
+    let cache = InfoCache::open(None, false);
+
+    assert_eq!(cache.get(&vault), None);
+    cache.put(cache.stamp(&vault).unwrap(), json!({ "name": "a" }));
+    assert_eq!(cache.get(&vault), Some(json!({ "name": "a" })));
+
+    fs::write(&vault, "version 2").unwrap();
+    assert_eq!(cache.get(&vault), None);
+  }
+
+  #[test]
+  fn a_rewrite_while_reading_is_not_cached_as_current() {
+    let dir = Dir::new("race");
+    let vault = dir.file("a.tvlt", "v1");
+    let cache = InfoCache::open(None, false);
+
+    let stamp = cache.stamp(&vault).unwrap();
+    fs::write(&vault, "version 2").unwrap();
+    cache.put(stamp, json!({ "name": "read from v1" }));
+    assert_eq!(cache.get(&vault), None);
+  }
+
+  #[test]
+  fn header_hash_catches_same_size_rewrites() {
+    let dir = Dir::new("header");
+    let vault = dir.file("a.tvlt", "AAAA");
+    let cache = InfoCache::open(None, true);
+    cache.put(cache.stamp(&vault).unwrap(), json!({ "name": "a" }));
+
+    let mtime = fs::metadata(&vault).unwrap().modified().unwrap();
+    fs::write(&vault, "BBBB").unwrap();
+    File::options().write(true).open(&vault).unwrap().set_modified(mtime).unwrap();
+
+    assert_eq!(cache.get(&vault), None);
+  }
+
+  #[test]
+  fn persists_and_evicts_least_recently_used() {
+    let dir = Dir::new("persist");
+    let store = dir.0.join("cache").join(CACHE_FILE);
+    let paths: Vec<String> = (0..3).map(|i| dir.file(&format!("{i}.tvlt"), "x")).collect();
+
+    let mut cache = InfoCache::open(Some(store.clone()), false);
+    cache.capacity = 2;
+    cache.put(cache.stamp(&paths[0]).unwrap(), json!(0));
+    cache.put(cache.stamp(&paths[1]).unwrap(), json!(1));
+    assert!(cache.get(&paths[0]).is_some());
+    cache.put(cache.stamp(&paths[2]).unwrap(), json!(2));
+
+    let reopened = InfoCache::open(Some(store), false);
+    assert_eq!(reopened.get(&paths[0]), Some(json!(0)));
+    assert_eq!(reopened.get(&paths[1]), None);
+    assert_eq!(reopened.get(&paths[2]), Some(json!(2)));
+
+    reopened.invalidate(&paths[2]);
+    assert_eq!(reopened.get(&paths[2]), None);
+  }
+}
@@ -0,0 +1,372 @@
+//! key_file.rs — key files as an additional unlock factor
+//!
+//! Any file can serve as a key file: its SHA-256 becomes part of the key
+//! material. The core only takes passphrases, so the password and the hashes
+//! of all key files are combined here into the passphrase it is given:
+//!
+//! `base64(SHA-256("tvault-keyfile-v1" ‖ len ‖ password ‖ sorted file hashes))`
+//!
+//! The order of the key files does not matter, and a vault sealed with key
+//! files alone uses an empty password. Without key files the password is
+//! passed through unchanged, so existing vaults keep opening.
+//!
+//! A successful seal or reseal records a short check value per key file and
+//! container in `key-files.json`. `check_key_files` compares against it, so a key file that
+//! was edited since (which locks the vault for good) is reported before the
+//! unseal fails. The check values are 32 bits, too short to stand in for the
+//! file itself.
+
+use std::{
+  collections::HashMap,
+  fs::{self, File, OpenOptions},
+  io::{self, Write},
+  path::{Path, PathBuf},
+  sync::Mutex,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager, Runtime, State, Wry};
+
+use crate::{container, data_file};
+
+pub const KEY_FILES_FILE: &str = "key-files.json";
+const FORMAT_VERSION: u32 = 1;
+const DOMAIN: &[u8] = b"tvault-keyfile-v1";
+const CHECK_DOMAIN: &[u8] = b"tvault-keyfile-check-v1";
+/// Size of a generated key file.
+const GENERATED_BYTES: usize = 64;
+
+/// A key file as it was when a container was sealed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecordedKeyFile {
+  /// File name only; the file may be moved.
+  pub name:        String,
+  pub check:       String,
+  pub recorded_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Stored {
+  version: u32,
+  vaults:  HashMap<String, Vec<RecordedKeyFile>>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyFileState {
+  /// Same contents as at seal time.
+  Unchanged,
+  /// A file of that name was used, but its contents differ.
+  Changed,
+  /// Not one of the files the container was sealed with.
+  Unknown,
+  /// Used at seal time but not given now.
+  Missing,
+  /// Cannot be read.
+  Unreadable,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct KeyFileStatus {
+  /// Path as given; `None` for a missing file, known by name only.
+  pub path:  Option<String>,
+  pub name:  String,
+  pub state: KeyFileState,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct KeyFileCheck {
+  /// Whether the key files were recorded when the container was sealed.
+  pub recorded: bool,
+  pub files:    Vec<KeyFileStatus>,
+  /// "backup.key has changed since the vault was sealed".
+  pub warning:  Option<String>,
+}
+
+/// Key file check values by container.
+pub struct KeyFileStore {
+  /// `None` keeps the records in memory only.
+  file:     Option<PathBuf>,
+  vaults:   Mutex<HashMap<String, Vec<RecordedKeyFile>>>,
+  /// Why the file could not be loaded nor moved aside; saving is refused.
+  unusable: Option<String>,
+}
+
+impl KeyFileStore {
+  /// An unusable file is moved aside (see `data_file`) rather than overwritten.
+  pub fn open(file: Option<PathBuf>) -> Self {
+    let loaded = file.as_deref().map(|f| data_file::load::<Stored>(f, FORMAT_VERSION, |s| s.version)).transpose();
+    let (stored, unusable) = match loaded {
+      Ok(stored) => (stored.flatten().unwrap_or_default(), None),
+      Err(e) => (Stored::default(), Some(e)),
+    };
+    KeyFileStore { file, vaults: Mutex::new(stored.vaults), unusable }
+  }
+
+  pub fn for_app<R: Runtime>(app: &AppHandle<R>) -> Self {
+    Self::open(app.path().app_data_dir().ok().map(|d| d.join(KEY_FILES_FILE)))
+  }
+
+  /// Replaces what is recorded for `container`; no key files clears it.
+  pub fn record(&self, container: &str, key_files: &[String]) -> Result<(), String> {
+    self.put(container, records(key_files)?)
+  }
+
+  fn put(&self, container: &str, recorded: Vec<RecordedKeyFile>) -> Result<(), String> {
+    let mut vaults = self.vaults.lock().unwrap();
+    // kept only once it is on disk
+    let mut next = vaults.clone();
+    if recorded.is_empty() {
+      next.remove(&vault_key(container));
+    } else {
+      next.insert(vault_key(container), recorded);
+    }
+    self.save(&next)?;
+    *vaults = next;
+    Ok(())
+  }
+
+  pub fn check(&self, container: &str, key_files: &[String]) -> KeyFileCheck {
+    let recorded = self.vaults.lock().unwrap().get(&vault_key(container)).cloned();
+    let Some(recorded) = recorded else {
+      let files = key_files.iter().map(|p| KeyFileStatus { path: Some(p.clone()), name: file_name(p), state: KeyFileState::Unknown }).collect();
+      return KeyFileCheck { recorded: false, files, warning: None };
+    };
+
+    let mut matched = vec![false; recorded.len()];
+    let mut files: Vec<KeyFileStatus> = key_files
+      .iter()
+      .map(|p| {
+        let name = file_name(p);
+        let state = match hash_file(Path::new(p)) {
+          Err(_) => KeyFileState::Unreadable,
+          Ok(hash) => {
+            let check = check_value(&hash);
+            match recorded.iter().position(|r| r.check == check) {
+              Some(i) => {
+                matched[i] = true;
+                KeyFileState::Unchanged
+              }
+              None if recorded.iter().any(|r| r.name == name) => KeyFileState::Changed,
+              None => KeyFileState::Unknown,
+            }
+          }
+        };
+        KeyFileStatus { path: Some(p.clone()), name, state }
+      })
+      .collect();
+    // a changed file stands in for its recorded entry
+    let changed: Vec<String> = files.iter().filter(|f| f.state == KeyFileState::Changed).map(|f| f.name.clone()).collect();
+    for (r, _) in recorded.iter().zip(&matched).filter(|(r, m)| !**m && !changed.contains(&r.name)) {
+      files.push(KeyFileStatus { path: None, name: r.name.clone(), state: KeyFileState::Missing });
+    }
+
+    let warning = files.iter().find(|f| f.state != KeyFileState::Unchanged).map(|f| match f.state {
+      KeyFileState::Changed => format!("{} has changed since the vault was sealed", f.name),
+      KeyFileState::Unknown => format!("{} is not one of the key files the vault was sealed with", f.name),
+      KeyFileState::Missing => format!("the vault was sealed with {} as well", f.name),
+      _ => format!("cannot read {}", f.name),
+    });
+    KeyFileCheck { recorded: true, files, warning }
+  }
+
+  fn save(&self, vaults: &HashMap<String, Vec<RecordedKeyFile>>) -> Result<(), String> {
+    if let Some(e) = &self.unusable {
+      return Err(format!("key file records cannot be saved: {e}"));
+    }
+    let Some(file) = &self.file else { return Ok(()) };
+    let raw = serde_json::to_vec_pretty(&Stored { version: FORMAT_VERSION, vaults: vaults.clone() }).map_err(|e| e.to_string())?;
+    if let Some(dir) = file.parent() {
+      fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let tmp = file.with_extension("json.tmp");
+    fs::write(&tmp, raw).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, file).map_err(|e| e.to_string())
+  }
+}
+
+/* ─────────── Key material ─────────── */
+
+/// The passphrase the core is given for `password` and `key_files`.
+pub fn combine(password: &str, key_files: &[String]) -> Result<String, String> {
+  if key_files.is_empty() {
+    return Ok(password.to_string());
+  }
+  let mut hashes = key_files.iter().map(|p| hash_file(Path::new(p))).collect::<Result<Vec<_>, _>>()?;
+  hashes.sort_unstable();
+  let mut state = Sha256::new();
+  state.update(DOMAIN);
+  state.update((password.len() as u64).to_le_bytes());
+  state.update(password.as_bytes());
+  for h in &hashes {
+    state.update(h);
+  }
+  Ok(B64.encode(state.finalize()))
+}
+
+fn hash_file(path: &Path) -> Result<[u8; 32], String> {
+  let mut file = File::open(path).map_err(|e| format!("cannot read key file {}: {e}", path.display()))?;
+  let mut state = Sha256::new();
+  let len = io::copy(&mut file, &mut state).map_err(|e| format!("cannot read key file {}: {e}", path.display()))?;
+  if len == 0 {
+    return Err(format!("key file {} is empty", path.display()));
+  }
+  Ok(state.finalize().into())
+}
+
+fn check_value(hash: &[u8; 32]) -> String {
+  let mut state = Sha256::new();
+  state.update(CHECK_DOMAIN);
+  state.update(hash);
+  state.finalize()[..4].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn file_name(path: &str) -> String {
+  Path::new(path).file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_else(|| path.to_string())
+}
+
+fn vault_key(container: &str) -> String {
+  container::key(Path::new(container)).display().to_string()
+}
+
+/// Check values for `key_files`, taken before a seal or reseal starts so the
+/// record matches the files the core was given.
+pub(crate) fn records(key_files: &[String]) -> Result<Vec<RecordedKeyFile>, String> {
+  let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+  key_files
+    .iter()
+    .map(|p| Ok(RecordedKeyFile { name: file_name(p), check: check_value(&hash_file(Path::new(p))?), recorded_at: now }))
+    .collect()
+}
+
+/// Records the key files a successful seal or reseal of `container` used, when the app keeps records.
+pub(crate) fn record<R: Runtime>(app: &AppHandle<R>, container: &str, recorded: Vec<RecordedKeyFile>) -> Result<(), String> {
+  match app.try_state::<KeyFileStore>() {
+    Some(store) => store.put(container, recorded),
+    None => Ok(()),
+  }
+}
+
+/* ─────────── Commands ─────────── */
+
+/// Writes a new key file of random bytes; never overwrites an existing file.
+#[tauri::command]
+pub fn generate_key_file(path: String) -> Result<(), String> {
+  let mut raw = [0u8; GENERATED_BYTES];
+  OsRng.fill_bytes(&mut raw);
+  let mut file = OpenOptions::new().write(true).create_new(true).open(&path).map_err(|e| format!("cannot create key file {path}: {e}"))?;
+  file.write_all(&raw).and_then(|_| file.sync_all()).map_err(|e| format!("cannot write key file {path}: {e}"))
+}
+
+#[tauri::command]
+pub fn check_key_files(store: State<'_, KeyFileStore>, container_path: String, key_files: Vec<String>) -> KeyFileCheck {
+  store.check(&container_path, &key_files)
+}
+
+/// Loads the key file records once the app data dir is known.
+pub fn init(app: &AppHandle<Wry>) {
+  app.manage(KeyFileStore::for_app(app));
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct Dir(PathBuf);
+
+  impl Dir {
+    fn new(name: &str) -> Self {
+      let dir = std::env::temp_dir().join(format!("tvault-keyfile-{name}-{}", std::process::id()));
+      fs::create_dir_all(&dir).unwrap();
+      Dir(dir)
+    }
+
+    fn file(&self, name: &str, contents: &[u8]) -> String {
+      let path = self.0.join(name);
+      fs::write(&path, contents).unwrap();
+      path.display().to_string()
+    }
+  }
+
+  impl Drop for Dir {
+    fn drop(&mut self) {
+      let _ = fs::remove_dir_all(&self.0);
+    }
+  }
+
+  #[test]
+  fn key_files_change_the_passphrase_in_any_order() {
+    let dir = Dir::new("combine");
+    let (a, b) = (dir.file("a.key", b"first"), dir.file("b.jpg", b"second"));
+    assert_eq!(combine("pw", &[]).unwrap(), "pw");
+
+    let both = combine("pw", &[a.clone(), b.clone()]).unwrap();
+    assert_eq!(both, combine("pw", &[b.clone(), a.clone()]).unwrap());
+    assert_ne!(both, combine("other", &[a.clone(), b.clone()]).unwrap());
+    assert_ne!(both, combine("pw", &[b]).unwrap());
+    // key files alone
+    assert_ne!(combine("", &[a]).unwrap(), "");
+
+    assert!(combine("pw", &[dir.file("empty", b"")]).unwrap_err().contains("is empty"));
+    assert!(combine("pw", &[dir.0.join("nope").display().to_string()]).unwrap_err().starts_with("cannot read key file"));
+  }
+
+  #[test]
+  fn generated_key_files_are_random_and_never_overwrite() {
+    let dir = Dir::new("generate");
+    let (a, b) = (dir.0.join("a.key").display().to_string(), dir.0.join("b.key").display().to_string());
+    generate_key_file(a.clone()).unwrap();
+    generate_key_file(b.clone()).unwrap();
+    assert_eq!(fs::read(&a).unwrap().len(), GENERATED_BYTES);
+    assert_ne!(fs::read(&a).unwrap(), fs::read(&b).unwrap());
+    assert!(generate_key_file(a).is_err());
+  }
+
+  #[test]
+  fn warns_when_a_key_file_changed_since_sealing() {
+    let dir = Dir::new("check");
+    let store = KeyFileStore::open(None);
+    let container = dir.0.join("box.tvlt").display().to_string();
+    let (key, photo) = (dir.file("backup.key", b"one"), dir.file("photo.jpg", b"two"));
+    store.record(&container, &[key.clone(), photo.clone()]).unwrap();
+
+    let ok = store.check(&container, &[photo.clone(), key.clone()]);
+    assert!(ok.recorded && ok.warning.is_none());
+
+    fs::write(&key, b"one, edited").unwrap();
+    let changed = store.check(&container, &[key.clone(), photo.clone()]);
+    let states: Vec<KeyFileState> = changed.files.iter().map(|f| f.state).collect();
+    assert_eq!(states, [KeyFileState::Changed, KeyFileState::Unchanged]);
+    assert_eq!(changed.warning.as_deref(), Some("backup.key has changed since the vault was sealed"));
+
+    let missing = store.check(&container, &[photo]);
+    assert_eq!(missing.files[1], KeyFileStatus { path: None, name: "backup.key".into(), state: KeyFileState::Missing });
+
+    let unsealed_elsewhere = store.check(&dir.0.join("other.tvlt").display().to_string(), &[key]);
+    assert!(!unsealed_elsewhere.recorded && unsealed_elsewhere.warning.is_none());
+  }
+
+  #[test]
+  fn an_unloadable_record_file_is_moved_aside_and_a_stuck_one_never_saved_over() {
+    let dir = Dir::new("corrupt");
+    let file = dir.0.join(KEY_FILES_FILE);
+    let container = dir.0.join("box.tvlt").display().to_string();
+    let key = dir.file("backup.key", b"one");
+    fs::write(&file, r#"{"version":2,"vaults":{}}"#).unwrap();
+
+    let store = KeyFileStore::open(Some(file.clone()));
+    assert_eq!(fs::read_to_string(dir.0.join(format!("{KEY_FILES_FILE}.corrupt"))).unwrap(), r#"{"version":2,"vaults":{}}"#);
+    store.record(&container, std::slice::from_ref(&key)).unwrap();
+    assert!(KeyFileStore::open(Some(file.clone())).check(&container, std::slice::from_ref(&key)).recorded);
+
+    let stuck = KeyFileStore { file: Some(file.clone()), vaults: Mutex::default(), unusable: Some("cannot be moved aside".into()) };
+    assert_eq!(stuck.record(&container, &[]).unwrap_err(), "key file records cannot be saved: cannot be moved aside");
+    assert!(KeyFileStore::open(Some(file)).check(&container, &[key]).recorded);
+  }
+}
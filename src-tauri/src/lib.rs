@@ -1,13 +1,44 @@
-use once_cell::sync::Lazy;
-use std::sync::atomic::{AtomicU32, Ordering};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Manager};
 use tauri_plugin_single_instance;
 use tauri_plugin_fs;
 use tauri_plugin_store;
 use tauri_plugin_updater;
-mod cli_runner;
+pub mod audit;
+pub mod cli_runner;
+mod container;
+mod data_file;
+pub mod diagnostics;
+pub mod entropy;
+mod entropy_health;
+pub mod info_cache;
+pub mod key_file;
+mod logging;
+pub mod mnemonic;
+pub mod passphrase;
+pub mod progress;
+mod scheduler;
+mod settings;
+pub mod share_import;
+pub mod share_paper;
+pub mod sidecar;
+pub mod signing;
+pub mod trust_store;
+pub mod watchdog;
 
-use cli_runner::{run_encrypt, run_decrypt, run_container_info, run_reseal, container_info_once}; 
+use audit::{verify_audit_log, export_audit_log};
+use cli_runner::{run_encrypt, run_decrypt, run_container_info, run_reseal, container_info_once, container_info_many, cancel_operation, queue_status}; 
+use diagnostics::{preview_diagnostics, export_diagnostics};
+use entropy::{start_entropy_session, entropy_batch, end_entropy_session, EntropyPool};
+use info_cache::{invalidate_container_info, InfoCache};
+use key_file::{generate_key_file, check_key_files};
+use logging::{set_verbose_logging, export_log};
+use mnemonic::{master_token_to_mnemonic, mnemonic_to_master_token};
+use passphrase::{generate_passphrase, estimate_strength};
+use share_import::{scan_share_images, check_shares};
+use share_paper::export_share_papers;
+use sidecar::sidecar_diagnostics;
+use signing::{generate_signing_key, list_signing_keys, delete_signing_key};
+use trust_store::{list_trusted_keys, trust_key, untrust_key, import_trusted_key, export_public_key};
 
 /* ---------- scan directory for containers ---------- */
 #[tauri::command]
@@ -41,26 +72,6 @@ fn scan_containers_directory(path: String) -> Result<Vec<String>, String> {
     Ok(containers)
 }
 
-/* ---------- global counter ---------- */
-static ENTROPY_BITS: Lazy<AtomicU32> = Lazy::new(|| AtomicU32::new(0));
-const TARGET_BITS: u32 = 512;
-const ENTROPY_PER_BYTE_NUM: u32 = 1;
-const ENTROPY_PER_BYTE_DEN: u32 = 2;
-
-/* ---------- IPC: accept entropy ---------- */
-#[tauri::command]
-fn entropy_batch(app: AppHandle, bytes: Vec<u8>) -> u32 {
-    /* add 0.5 bit for each byte */
-    let added = (bytes.len() as u32 * ENTROPY_PER_BYTE_NUM) / ENTROPY_PER_BYTE_DEN;
-    let total = ENTROPY_BITS.fetch_add(added, Ordering::SeqCst) + added;
-
-    if total >= TARGET_BITS {
-        ENTROPY_BITS.store(0, Ordering::SeqCst);
-        app.emit("entropy_ready", ()).ok();
-    }
-    total.min(TARGET_BITS)
-}
-
 #[tauri::command]
 fn check_container_path(path: String) -> Result<(), String> {
     use std::path::Path;
@@ -100,6 +111,35 @@ fn remove_dir(path: String, recursive: bool) -> Result<(), String> {
     Ok(())
 }
 
+#[tauri::command]
+fn delete_container(app: AppHandle, path: String) -> Result<(), String> {
+    let started = std::time::Instant::now();
+    let cache = app.try_state::<InfoCache>();
+    let container_id = audit::container_id(None, cache.as_ref().and_then(|c| c.get(&path)));
+
+    // only ever a container file, never whatever path the webview names
+    let removed = if container::is_container_file(std::path::Path::new(&path)) {
+        std::fs::remove_file(&path).map_err(|e| e.to_string())
+    } else {
+        Err(format!("refusing to delete {path}: not a .{} container file", container::EXTENSION))
+    };
+    if removed.is_ok() {
+        if let Some(cache) = cache {
+            cache.invalidate(&path);
+        }
+    }
+
+    audit::record(&app, audit::Event {
+        op:           "delete",
+        container:    path,
+        container_id,
+        outcome:      if removed.is_ok() { "ok" } else { "failed" }.into(),
+        integrity:    None,
+        duration:     started.elapsed(),
+    });
+    removed
+}
+
 
 /* ---------- run Tauri ---------- */
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -112,23 +152,62 @@ pub fn run() {
             bring_to_front(app);
         }))
         .plugin(tauri_plugin_opener::init())
+        .manage(EntropyPool::default())
         .setup(|app| {
+            logging::init(app.handle());
+            watchdog::init(app.handle());
             #[cfg(desktop)]
             app.handle().plugin(tauri_plugin_updater::Builder::new().build());
             app.handle().plugin(tauri_plugin_process::init());
+            audit::init(app.handle());
+            info_cache::init(app.handle());
+            key_file::init(app.handle());
+            signing::init(app.handle());
+            trust_store::init(app.handle());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
+            start_entropy_session,
             entropy_batch,
+            end_entropy_session,
             check_container_path,
             check_file_exists,
             remove_dir,
+            delete_container,
             scan_containers_directory,
             run_encrypt,
             run_decrypt,
             run_container_info,
             container_info_once,
-            run_reseal
+            container_info_many,
+            invalidate_container_info,
+            run_reseal,
+            cancel_operation,
+            queue_status,
+            generate_signing_key,
+            list_signing_keys,
+            delete_signing_key,
+            list_trusted_keys,
+            trust_key,
+            untrust_key,
+            import_trusted_key,
+            export_public_key,
+            generate_key_file,
+            check_key_files,
+            generate_passphrase,
+            estimate_strength,
+            master_token_to_mnemonic,
+            mnemonic_to_master_token,
+            export_share_papers,
+            scan_share_images,
+            check_shares,
+            set_verbose_logging,
+            export_log,
+            preview_diagnostics,
+            export_diagnostics,
+            verify_audit_log,
+            export_audit_log,
+            sidecar_diagnostics
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,395 @@
+//! logging.rs — the backend's log file, with credentials redacted on the way in
+//!
+//! Code logs through the `log` macros, optionally with key-value fields:
+//! `log::info!(op = prefix; "started")`. Lines go to `tvault.log` in the app
+//! log dir, which rotates at `MAX_BYTES` keeping `KEEP` older files, and to
+//! stderr in debug builds.
+//!
+//! Every message and field value passes through `redact` before it is
+//! written: values of the core's credential flags are replaced, JSON is
+//! scrubbed of credential-named keys, and fields with such names read
+//! `[REDACTED]` whatever they hold.
+//!
+//! The crate logs at info, or debug with the `verboseLogging` setting; other
+//! crates only get through with warnings and errors.
+
+use std::{
+  fmt::Write as _,
+  fs::{self, File, OpenOptions},
+  io::Write,
+  path::{Path, PathBuf},
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+  },
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::{
+  kv::{Error as KvError, Key, Value as KvValue, VisitSource},
+  LevelFilter, Log, Metadata, Record,
+};
+use once_cell::sync::Lazy;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::settings;
+
+pub const LOG_FILE: &str = "tvault.log";
+/// Settings store key: log at debug level.
+pub const VERBOSE_SETTING: &str = "verboseLogging";
+const MAX_BYTES: u64 = 2 * 1024 * 1024;
+/// Rotated files kept next to the current one: `tvault.log.1` is the newest.
+const KEEP: usize = 3;
+const CRATE_TARGET: &str = "tvault_client_lib";
+const REDACTED: &str = "[REDACTED]";
+
+/// Core flags whose value is a credential.
+pub(crate) const SECRET_FLAGS: [&str; 4] = ["-passphrase=", "-current-passphrase=", "-new-passphrase=", "-flag="];
+/// Field and JSON key fragments that mark a credential.
+const SECRET_KEYS: [&str; 6] = ["pass", "token", "share", "secret", "private", "mnemonic"];
+
+static LOGGER: Lazy<Logger> = Lazy::new(|| Logger { sink: Mutex::new(None), verbose: AtomicBool::new(false) });
+
+struct Logger {
+  sink:    Mutex<Option<FileLog>>,
+  verbose: AtomicBool,
+}
+
+/* ─────────── Log file ─────────── */
+
+struct FileLog {
+  path:      PathBuf,
+  max_bytes: u64,
+  file:      Option<File>,
+  size:      u64,
+}
+
+impl FileLog {
+  fn open(path: PathBuf, max_bytes: u64) -> Self {
+    if let Some(dir) = path.parent() {
+      let _ = fs::create_dir_all(dir);
+    }
+    let file = OpenOptions::new().create(true).append(true).open(&path).ok();
+    let size = file.as_ref().and_then(|f| f.metadata().ok()).map(|m| m.len()).unwrap_or_default();
+    FileLog { path, max_bytes, file, size }
+  }
+
+  fn write_line(&mut self, line: &str) {
+    let len = line.len() as u64 + 1;
+    if self.size > 0 && self.size + len > self.max_bytes {
+      self.rotate();
+    }
+    if let Some(file) = &mut self.file {
+      if writeln!(file, "{line}").is_ok() {
+        self.size += len;
+      }
+    }
+  }
+
+  /// `tvault.log` → `.1` → … → `.KEEP`, dropping the oldest.
+  fn rotate(&mut self) {
+    self.file = None;
+    let _ = fs::remove_file(rotated(&self.path, KEEP));
+    for n in (1..KEEP).rev() {
+      let _ = fs::rename(rotated(&self.path, n), rotated(&self.path, n + 1));
+    }
+    let _ = fs::rename(&self.path, rotated(&self.path, 1));
+    *self = FileLog::open(self.path.clone(), self.max_bytes);
+  }
+
+  fn flush(&mut self) {
+    if let Some(file) = &mut self.file {
+      let _ = file.flush();
+    }
+  }
+}
+
+fn rotated(path: &Path, n: usize) -> PathBuf {
+  let mut name = path.as_os_str().to_owned();
+  name.push(format!(".{n}"));
+  PathBuf::from(name)
+}
+
+/// Log files from oldest to newest.
+pub(crate) fn log_files() -> Vec<PathBuf> {
+  let Some(path) = LOGGER.sink.lock().unwrap().as_mut().map(|s| {
+    s.flush();
+    s.path.clone()
+  }) else {
+    return Vec::new();
+  };
+  let mut files: Vec<PathBuf> = (1..=KEEP).rev().map(|n| rotated(&path, n)).collect();
+  files.push(path);
+  files.retain(|p| p.is_file());
+  files
+}
+
+/* ─────────── Logger ─────────── */
+
+impl Logger {
+  fn level(&self, target: &str) -> LevelFilter {
+    match (target.starts_with(CRATE_TARGET), self.verbose.load(Ordering::Relaxed)) {
+      (true, true) => LevelFilter::Debug,
+      (true, false) => LevelFilter::Info,
+      (false, _) => LevelFilter::Warn,
+    }
+  }
+}
+
+impl Log for Logger {
+  fn enabled(&self, metadata: &Metadata) -> bool {
+    metadata.level() <= self.level(metadata.target())
+  }
+
+  fn log(&self, record: &Record) {
+    if !self.enabled(record.metadata()) {
+      return;
+    }
+    let line = format_record(record, SystemTime::now());
+    #[cfg(debug_assertions)]
+    eprintln!("{line}");
+    if let Some(sink) = self.sink.lock().unwrap().as_mut() {
+      sink.write_line(&line);
+    }
+  }
+
+  fn flush(&self) {
+    if let Some(sink) = self.sink.lock().unwrap().as_mut() {
+      sink.flush();
+    }
+  }
+}
+
+/// `2024-05-01T12:00:00.000Z INFO  cli_runner: started op=reseal`
+fn format_record(record: &Record, now: SystemTime) -> String {
+  let target = record.target().strip_prefix(CRATE_TARGET).map(|t| t.trim_start_matches("::")).unwrap_or(record.target());
+  let target = if target.is_empty() { CRATE_TARGET } else { target };
+  let mut line = format!("{} {:<5} {target}: {}", timestamp(now), record.level(), redact(&record.args().to_string()));
+  let _ = record.key_values().visit(&mut Fields(&mut line));
+  // one record, one line
+  line.replace(['\n', '\r'], " ")
+}
+
+struct Fields<'a>(&'a mut String);
+
+impl<'kvs> VisitSource<'kvs> for Fields<'_> {
+  fn visit_pair(&mut self, key: Key<'kvs>, value: KvValue<'kvs>) -> Result<(), KvError> {
+    let value = if is_secret_key(key.as_str()) { REDACTED.to_string() } else { redact(&value.to_string()) };
+    let _ = write!(self.0, " {key}={value}");
+    Ok(())
+  }
+}
+
+fn is_secret_key(key: &str) -> bool {
+  let key = key.to_ascii_lowercase();
+  SECRET_KEYS.iter().any(|s| key.contains(s))
+}
+
+/* ─────────── Redaction ─────────── */
+
+/// `text` with credentials replaced: JSON has credential-named keys scrubbed,
+/// anything else has the values of the core's credential flags removed.
+pub fn redact(text: &str) -> String {
+  let trimmed = text.trim_start();
+  if trimmed.starts_with('{') || trimmed.starts_with('[') {
+    if let Ok(mut json) = serde_json::from_str::<Value>(text) {
+      scrub(&mut json);
+      return json.to_string();
+    }
+  }
+  redact_flags(text)
+}
+
+pub(crate) fn scrub(value: &mut Value) {
+  match value {
+    Value::Object(fields) => {
+      for (k, v) in fields.iter_mut() {
+        if is_secret_key(k) {
+          *v = Value::String(REDACTED.into());
+        } else {
+          scrub(v);
+        }
+      }
+    }
+    Value::Array(items) => items.iter_mut().for_each(scrub),
+    // a string of its own is a whole argument, spaces and all
+    Value::String(s) => match SECRET_FLAGS.iter().find(|f| s.starts_with(*f)) {
+      Some(flag) => *s = format!("{flag}{REDACTED}"),
+      None => *s = redact_flags(s),
+    },
+    _ => {}
+  }
+}
+
+/// Replaces the value of every credential flag up to the end of its argument:
+/// the closing quote when the flag opens a quoted string, otherwise the end of
+/// the text, since an unquoted value may hold spaces.
+fn redact_flags(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  let mut rest = text;
+  'scan: while !rest.is_empty() {
+    for flag in SECRET_FLAGS {
+      let opener = out.chars().next_back();
+      let at_boundary = opener.is_none_or(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '[' | ',' | '('));
+      if at_boundary && rest.starts_with(flag) {
+        out.push_str(flag);
+        out.push_str(REDACTED);
+        let value = &rest[flag.len()..];
+        let end = match opener {
+          Some(quote @ ('"' | '\'')) => closing_quote(value, quote).unwrap_or(value.len()),
+          _ => value.len(),
+        };
+        rest = &value[end..];
+        continue 'scan;
+      }
+    }
+    let c = rest.chars().next().unwrap_or_default();
+    out.push(c);
+    rest = &rest[c.len_utf8()..];
+  }
+  out
+}
+
+/// Byte offset of the first `quote` in `text` that is not escaped.
+fn closing_quote(text: &str, quote: char) -> Option<usize> {
+  let mut escaped = false;
+  for (i, c) in text.char_indices() {
+    match c {
+      _ if escaped => escaped = false,
+      '\\' => escaped = true,
+      c if c == quote => return Some(i),
+      _ => {}
+    }
+  }
+  None
+}
+
+/// UTC, millisecond precision.
+pub(crate) fn timestamp(now: SystemTime) -> String {
+  let since = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+  let secs = since.as_secs();
+  // days to civil date, after Howard Hinnant
+  let z = (secs / 86_400) as i64 + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z - era * 146_097;
+  let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + i64::from(month <= 2);
+  let rem = secs % 86_400;
+  format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z", rem / 3_600, rem / 60 % 60, rem % 60, since.subsec_millis())
+}
+
+fn set_verbose(verbose: bool) {
+  LOGGER.verbose.store(verbose, Ordering::Relaxed);
+  log::set_max_level(if verbose { LevelFilter::Debug } else { LevelFilter::Info });
+}
+
+/* ─────────── Commands ─────────── */
+
+/// Applies the `verboseLogging` setting right away; the UI stores it.
+#[tauri::command]
+pub fn set_verbose_logging(enabled: bool) {
+  set_verbose(enabled);
+  log::info!("verbose logging {}", if enabled { "on" } else { "off" });
+}
+
+/// Writes the current and rotated logs, oldest first, to `path`.
+#[tauri::command]
+pub fn export_log(path: String) -> Result<(), String> {
+  let mut out = File::create(&path).map_err(|e| format!("cannot create {path}: {e}"))?;
+  for file in log_files() {
+    let raw = fs::read(&file).map_err(|e| format!("cannot read {}: {e}", file.display()))?;
+    out.write_all(&raw).map_err(|e| format!("cannot write {path}: {e}"))?;
+  }
+  Ok(())
+}
+
+/// Opens the log file in the app log dir and installs the logger.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+  if let Ok(dir) = app.path().app_log_dir() {
+    *LOGGER.sink.lock().unwrap() = Some(FileLog::open(dir.join(LOG_FILE), MAX_BYTES));
+  }
+  if log::set_logger(&*LOGGER).is_ok() {
+    set_verbose(settings::get(app, VERBOSE_SETTING).and_then(|v| v.as_bool()).unwrap_or(false));
+  }
+  log::info!("tvault {} starting on {}", env!("CARGO_PKG_VERSION"), std::env::consts::OS);
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use log::Level;
+  use std::time::Duration;
+
+  #[test]
+  fn credential_flags_are_redacted_in_text_and_json() {
+    let argv = r#"["unseal", "-passphrase=P@ss w", "-flag=s1|s2", "-folder-path=/mnt/box"]"#;
+    assert_eq!(redact(argv), r#"["unseal","-passphrase=[REDACTED]","-flag=[REDACTED]","-folder-path=/mnt/box"]"#);
+    // a quoted value ends at its closing quote, escaped quotes and spaces included
+    assert_eq!(
+      redact(r#"starting ["-passphrase=a \" b", "-name=box"]"#),
+      r#"starting ["-passphrase=[REDACTED]", "-name=box"]"#,
+    );
+    // an unquoted one may hold spaces, so the rest of the text goes
+    assert_eq!(redact("reseal -name=box -new-passphrase=correct horse -x"), "reseal -name=box -new-passphrase=[REDACTED]");
+    // not a flag of its own
+    assert_eq!(redact("x-flag=1"), "x-flag=1");
+  }
+
+  #[test]
+  fn credential_keys_are_redacted_in_core_output() {
+    let result = r#"{"shares":["a","b"],"master_token":"MT","container":{"path":"/v/box.tvlt","passphrase":"pw"}}"#;
+    let scrubbed: Value = serde_json::from_str(&redact(result)).unwrap();
+    assert_eq!(scrubbed["shares"], "[REDACTED]");
+    assert_eq!(scrubbed["master_token"], "[REDACTED]");
+    assert_eq!(scrubbed["container"]["passphrase"], "[REDACTED]");
+    assert_eq!(scrubbed["container"]["path"], "/v/box.tvlt");
+  }
+
+  #[test]
+  fn records_carry_level_target_and_redacted_fields() {
+    let now = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+    let line = format_record(
+      &Record::builder()
+        .args(format_args!("started\nwith -passphrase=pw"))
+        .level(Level::Info)
+        .target("tvault_client_lib::cli_runner")
+        .key_values(&[("op", "reseal"), ("shares", "s1|s2"), ("argv", "-flag=MT")])
+        .build(),
+      now,
+    );
+    assert_eq!(line, "2023-11-14T22:13:20.123Z INFO  cli_runner: started with -passphrase=[REDACTED] op=reseal shares=[REDACTED] argv=-flag=[REDACTED]");
+  }
+
+  #[test]
+  fn verbosity_applies_to_this_crate_only() {
+    let logger = Logger { sink: Mutex::new(None), verbose: AtomicBool::new(false) };
+    assert_eq!(logger.level("tvault_client_lib::verify"), LevelFilter::Info);
+    logger.verbose.store(true, Ordering::Relaxed);
+    assert_eq!(logger.level("tvault_client_lib::verify"), LevelFilter::Debug);
+    assert_eq!(logger.level("tao::platform"), LevelFilter::Warn);
+  }
+
+  #[test]
+  fn rotates_and_keeps_a_bounded_history() {
+    let dir = std::env::temp_dir().join(format!("tvault-log-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let mut log = FileLog::open(dir.join(LOG_FILE), 100);
+    for i in 0..20 {
+      log.write_line(&format!("line {i:02} {}", "x".repeat(30)));
+    }
+    log.flush();
+    let current = fs::read_to_string(dir.join(LOG_FILE)).unwrap();
+    assert!(current.ends_with(&format!("line 19 {}\n", "x".repeat(30))));
+    assert!(current.len() <= 100);
+    assert!(rotated(&dir.join(LOG_FILE), KEEP).is_file());
+    assert!(!rotated(&dir.join(LOG_FILE), KEEP + 1).exists());
+    let _ = fs::remove_dir_all(&dir);
+  }
+}
@@ -0,0 +1,192 @@
+//! mnemonic.rs — master tokens as BIP39 English words
+//!
+//! The core's master token is an opaque string, so this is BIP39-style rather
+//! than BIP39 proper: the words carry a one-byte encoding tag, the payload
+//! length and the payload, followed by 8–18 bits of SHA-256 checksum to fill
+//! the last word. Hex and base64 tokens are packed as the bytes they encode;
+//! anything else is carried as UTF-8. A 256-bit key in hex becomes 26 words.
+//!
+//! Decoding rejects unknown words and checksum mismatches, so a mistyped word
+//! is reported here instead of as a failed unseal. The UI decodes words with
+//! `mnemonic_to_master_token` before it hands a token to any operation; the
+//! operations themselves only take tokens.
+
+use base64::{
+  engine::general_purpose::{STANDARD as B64, URL_SAFE_NO_PAD as B64_URL},
+  Engine,
+};
+use bip39::Language;
+use sha2::{Digest, Sha256};
+
+const BITS_PER_WORD: usize = 11;
+const MIN_CHECKSUM_BITS: usize = 8;
+/// Tag and length bytes in front of the payload.
+const HEADER_BYTES: usize = 2;
+
+/// How the token text is rebuilt from the payload bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+  Text,
+  HexLower,
+  HexUpper,
+  Base64,
+  Base64Url,
+}
+
+const ENCODINGS: [Encoding; 5] = [Encoding::Text, Encoding::HexLower, Encoding::HexUpper, Encoding::Base64, Encoding::Base64Url];
+
+impl Encoding {
+  fn tag(self) -> u8 {
+    ENCODINGS.iter().position(|e| *e == self).unwrap_or_default() as u8
+  }
+
+  fn decode(self, text: &str) -> Option<Vec<u8>> {
+    match self {
+      Encoding::Text => Some(text.as_bytes().to_vec()),
+      Encoding::HexLower | Encoding::HexUpper => {
+        if text.len() % 2 != 0 {
+          return None;
+        }
+        (0..text.len()).step_by(2).map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok()).collect()
+      }
+      Encoding::Base64 => B64.decode(text).ok(),
+      Encoding::Base64Url => B64_URL.decode(text).ok(),
+    }
+  }
+
+  fn encode(self, bytes: &[u8]) -> Option<String> {
+    match self {
+      Encoding::Text => String::from_utf8(bytes.to_vec()).ok(),
+      Encoding::HexLower => Some(bytes.iter().map(|b| format!("{b:02x}")).collect()),
+      Encoding::HexUpper => Some(bytes.iter().map(|b| format!("{b:02X}")).collect()),
+      Encoding::Base64 => Some(B64.encode(bytes)),
+      Encoding::Base64Url => Some(B64_URL.encode(bytes)),
+    }
+  }
+}
+
+/* ─────────── Commands ─────────── */
+
+#[tauri::command]
+pub fn master_token_to_mnemonic(token: String) -> Result<String, String> {
+  to_mnemonic(token.trim())
+}
+
+#[tauri::command]
+pub fn mnemonic_to_master_token(words: String) -> Result<String, String> {
+  from_mnemonic(&words)
+}
+
+/* ─────────── Encoding ─────────── */
+
+pub fn to_mnemonic(token: &str) -> Result<String, String> {
+  if token.is_empty() {
+    return Err("the master token is empty".into());
+  }
+  // the most compact encoding that gives the token back unchanged
+  let (encoding, payload) = ENCODINGS
+    .iter()
+    .filter_map(|e| e.decode(token).filter(|p| e.encode(p).as_deref() == Some(token)).map(|p| (*e, p)))
+    .min_by_key(|(_, p)| p.len())
+    .ok_or("the master token cannot be encoded")?;
+  let len = u8::try_from(payload.len()).map_err(|_| "the master token is too long for a mnemonic")?;
+
+  let mut bits = Vec::new();
+  push_bits(&mut bits, &[encoding.tag(), len]);
+  push_bits(&mut bits, &payload);
+  let checksum_bits = checksum_bits(bits.len());
+  let mut sum = Vec::new();
+  push_bits(&mut sum, &Sha256::digest(&payload));
+  bits.extend_from_slice(&sum[..checksum_bits]);
+
+  let words = Language::English.word_list();
+  Ok(bits.chunks(BITS_PER_WORD).map(|c| words[read_bits(c)]).collect::<Vec<_>>().join(" "))
+}
+
+pub fn from_mnemonic(text: &str) -> Result<String, String> {
+  let mut bits = Vec::new();
+  for (i, word) in text.split_whitespace().enumerate() {
+    let index = Language::English.find_word(&word.to_lowercase()).ok_or_else(|| format!("word {} \"{word}\" is not in the word list", i + 1))?;
+    for b in (0..BITS_PER_WORD).rev() {
+      bits.push(index >> b & 1 == 1);
+    }
+  }
+  let wrong_length = || "the mnemonic has the wrong number of words".to_string();
+  if bits.len() < (HEADER_BYTES + 1) * 8 + MIN_CHECKSUM_BITS {
+    return Err(wrong_length());
+  }
+  let tag = read_bits(&bits[..8]);
+  let len = read_bits(&bits[8..16]);
+  let data_bits = (HEADER_BYTES + len) * 8;
+  if len == 0 || checksum_bits(data_bits) != bits.len().saturating_sub(data_bits) {
+    return Err(wrong_length());
+  }
+
+  let payload: Vec<u8> = bits[HEADER_BYTES * 8..data_bits].chunks(8).map(|c| read_bits(c) as u8).collect();
+  let mut sum = Vec::new();
+  push_bits(&mut sum, &Sha256::digest(&payload));
+  let encoding = ENCODINGS.get(tag);
+  if bits[data_bits..] != sum[..bits.len() - data_bits] || encoding.is_none() {
+    return Err("the words do not match their checksum; check for a mistyped or swapped word".into());
+  }
+  encoding.and_then(|e| e.encode(&payload)).ok_or_else(|| "the mnemonic does not hold a master token".into())
+}
+
+/// Checksum bits after `data_bits`: at least 8, and enough to fill the last word.
+fn checksum_bits(data_bits: usize) -> usize {
+  let total = (data_bits + MIN_CHECKSUM_BITS).div_ceil(BITS_PER_WORD) * BITS_PER_WORD;
+  total - data_bits
+}
+
+fn push_bits(bits: &mut Vec<bool>, bytes: &[u8]) {
+  for byte in bytes {
+    for b in (0..8).rev() {
+      bits.push(byte >> b & 1 == 1);
+    }
+  }
+}
+
+fn read_bits(bits: &[bool]) -> usize {
+  bits.iter().fold(0, |acc, b| acc << 1 | usize::from(*b))
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tokens_round_trip_in_every_encoding() {
+    let hex = "9f".repeat(32);
+    for token in [hex.as_str(), &hex.to_uppercase(), &B64.encode([7u8; 32]), &B64_URL.encode([250u8; 32]), "mt-1:opaque token"] {
+      let words = to_mnemonic(token).unwrap();
+      assert_eq!(from_mnemonic(&words).unwrap(), token, "{words}");
+    }
+    assert_eq!(to_mnemonic(&hex).unwrap().split(' ').count(), 26);
+  }
+
+  #[test]
+  fn typos_are_caught_before_the_core_sees_them() {
+    let token: String = (0u8..32).map(|b| format!("{b:02x}")).collect();
+    let words: Vec<String> = to_mnemonic(&token).unwrap().split(' ').map(String::from).collect();
+
+    let mut unknown = words.clone();
+    unknown[4] = "abandn".into();
+    assert_eq!(from_mnemonic(&unknown.join(" ")).unwrap_err(), "word 5 \"abandn\" is not in the word list");
+
+    let mut swapped = words.clone();
+    swapped.swap(10, 11);
+    assert!(from_mnemonic(&swapped.join(" ")).unwrap_err().contains("checksum"));
+
+    assert!(from_mnemonic(&words[..25].join(" ")).unwrap_err().contains("number of words"));
+  }
+
+  #[test]
+  fn words_are_read_whatever_their_case_and_spacing() {
+    let token = B64.encode([1u8; 32]);
+    let words = to_mnemonic(&token).unwrap();
+    assert_eq!(from_mnemonic(&format!("  {}\n", words.to_uppercase().replace(' ', "\t"))).unwrap(), token);
+    assert!(from_mnemonic("zebra zebra zebra").is_err());
+  }
+}
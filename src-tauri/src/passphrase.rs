@@ -0,0 +1,304 @@
+//! passphrase.rs — word passphrase generator and password strength estimates
+//!
+//! Generated passphrases draw words uniformly from the BIP39 English list that
+//! already ships for mnemonics (2048 words, 11 bits each) using the OS CSPRNG.
+//! This is not a diceware list: six words give 66 bits, about what 5.1 diceware
+//! words (12.9 bits each) would.
+//!
+//! The estimator follows zxcvbn's approach in a reduced form: the cheapest of a
+//! few attacker models gives the guess count, which maps to a 0–4 score and to
+//! crack times for four attack scenarios. Models: common passwords (with l33t
+//! substitutions and a short digit/symbol suffix), words from the list, the
+//! user's own inputs (vault name, tags) and brute force where repeats,
+//! sequences and keyboard runs are cheap.
+
+use bip39::Language;
+use rand_core::{OsRng, RngCore};
+use secrecy::{ExposeSecret, SecretString};
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+use zeroize::Zeroizing;
+
+use crate::settings;
+
+/// Settings store key: minimum score (0–4) for passwords chosen at seal or reseal time.
+pub const MIN_SCORE_SETTING: &str = "minPasswordScore";
+pub const DEFAULT_WORDS: u8 = 6;
+const MIN_WORDS: u8 = 4;
+const MAX_WORDS: u8 = 16;
+/// Guess counts are capped here: a long enough password overflows `f64` to
+/// infinity, which JSON has no number for.
+const MAX_GUESSES: f64 = 1e300;
+
+/// Most common leaked passwords, most common first.
+const COMMON: &[&str] = &[
+  "123456", "password", "123456789", "12345678", "12345", "qwerty", "1234567", "111111", "1234567890", "123123",
+  "abc123", "1234", "password1", "iloveyou", "1q2w3e4r", "000000", "qwerty123", "zaq12wsx", "dragon", "sunshine",
+  "princess", "letmein", "654321", "monkey", "27653", "1qaz2wsx", "123321", "qwertyuiop", "superman", "asdfghjkl",
+  "football", "baseball", "welcome", "admin", "master", "shadow", "michael", "login", "passw0rd", "starwars",
+  "trustno1", "hello", "freedom", "whatever", "qazwsx", "secret", "access", "mustang", "flower", "hunter2",
+];
+const KEYBOARD_ROWS: &[&str] = &["`1234567890-=", "qwertyuiop[]\\", "asdfghjkl;'", "zxcvbnm,./"];
+
+/* ─────────── Generator ─────────── */
+
+#[tauri::command]
+pub fn generate_passphrase(words: Option<u8>, separator: Option<String>) -> Result<String, String> {
+  let count = words.unwrap_or(DEFAULT_WORDS);
+  if !(MIN_WORDS..=MAX_WORDS).contains(&count) {
+    return Err(format!("a passphrase needs {MIN_WORDS} to {MAX_WORDS} words"));
+  }
+  let list = Language::English.word_list();
+  // 2048 is a power of two, so masking keeps the draw uniform
+  let picked: Vec<&str> = (0..count).map(|_| list[(OsRng.next_u32() as usize) & (list.len() - 1)]).collect();
+  Ok(picked.join(separator.as_deref().unwrap_or("-")))
+}
+
+/* ─────────── Estimator ─────────── */
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct CrackTime {
+  pub seconds: f64,
+  /// "3 hours", "centuries".
+  pub display: String,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct CrackTimes {
+  /// Online, rate limited: 100 guesses per hour.
+  pub online_throttled:   CrackTime,
+  /// Online, not rate limited: 10 per second.
+  pub online_unthrottled: CrackTime,
+  /// Offline against a slow hash: 10k per second.
+  pub offline_slow:       CrackTime,
+  /// Offline against a fast hash: 10 billion per second.
+  pub offline_fast:       CrackTime,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Strength {
+  /// 0 (too guessable) to 4 (very unguessable), as in zxcvbn.
+  pub score:         u8,
+  pub guesses:       f64,
+  pub guesses_log10: f64,
+  pub crack_times:   CrackTimes,
+  pub warning:       Option<String>,
+  pub suggestions:   Vec<String>,
+}
+
+#[tauri::command]
+pub fn estimate_strength(password: SecretString, user_inputs: Option<Vec<String>>) -> Strength {
+  estimate(password.expose_secret(), &user_inputs.unwrap_or_default())
+}
+
+pub fn estimate(password: &str, user_inputs: &[String]) -> Strength {
+  let (guesses, warning) = guesses(password, user_inputs);
+  let guesses = guesses.clamp(1.0, MAX_GUESSES);
+  let score = match guesses {
+    g if g < 1e3 => 0,
+    g if g < 1e6 => 1,
+    g if g < 1e8 => 2,
+    g if g < 1e10 => 3,
+    _ => 4,
+  };
+  let time = |per_second: f64| {
+    let seconds = guesses / per_second;
+    CrackTime { seconds, display: display_time(seconds) }
+  };
+  let mut suggestions = Vec::new();
+  if score < 3 {
+    suggestions.push("Add another word or two; uncommon words are better.".to_string());
+    if password.chars().count() < 12 {
+      suggestions.push("Use a longer passphrase, for example a generated one.".to_string());
+    }
+  }
+  Strength {
+    score,
+    guesses,
+    guesses_log10: guesses.log10(),
+    crack_times: CrackTimes {
+      online_throttled:   time(100.0 / 3600.0),
+      online_unthrottled: time(10.0),
+      offline_slow:       time(1e4),
+      offline_fast:       time(1e10),
+    },
+    warning: warning.filter(|_| score < 3).map(str::to_string),
+    suggestions,
+  }
+}
+
+/// Cheapest attack on the password, and what made it cheap.
+fn guesses(password: &str, user_inputs: &[String]) -> (f64, Option<&'static str>) {
+  let lower = Zeroizing::new(password.to_lowercase());
+  let mut best = (brute_force(password), None);
+  let mut consider = |g: f64, why: &'static str| {
+    if g < best.0 {
+      best = (g, Some(why));
+    }
+  };
+
+  // common password, possibly l33t and with a digit/symbol suffix
+  let stem = lower.trim_end_matches(|c: char| c.is_ascii_digit() || c.is_ascii_punctuation());
+  let suffix = brute_force(&lower[stem.len()..]);
+  for (candidate, extra) in [(lower.as_str(), 1.0), (stem, suffix)] {
+    for (text, leet) in [(candidate.to_string(), 1.0), (unleet(candidate), 10.0)] {
+      if let Some(rank) = COMMON.iter().position(|p| *p == text) {
+        consider((rank + 1) as f64 * extra * leet, "This is a very common password.");
+      }
+    }
+  }
+
+  // the vault's own name, tags and the like
+  for input in user_inputs.iter().map(|s| s.trim().to_lowercase()).filter(|s| s.len() >= 3) {
+    if lower.contains(&input) {
+      let rest = lower.replacen(&input, "", 1);
+      consider(100.0 * brute_force(&rest).max(1.0), "Avoid the vault's name or tags in the password.");
+    }
+  }
+
+  // words from the list, however they are separated
+  let words: Vec<&str> = lower.split(|c: char| !c.is_ascii_alphabetic()).filter(|w| !w.is_empty()).collect();
+  if !words.is_empty() && words.iter().all(|w| Language::English.find_word(w).is_some()) {
+    let separators = if words.len() > 1 { 10.0 } else { 1.0 };
+    consider(2048f64.powi(words.len() as i32) * separators, "A single word is easy to guess.");
+  }
+  best
+}
+
+/// Guesses for a character-by-character search, where a character that
+/// repeats or continues a sequence or keyboard run costs little. Empty is 1.
+fn brute_force(password: &str) -> f64 {
+  let chars: Vec<char> = password.chars().collect();
+  let pool = [
+    chars.iter().any(char::is_ascii_lowercase).then_some(26.0),
+    chars.iter().any(char::is_ascii_uppercase).then_some(26.0),
+    chars.iter().any(char::is_ascii_digit).then_some(10.0),
+    chars.iter().any(|c| c.is_ascii_punctuation() || *c == ' ').then_some(33.0),
+    chars.iter().any(|c| !c.is_ascii()).then_some(100.0),
+  ]
+  .into_iter()
+  .flatten()
+  .sum::<f64>();
+
+  let mut log = 0.0;
+  for (i, c) in chars.iter().enumerate() {
+    let cur = c.to_ascii_lowercase();
+    let options = match i.checked_sub(1).map(|p| chars[p].to_ascii_lowercase()) {
+      Some(prev) if prev == cur => 2.0,
+      Some(prev) if (cur as i64 - prev as i64).abs() == 1 => 4.0,
+      Some(prev) if KEYBOARD_ROWS.iter().any(|row| row.contains(&format!("{prev}{cur}")) || row.contains(&format!("{cur}{prev}"))) => 4.0,
+      _ => pool,
+    };
+    log += f64::log10(options);
+  }
+  10f64.powf(log)
+}
+
+fn unleet(s: &str) -> String {
+  s.chars()
+    .map(|c| match c {
+      '0' => 'o',
+      '1' | '!' => 'i',
+      '3' => 'e',
+      '4' | '@' => 'a',
+      '5' | '$' => 's',
+      '7' => 't',
+      _ => c,
+    })
+    .collect()
+}
+
+fn display_time(seconds: f64) -> String {
+  const UNITS: [(f64, &str); 6] = [(60.0, "second"), (60.0, "minute"), (24.0, "hour"), (31.0, "day"), (12.0, "month"), (100.0, "year")];
+  if seconds < 1.0 {
+    return "less than a second".into();
+  }
+  let mut value = seconds;
+  for (size, unit) in UNITS {
+    if value < size {
+      let n = value.round() as u64;
+      return format!("{n} {unit}{}", if n == 1 { "" } else { "s" });
+    }
+    value /= size;
+  }
+  "centuries".into()
+}
+
+/* ─────────── Policy ─────────── */
+
+/// Rejects a password below the `minPasswordScore` setting, when one is set.
+pub(crate) fn enforce_policy<R: Runtime>(app: &AppHandle<R>, what: &str, password: &str) -> Result<(), String> {
+  let Some(min) = settings::get(app, MIN_SCORE_SETTING).and_then(|v| v.as_u64()).filter(|m| *m > 0) else {
+    return Ok(());
+  };
+  let strength = estimate(password, &[]);
+  if u64::from(strength.score) >= min {
+    return Ok(());
+  }
+  let mut message = format!("{what} is too weak (score {} of the required {min})", strength.score);
+  if let Some(w) = &strength.warning {
+    message.push_str(&format!(": {w}"));
+  }
+  Err(message)
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn generates_the_requested_number_of_list_words() {
+    let phrase = generate_passphrase(Some(7), Some(" ".into())).unwrap();
+    let words: Vec<&str> = phrase.split(' ').collect();
+    assert_eq!(words.len(), 7);
+    assert!(words.iter().all(|w| Language::English.find_word(w).is_some()));
+    assert_eq!(generate_passphrase(None, None).unwrap().split('-').count(), 6);
+    assert!(generate_passphrase(Some(3), None).is_err());
+    assert!(generate_passphrase(Some(17), None).is_err());
+  }
+
+  #[test]
+  fn common_and_patterned_passwords_score_low() {
+    for weak in ["password", "P@ssw0rd", "qwerty123", "abcdefgh", "aaaaaaaaaa", "monkey2024"] {
+      let s = estimate(weak, &[]);
+      assert!(s.score <= 1, "{weak}: {s:?}");
+    }
+    let s = estimate("password", &[]);
+    assert_eq!(s.warning.as_deref(), Some("This is a very common password."));
+    assert!(!s.suggestions.is_empty());
+    assert_eq!(s.crack_times.offline_fast.display, "less than a second");
+  }
+
+  #[test]
+  fn generated_passphrases_and_random_keys_score_high() {
+    let phrase = generate_passphrase(None, None).unwrap();
+    assert_eq!(estimate(&phrase, &[]).score, 4);
+    assert_eq!(estimate("Tr0ub4dor&3-xK9#qL", &[]).score, 4);
+    // two list words are guessable however they are joined
+    assert!(estimate("correct-horse", &[]).score <= 2);
+  }
+
+  #[test]
+  fn the_vault_name_does_not_count() {
+    let inputs = vec!["Quarterly Reports".to_string()];
+    assert!(estimate("quarterly reports1", &inputs).score < estimate("quarterly reports1", &[]).score);
+  }
+
+  #[test]
+  fn very_long_passwords_stay_finite() {
+    let s = estimate(&"x7#Q".repeat(200), &[]);
+    assert_eq!((s.guesses, s.guesses_log10, s.score), (MAX_GUESSES, 300.0, 4));
+    assert_eq!(serde_json::to_value(&s).unwrap()["guesses"], serde_json::json!(MAX_GUESSES));
+    assert!(estimate(&["abandon"; 120].join(" "), &[]).guesses.is_finite());
+  }
+
+  #[test]
+  fn crack_times_read_naturally() {
+    assert_eq!(display_time(0.2), "less than a second");
+    assert_eq!(display_time(1.0), "1 second");
+    assert_eq!(display_time(3.0 * 3600.0), "3 hours");
+    assert_eq!(display_time(1e12), "centuries");
+  }
+}
@@ -0,0 +1,267 @@
+//! progress.rs — structured progress from tvault-core, with throughput, ETA and throttling
+//!
+//! The core reports progress either as the legacy `PROGRESS <percent>` line or
+//! as `PROGRESS {json}`:
+//!
+//!   {"phase":"compress","percent":42,"bytes_done":..,"bytes_total":..,
+//!    "files_done":..,"files_total":..,"current_file":"docs/a.pdf"}
+//!
+//! Every field is optional. Byte and file counters are per phase.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Minimum gap between two `{prefix}-progress-detail` events.
+const MIN_INTERVAL: Duration = Duration::from_millis(100);
+/// Weight of the newest throughput measurement in the moving average.
+const RATE_SMOOTHING: f64 = 0.3;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+  Scan,
+  Compress,
+  Encrypt,
+  Write,
+  Verify,
+  Extract,
+}
+
+impl Phase {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Phase::Scan => "scan",
+      Phase::Compress => "compress",
+      Phase::Encrypt => "encrypt",
+      Phase::Write => "write",
+      Phase::Verify => "verify",
+      Phase::Extract => "extract",
+    }
+  }
+}
+
+/// One progress report as sent by the core.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ProgressSample {
+  #[serde(default)]
+  pub percent:      Option<f64>,
+  /// Unknown phase names are ignored rather than failing the whole sample.
+  #[serde(default, deserialize_with = "lenient_phase")]
+  pub phase:        Option<Phase>,
+  #[serde(default)]
+  pub bytes_done:   Option<u64>,
+  #[serde(default)]
+  pub bytes_total:  Option<u64>,
+  #[serde(default)]
+  pub files_done:   Option<u64>,
+  #[serde(default)]
+  pub files_total:  Option<u64>,
+  #[serde(default)]
+  pub current_file: Option<String>,
+}
+
+/// Payload of `{prefix}-progress-detail`.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ProgressDetail {
+  pub percent:      u8,
+  pub phase:        Option<Phase>,
+  pub bytes_done:   Option<u64>,
+  pub bytes_total:  Option<u64>,
+  pub files_done:   Option<u64>,
+  pub files_total:  Option<u64>,
+  pub current_file: Option<String>,
+  /// Bytes per second, smoothed.
+  pub throughput:   Option<f64>,
+  pub eta_secs:     Option<u64>,
+}
+
+fn lenient_phase<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Option<Phase>, D::Error> {
+  Ok(Option::<Value>::deserialize(d)?.and_then(|v| serde_json::from_value(v).ok()))
+}
+
+impl ProgressSample {
+  /// Parses what follows `PROGRESS ` on a stdout line: a bare percentage or a JSON object.
+  pub fn parse(text: &str) -> Option<Self> {
+    let text = text.trim();
+    if let Ok(percent) = text.parse::<f64>() {
+      return Some(Self::from_percent(percent));
+    }
+    serde_json::from_str::<Value>(text).ok().and_then(|v| Self::from_value(&v))
+  }
+
+  pub fn from_value(v: &Value) -> Option<Self> {
+    if !v.is_object() {
+      return None;
+    }
+    serde_json::from_value(v.clone()).ok()
+  }
+
+  pub fn from_percent(percent: f64) -> Self {
+    Self { percent: Some(percent), ..Self::default() }
+  }
+
+  /// Explicit percentage, else derived from bytes, else from files.
+  fn percent(&self) -> Option<f64> {
+    let ratio = |done: Option<u64>, total: Option<u64>| match (done, total) {
+      (Some(d), Some(t)) if t > 0 => Some(d as f64 * 100.0 / t as f64),
+      _ => None,
+    };
+    self.percent
+      .or_else(|| ratio(self.bytes_done, self.bytes_total))
+      .or_else(|| ratio(self.files_done, self.files_total))
+  }
+}
+
+/// Turns raw samples into throttled `ProgressDetail`s for one operation.
+#[derive(Default)]
+pub struct ProgressTracker {
+  phase:      Option<Phase>,
+  percent:    u8,
+  rate:       Option<f64>,
+  last_bytes: Option<(Instant, u64)>,
+  last_emit:  Option<Instant>,
+  /// Latest detail that was throttled away, flushed at the end.
+  held:       Option<ProgressDetail>,
+}
+
+impl ProgressTracker {
+  /// Returns the detail to emit now, or `None` when throttled.
+  pub fn update(&mut self, sample: ProgressSample, now: Instant) -> Option<ProgressDetail> {
+    let phase_changed = sample.phase.is_some() && sample.phase != self.phase;
+    if phase_changed {
+      self.phase = sample.phase;
+      self.rate = None;
+      self.last_bytes = None;
+    }
+    if let Some(p) = sample.percent() {
+      self.percent = p.clamp(0.0, 100.0) as u8;
+    }
+    if let Some(done) = sample.bytes_done {
+      self.measure(done, now);
+    }
+
+    let eta_secs = match (self.rate, sample.bytes_done, sample.bytes_total) {
+      (Some(rate), Some(done), Some(total)) if rate > 0.0 => Some((total.saturating_sub(done) as f64 / rate).ceil() as u64),
+      _ => None,
+    };
+    let detail = ProgressDetail {
+      percent: self.percent,
+      phase: self.phase,
+      bytes_done: sample.bytes_done,
+      bytes_total: sample.bytes_total,
+      files_done: sample.files_done,
+      files_total: sample.files_total,
+      current_file: sample.current_file,
+      throughput: self.rate,
+      eta_secs,
+    };
+
+    let due = self.last_emit.is_none_or(|t| now.duration_since(t) >= MIN_INTERVAL);
+    if due || phase_changed || self.percent == 100 {
+      self.last_emit = Some(now);
+      self.held = None;
+      Some(detail)
+    } else {
+      self.held = Some(detail);
+      None
+    }
+  }
+
+  /// Phase of the last sample that named one.
+  pub fn phase(&self) -> Option<Phase> {
+    self.phase
+  }
+
+  /// Percentage after the last update, for the legacy `{prefix}-progress` event.
+  pub fn percent(&self) -> u8 {
+    self.percent
+  }
+
+  /// The last throttled detail, so the UI ends on the final state.
+  pub fn flush(&mut self) -> Option<ProgressDetail> {
+    self.held.take()
+  }
+
+  fn measure(&mut self, done: u64, now: Instant) {
+    if let Some((then, before)) = self.last_bytes {
+      let secs = now.duration_since(then).as_secs_f64();
+      if secs <= 0.0 || done < before {
+        return;
+      }
+      let inst = (done - before) as f64 / secs;
+      self.rate = Some(match self.rate {
+        Some(prev) => RATE_SMOOTHING * inst + (1.0 - RATE_SMOOTHING) * prev,
+        None => inst,
+      });
+    }
+    self.last_bytes = Some((now, done));
+  }
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn ms(start: Instant, n: u64) -> Instant {
+    start + Duration::from_millis(n)
+  }
+
+  #[test]
+  fn parses_legacy_and_structured_lines() {
+    assert_eq!(ProgressSample::parse(" 42 "), Some(ProgressSample::from_percent(42.0)));
+    let s = ProgressSample::parse(r#"{"phase":"encrypt","bytes_done":50,"bytes_total":200,"current_file":"a.txt"}"#).unwrap();
+    assert_eq!(s.phase, Some(Phase::Encrypt));
+    assert_eq!(s.percent(), Some(25.0));
+    assert_eq!(s.current_file.as_deref(), Some("a.txt"));
+    assert_eq!(ProgressSample::parse("soon"), None);
+  }
+
+  #[test]
+  fn unknown_phase_keeps_the_counters() {
+    let s = ProgressSample::from_value(&json!({ "phase": "defrag", "files_done": 1, "files_total": 4 })).unwrap();
+    assert_eq!(s.phase, None);
+    assert_eq!(s.percent(), Some(25.0));
+  }
+
+  #[test]
+  fn throttles_but_never_drops_phase_changes_or_completion() {
+    let t0 = Instant::now();
+    let mut tracker = ProgressTracker::default();
+    let compress = |done| ProgressSample { phase: Some(Phase::Compress), bytes_done: Some(done), bytes_total: Some(1000), ..Default::default() };
+
+    assert!(tracker.update(compress(100), t0).is_some());
+    assert!(tracker.update(compress(200), ms(t0, 20)).is_none());
+    assert!(tracker.update(compress(300), ms(t0, 40)).is_none());
+    assert_eq!(tracker.flush().unwrap().bytes_done, Some(300));
+    assert!(tracker.update(compress(400), ms(t0, 150)).is_some());
+
+    let encrypt = ProgressSample { phase: Some(Phase::Encrypt), ..ProgressSample::from_percent(0.0) };
+    assert_eq!(tracker.update(encrypt, ms(t0, 160)).unwrap().phase, Some(Phase::Encrypt));
+    assert_eq!(tracker.update(ProgressSample::from_percent(100.0), ms(t0, 170)).unwrap().percent, 100);
+    assert_eq!(tracker.flush(), None);
+  }
+
+  #[test]
+  fn estimates_throughput_and_eta_per_phase() {
+    let t0 = Instant::now();
+    let mut tracker = ProgressTracker::default();
+    let write = |done| ProgressSample { phase: Some(Phase::Write), bytes_done: Some(done), bytes_total: Some(10_000), ..Default::default() };
+
+    let first = tracker.update(write(0), t0).unwrap();
+    assert_eq!((first.throughput, first.eta_secs), (None, None));
+
+    let second = tracker.update(write(1000), ms(t0, 1000)).unwrap();
+    assert_eq!(second.throughput, Some(1000.0));
+    assert_eq!(second.eta_secs, Some(9));
+    assert_eq!(second.percent, 10);
+
+    let verify = ProgressSample { phase: Some(Phase::Verify), bytes_done: Some(0), bytes_total: Some(10_000), ..Default::default() };
+    let third = tracker.update(verify, ms(t0, 1100)).unwrap();
+    assert_eq!((third.throughput, third.eta_secs), (None, None));
+  }
+}
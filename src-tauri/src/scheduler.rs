@@ -0,0 +1,272 @@
+//! scheduler.rs — global concurrency cap and per-container exclusion for tvault-core operations
+//!
+//! Every operation names the containers it touches. It starts only when a slot
+//! is free and none of its containers is held by a running operation, so a
+//! seal and a reseal never race on the same file. Waiting operations start in
+//! priority order, then first come first served; an operation blocked on its
+//! container does not hold back the ones behind it. Containers are compared
+//! by `container::key`, so two spellings of one path still exclude each other.
+
+use std::{
+  collections::HashSet,
+  path::PathBuf,
+  sync::{Arc, Mutex},
+};
+
+use tokio::sync::oneshot;
+
+use crate::container;
+
+/// Lower runs first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+  /// The user is waiting on the answer, e.g. a single container info.
+  Interactive,
+  /// Seal, unseal, reseal and batch work.
+  Bulk,
+}
+
+type OnQueued = Arc<dyn Fn(usize) + Send + Sync>;
+
+#[derive(Clone)]
+pub struct Scheduler {
+  inner: Arc<Inner>,
+}
+
+struct Inner {
+  cap:   usize,
+  state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+  running: usize,
+  busy:    HashSet<PathBuf>,
+  seq:     u64,
+  queue:   Vec<Waiter>,
+}
+
+struct Waiter {
+  seq:       u64,
+  priority:  Priority,
+  paths:     Vec<PathBuf>,
+  wake:      oneshot::Sender<Permit>,
+  /// Last position reported through `on_queued`; 0 before the first report.
+  position:  usize,
+  on_queued: OnQueued,
+}
+
+/// Held for as long as the operation runs; dropping it lets the next one start.
+pub struct Permit {
+  sched: Scheduler,
+  paths: Vec<PathBuf>,
+}
+
+impl Scheduler {
+  pub fn new(cap: usize) -> Self {
+    Scheduler { inner: Arc::new(Inner { cap: cap.max(1), state: Mutex::default() }) }
+  }
+
+  /// Waits for a slot and exclusive use of `paths`. While waiting, `on_queued`
+  /// receives the 1-based queue position every time it changes. Dropping the
+  /// future leaves the queue.
+  pub async fn acquire(&self, paths: Vec<PathBuf>, priority: Priority, on_queued: impl Fn(usize) + Send + Sync + 'static) -> Permit {
+    let paths: Vec<PathBuf> = paths.iter().map(|p| container::key(p)).collect();
+    let (wake, rx) = oneshot::channel();
+    let seq = {
+      let mut st = self.inner.state.lock().unwrap();
+      st.seq += 1;
+      let seq = st.seq;
+      st.queue.push(Waiter { seq, priority, paths, wake, position: 0, on_queued: Arc::new(on_queued) });
+      seq
+    };
+    let _leave = Leave { sched: self, seq };
+    self.dispatch();
+    // a waiter only leaves the queue by being granted or through `Leave`
+    rx.await.expect("scheduler dropped a queued operation")
+  }
+
+  /// Operations running and waiting right now.
+  pub fn load(&self) -> (usize, usize) {
+    let st = self.inner.state.lock().unwrap();
+    (st.running, st.queue.len())
+  }
+
+  fn dispatch(&self) {
+    let mut grants = Vec::new();
+    let mut notices = Vec::new();
+    {
+      let mut st = self.inner.state.lock().unwrap();
+      st.queue.sort_by_key(|w| (w.priority, w.seq));
+      let mut i = 0;
+      while i < st.queue.len() && st.running < self.inner.cap {
+        if st.queue[i].paths.iter().any(|p| st.busy.contains(p)) {
+          i += 1;
+          continue;
+        }
+        let w = st.queue.remove(i);
+        st.running += 1;
+        st.busy.extend(w.paths.iter().cloned());
+        grants.push((w.wake, Permit { sched: self.clone(), paths: w.paths }));
+      }
+      for (i, w) in st.queue.iter_mut().enumerate() {
+        if w.position != i + 1 {
+          w.position = i + 1;
+          notices.push((w.on_queued.clone(), i + 1));
+        }
+      }
+    }
+    // outside the lock: a failed send drops the permit, which dispatches again
+    for (wake, permit) in grants {
+      let _ = wake.send(permit);
+    }
+    for (on_queued, position) in notices {
+      on_queued(position);
+    }
+  }
+}
+
+impl Drop for Permit {
+  fn drop(&mut self) {
+    {
+      let mut st = self.sched.inner.state.lock().unwrap();
+      st.running -= 1;
+      for p in &self.paths {
+        st.busy.remove(p);
+      }
+    }
+    self.sched.dispatch();
+  }
+}
+
+/// Removes a waiter whose `acquire` future was dropped before it was granted.
+struct Leave<'a> {
+  sched: &'a Scheduler,
+  seq:   u64,
+}
+
+impl Drop for Leave<'_> {
+  fn drop(&mut self) {
+    let removed = {
+      let mut st = self.sched.inner.state.lock().unwrap();
+      let before = st.queue.len();
+      st.queue.retain(|w| w.seq != self.seq);
+      st.queue.len() != before
+    };
+    if removed {
+      self.sched.dispatch();
+    }
+  }
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::{sync::Mutex as StdMutex, time::Duration};
+
+  fn path(p: &str) -> Vec<PathBuf> {
+    vec![PathBuf::from(p)]
+  }
+
+  fn rt() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap()
+  }
+
+  /// Lets spawned tasks run until they block.
+  async fn settle() {
+    tokio::time::sleep(Duration::from_millis(5)).await;
+  }
+
+  #[test]
+  fn caps_concurrency() {
+    rt().block_on(async {
+      let s = Scheduler::new(2);
+      let a = s.acquire(path("a"), Priority::Bulk, |_| {}).await;
+      let _b = s.acquire(path("b"), Priority::Bulk, |_| {}).await;
+      let c = tokio::spawn({
+        let s = s.clone();
+        async move { s.acquire(path("c"), Priority::Bulk, |_| {}).await }
+      });
+      settle().await;
+      assert_eq!(s.load(), (2, 1));
+      drop(a);
+      let _c = c.await.unwrap();
+      assert_eq!(s.load(), (2, 0));
+    });
+  }
+
+  #[test]
+  fn excludes_operations_on_the_same_container_without_blocking_others() {
+    rt().block_on(async {
+      let s = Scheduler::new(4);
+      let order = Arc::new(StdMutex::new(Vec::new()));
+      let seal = s.acquire(path("vault.tvlt"), Priority::Bulk, |_| {}).await;
+
+      let spawn = |p: &'static str, tag: &'static str| {
+        let (s, order) = (s.clone(), order.clone());
+        tokio::spawn(async move {
+          let permit = s.acquire(path(p), Priority::Bulk, |_| {}).await;
+          order.lock().unwrap().push(tag);
+          permit
+        })
+      };
+      let reseal = spawn("vault.tvlt", "reseal");
+      let other = spawn("other.tvlt", "other");
+      let _other = other.await.unwrap();
+      assert_eq!(*order.lock().unwrap(), ["other"]);
+
+      drop(seal);
+      let _reseal = reseal.await.unwrap();
+      assert_eq!(*order.lock().unwrap(), ["other", "reseal"]);
+    });
+  }
+
+  #[test]
+  fn interactive_jumps_ahead_of_bulk_and_positions_are_reported() {
+    rt().block_on(async {
+      let s = Scheduler::new(1);
+      let positions = Arc::new(StdMutex::new(Vec::new()));
+      let order = Arc::new(StdMutex::new(Vec::new()));
+      let running = s.acquire(path("x"), Priority::Bulk, |_| {}).await;
+
+      let spawn = |p: &'static str, prio: Priority| {
+        let (s, order, positions) = (s.clone(), order.clone(), positions.clone());
+        tokio::spawn(async move {
+          let _permit = s.acquire(path(p), prio, move |n| positions.lock().unwrap().push((p, n))).await;
+          order.lock().unwrap().push(p);
+        })
+      };
+      let bulk = spawn("bulk", Priority::Bulk);
+      settle().await;
+      let info = spawn("info", Priority::Interactive);
+      settle().await;
+
+      drop(running);
+      bulk.await.unwrap();
+      info.await.unwrap();
+      assert_eq!(*order.lock().unwrap(), ["info", "bulk"]);
+      assert_eq!(*positions.lock().unwrap(), [("bulk", 1), ("info", 1), ("bulk", 2), ("bulk", 1)]);
+    });
+  }
+
+  #[test]
+  fn dropped_waiter_leaves_the_queue() {
+    rt().block_on(async {
+      let s = Scheduler::new(1);
+      let running = s.acquire(path("a"), Priority::Bulk, |_| {}).await;
+      let waiting = tokio::spawn({
+        let s = s.clone();
+        async move { s.acquire(path("b"), Priority::Bulk, |_| {}).await }
+      });
+      settle().await;
+      assert_eq!(s.load(), (1, 1));
+      waiting.abort();
+      let _ = waiting.await;
+      assert_eq!(s.load(), (1, 0));
+      drop(running);
+      assert_eq!(s.load(), (0, 0));
+    });
+  }
+}
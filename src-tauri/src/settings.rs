@@ -0,0 +1,33 @@
+//! settings.rs — backend read access to the frontend settings store
+
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+/// Same file the frontend persists vault settings into (see `Vault.actions.ts`).
+pub const SETTINGS_STORE: &str = "vault-settings.json";
+/// Recently opened containers, `{ path, lastOpenedAt, lastMountPath }` under `recent`.
+pub const RECENT_STORE: &str = "recent-containers.json";
+
+pub fn get<R: Runtime>(app: &AppHandle<R>, key: &str) -> Option<Value> {
+  app.store(SETTINGS_STORE).ok()?.get(key)
+}
+
+pub fn get_string<R: Runtime>(app: &AppHandle<R>, key: &str) -> Option<String> {
+  match get(app, key)? {
+    Value::String(s) if !s.trim().is_empty() => Some(s),
+    _ => None,
+  }
+}
+
+/// Every key in the settings store.
+pub fn all<R: Runtime>(app: &AppHandle<R>) -> Map<String, Value> {
+  app.store(SETTINGS_STORE).map(|store| store.entries().into_iter().collect()).unwrap_or_default()
+}
+
+pub fn recent<R: Runtime>(app: &AppHandle<R>) -> Vec<Value> {
+  match app.store(RECENT_STORE).ok().and_then(|store| store.get("recent")) {
+    Some(Value::Array(items)) => items,
+    _ => Vec::new(),
+  }
+}
@@ -0,0 +1,268 @@
+//! share_import.rs — reading shares back in and checking them before unseal
+//!
+//! Shares come back either as photos/scans of the pages `share_paper` prints
+//! (the QR code decodes to a `SharePayload`) or typed by hand, optionally with
+//! the checksum from the page. Each one is checked against its checksum, so a
+//! mistyped share is caught here instead of as a core failure. A page whose
+//! header hash matches the container about to be unsealed is checked; any
+//! other share is only unverified, since every reseal changes the hash.
+
+use std::{collections::HashSet, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  container::header_hash,
+  share_paper::{checksum, SharePayload},
+};
+
+/// A share as the UI hands it in: a scanned payload, or typed text.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ShareInput {
+  Scanned(SharePayload),
+  Typed {
+    share:    String,
+    /// Checksum printed on the page, with or without the space.
+    #[serde(default)]
+    checksum: Option<String>,
+  },
+}
+
+/// What one image held.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ScannedImage {
+  pub path:   String,
+  pub shares: Vec<ShareInput>,
+  pub error:  Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CheckSharesArgs {
+  shares:         Vec<ShareInput>,
+  /// Container the shares are meant to open.
+  container_path: Option<String>,
+  /// For typed shares, whose pages carry the threshold but not the text.
+  threshold:      Option<u8>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareState {
+  Ok,
+  /// Not tied to the container: typed by hand, or a page without its header
+  /// hash or printed before a reseal. Shown, but not counted as usable.
+  Unverified,
+  BadChecksum,
+  /// Same index or same text as an earlier share.
+  Duplicate,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ShareStatus {
+  pub index:    Option<usize>,
+  /// Checksum of the share as given, grouped like on the page.
+  pub checksum: String,
+  pub state:    ShareState,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ShareCheck {
+  pub shares:    Vec<ShareStatus>,
+  /// Checked shares, which count towards the threshold.
+  pub usable:     usize,
+  /// Shares that may still open the container, but nothing could vouch for.
+  pub unverified: usize,
+  pub threshold:  Option<u8>,
+  /// Whether `usable` reaches the threshold.
+  pub ready:      bool,
+  /// "you have 2 of the required 3 shares".
+  pub summary:    String,
+}
+
+/* ─────────── Commands ─────────── */
+
+/// Decodes every QR code in each image; a payload from a printed page comes
+/// back as `Scanned`, any other text as a typed share. Decoding runs on a
+/// blocking thread.
+#[tauri::command]
+pub async fn scan_share_images(paths: Vec<String>) -> Result<Vec<ScannedImage>, String> {
+  tauri::async_runtime::spawn_blocking(move || scan(paths)).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn check_shares(args: CheckSharesArgs) -> Result<ShareCheck, String> {
+  let container = match &args.container_path {
+    Some(p) => Some(header_hash(Path::new(p)).ok_or_else(|| format!("cannot read container {p}"))?),
+    None => None,
+  };
+  Ok(check(&args.shares, container.as_deref(), args.threshold))
+}
+
+/* ─────────── Checks ─────────── */
+
+fn scan(paths: Vec<String>) -> Vec<ScannedImage> {
+  paths
+    .into_iter()
+    .map(|path| match decode_image(Path::new(&path)) {
+      Ok(texts) => ScannedImage { shares: texts.into_iter().map(|t| parse_scan(&t)).collect(), path, error: None },
+      Err(e) => ScannedImage { path, shares: vec![], error: Some(e) },
+    })
+    .collect()
+}
+
+fn decode_image(path: &Path) -> Result<Vec<String>, String> {
+  let img = image::open(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?.to_luma8();
+  let mut prepared = rqrr::PreparedImage::prepare(img);
+  let texts: Vec<String> = prepared.detect_grids().iter().filter_map(|g| g.decode().ok()).map(|(_, text)| text).collect();
+  if texts.is_empty() {
+    return Err(format!("no readable QR code in {}", path.display()));
+  }
+  Ok(texts)
+}
+
+fn parse_scan(text: &str) -> ShareInput {
+  match serde_json::from_str::<SharePayload>(text) {
+    Ok(payload) => ShareInput::Scanned(payload),
+    Err(_) => ShareInput::Typed { share: text.trim().to_string(), checksum: None },
+  }
+}
+
+/// `container` is the header hash of the container to unseal, when known.
+fn check(shares: &[ShareInput], container: Option<&str>, threshold: Option<u8>) -> ShareCheck {
+  let mut seen_index = HashSet::new();
+  let mut seen_share = HashSet::new();
+  let mut threshold = threshold;
+
+  let statuses: Vec<ShareStatus> = shares
+    .iter()
+    .map(|input| {
+      let (share, index, expected, from) = match input {
+        ShareInput::Scanned(p) => {
+          threshold = threshold.or(Some(p.threshold));
+          (p.share.trim(), Some(p.index), Some(p.checksum.as_str()), p.container.as_deref())
+        }
+        ShareInput::Typed { share, checksum } => (share.trim(), None, checksum.as_deref(), None),
+      };
+      let typed = matches!(input, ShareInput::Typed { .. });
+      let actual = checksum(share);
+      let state = if expected.is_some_and(|c| normalize(c) != normalize(&actual)) {
+        ShareState::BadChecksum
+      } else if !seen_share.insert(share.to_string()) || index.is_some_and(|i| !seen_index.insert(i)) {
+        ShareState::Duplicate
+      } else if typed || container.is_some_and(|want| from != Some(want)) {
+        ShareState::Unverified
+      } else {
+        ShareState::Ok
+      };
+      ShareStatus { index, checksum: actual, state }
+    })
+    .collect();
+
+  let usable = statuses.iter().filter(|s| s.state == ShareState::Ok).count();
+  let unverified = statuses.iter().filter(|s| s.state == ShareState::Unverified).count();
+  let rejected = statuses.len() - usable - unverified;
+  let have = match unverified {
+    0 => usable.to_string(),
+    n => format!("{usable} checked and {n} unverified"),
+  };
+  let mut summary = match threshold {
+    Some(k) => format!("you have {have} of the required {k} shares"),
+    None => format!("you have {have} share{}; the threshold is not known", if usable + unverified == 1 { "" } else { "s" }),
+  };
+  if rejected > 0 {
+    summary.push_str(&format!(" ({rejected} rejected)"));
+  }
+  ShareCheck { ready: threshold.is_some_and(|k| usable >= usize::from(k)), shares: statuses, usable, unverified, threshold, summary }
+}
+
+fn normalize(checksum: &str) -> String {
+  checksum.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_ascii_lowercase()
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::fs;
+
+  fn scanned(index: usize, share: &str, container: Option<&str>) -> ShareInput {
+    ShareInput::Scanned(SharePayload {
+      tvault_share: 1,
+      vault:        "box".into(),
+      index,
+      total:        5,
+      threshold:    3,
+      checksum:     checksum(share).replace(' ', ""),
+      container:    container.map(Into::into),
+      share:        share.into(),
+    })
+  }
+
+  fn states(check: &ShareCheck) -> Vec<ShareState> {
+    check.shares.iter().map(|s| s.state).collect()
+  }
+
+  #[test]
+  fn counts_shares_against_the_threshold() {
+    let two = check(&[scanned(1, "s1", Some("abc")), scanned(4, "s4", Some("abc"))], Some("abc"), None);
+    assert_eq!(states(&two), [ShareState::Ok, ShareState::Ok]);
+    assert_eq!(two.summary, "you have 2 of the required 3 shares");
+    assert!(!two.ready);
+
+    let three = check(&[scanned(1, "s1", None), scanned(2, "s2", None), scanned(3, "s3", None)], None, None);
+    assert!(three.ready);
+    assert_eq!(three.threshold, Some(3));
+  }
+
+  #[test]
+  fn rejects_typos_and_repeated_shares() {
+    let typo = ShareInput::Typed { share: "s2x".into(), checksum: Some(checksum("s2").to_uppercase()) };
+    let result = check(&[scanned(1, "s1", Some("abc")), typo, scanned(3, "s3", Some("def")), scanned(1, "s1", Some("abc"))], Some("abc"), None);
+    assert_eq!(states(&result), [ShareState::Ok, ShareState::BadChecksum, ShareState::Unverified, ShareState::Duplicate]);
+    assert_eq!((result.usable, result.unverified), (1, 1));
+    assert_eq!(result.summary, "you have 1 checked and 1 unverified of the required 3 shares (2 rejected)");
+  }
+
+  #[test]
+  fn pages_printed_before_a_reseal_are_unverified_not_rejected() {
+    let dir = std::env::temp_dir().join(format!("tvault-share-reseal-{}", std::process::id()));
+    let box_path = dir.join("box.tvlt");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(&box_path, b"header v1").unwrap();
+    let printed = header_hash(&box_path).unwrap();
+    let pages: Vec<ShareInput> = (1..=3).map(|i| scanned(i, &format!("s{i}"), Some(&printed))).collect();
+    let args = || CheckSharesArgs { shares: pages.clone(), container_path: Some(box_path.display().to_string()), threshold: None };
+
+    let fresh = check_shares(args()).unwrap();
+    assert!(fresh.ready);
+
+    // resealing on close rewrites the header; the shares still open it
+    fs::write(&box_path, b"header v2").unwrap();
+    let resealed = check_shares(args()).unwrap();
+    assert_eq!(states(&resealed), [ShareState::Unverified; 3]);
+    assert_eq!((resealed.usable, resealed.unverified, resealed.threshold), (0, 3, Some(3)));
+    assert_eq!(resealed.summary, "you have 0 checked and 3 unverified of the required 3 shares");
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn typed_shares_stay_unverified_and_do_not_make_the_threshold() {
+    let typed = |s: &str, sum: Option<String>| ShareInput::Typed { share: s.into(), checksum: sum };
+    let result = check(&[typed("a", None), typed("b", Some(checksum("b")))], Some("abc"), Some(2));
+    assert_eq!(states(&result), [ShareState::Unverified, ShareState::Unverified]);
+    assert_eq!((result.usable, result.unverified), (0, 2));
+    assert!(!result.ready);
+    assert_eq!(result.summary, "you have 0 checked and 2 unverified of the required 2 shares");
+    assert_eq!(check(&[typed("a", None)], None, None).summary, "you have 0 checked and 1 unverified share; the threshold is not known");
+  }
+
+  #[test]
+  fn scans_parse_payloads_and_fall_back_to_text() {
+    let payload = scanned(2, "s2", None);
+    let ShareInput::Scanned(p) = &payload else { unreachable!() };
+    assert_eq!(parse_scan(&serde_json::to_string(p).unwrap()), payload);
+    assert_eq!(parse_scan(" raw-share\n"), ShareInput::Typed { share: "raw-share".into(), checksum: None });
+  }
+}
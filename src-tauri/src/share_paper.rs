@@ -0,0 +1,303 @@
+//! share_paper.rs — Shamir shares as printable QR pages
+//!
+//! Takes the share output of a seal (`token_list`, `share_list` or a bare
+//! array, inline or from the token file) and writes one page per share with
+//! the vault name, share index, threshold, a short checksum to compare against
+//! when the share is typed back in, and a QR code. Everything is rendered
+//! locally; pages are self-contained HTML (print to PDF from there) or SVG.
+//!
+//! The QR code holds a `SharePayload` rather than the bare share, so a scan
+//! also brings back the index, the threshold and the header hash of the
+//! container as it was sealed. The app reseals on close, which changes that
+//! hash while the shares stay valid, so it can confirm a share but never
+//! rule one out.
+
+use std::{fs, path::Path};
+
+use qrcode::{Color, EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::container::header_hash;
+
+/// Marks a QR payload as a tvault share, and its layout version.
+const PAYLOAD_VERSION: u32 = 1;
+/// Pixels per QR module.
+const MODULE_PX: usize = 6;
+/// Light modules around the code, as the QR spec asks for.
+const QUIET_ZONE: usize = 4;
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PaperFormat {
+  #[default]
+  Html,
+  Svg,
+}
+
+#[derive(Deserialize)]
+pub struct SharePaperArgs {
+  vault_name:  String,
+  threshold:   u8,
+  /// Seal result as emitted in `encrypt-result`.
+  output:      Option<Value>,
+  /// Token file written with `token_save_type=file`.
+  shares_path: Option<String>,
+  out_dir:     String,
+  #[serde(default)]
+  format:      PaperFormat,
+  /// Sealed container, whose header hash goes on each page.
+  container_path: Option<String>,
+}
+
+/// What a share QR code encodes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SharePayload {
+  pub tvault_share: u32,
+  pub vault:        String,
+  pub index:        usize,
+  pub total:        usize,
+  pub threshold:    u8,
+  /// `checksum(share)` without the space.
+  pub checksum:     String,
+  /// `container::header_hash` when the page was printed; a reseal changes it.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub container:    Option<String>,
+  pub share:        String,
+}
+
+/// One share as printed.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct SharePage {
+  pub index:    usize,
+  pub checksum: String,
+  pub path:     String,
+}
+
+/// Writes one page per share into `out_dir` and returns them in share order.
+/// QR encoding and the writes run on a blocking thread.
+#[tauri::command]
+pub async fn export_share_papers(args: SharePaperArgs) -> Result<Vec<SharePage>, String> {
+  tauri::async_runtime::spawn_blocking(move || write_papers(&args)).await.map_err(|e| e.to_string())?
+}
+
+fn write_papers(args: &SharePaperArgs) -> Result<Vec<SharePage>, String> {
+  let output = match (&args.output, &args.shares_path) {
+    (Some(v), _) => v.clone(),
+    (None, Some(p)) => serde_json::from_str(&fs::read_to_string(p).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?,
+    (None, None) => return Err("pass the seal output or the token file".into()),
+  };
+  let shares = shares_of(&output)?;
+  let total = shares.len();
+  if total < 2 || args.threshold < 2 || usize::from(args.threshold) > total {
+    return Err(format!("cannot print a {} of {total} split", args.threshold));
+  }
+
+  let container = match &args.container_path {
+    Some(p) => Some(header_hash(Path::new(p)).ok_or_else(|| format!("cannot read container {p}"))?),
+    None => None,
+  };
+
+  fs::create_dir_all(&args.out_dir).map_err(|e| e.to_string())?;
+  let stem = safe_name(&args.vault_name);
+  let ext = match args.format {
+    PaperFormat::Html => "html",
+    PaperFormat::Svg => "svg",
+  };
+  shares
+    .iter()
+    .map(|(index, share)| {
+      let sheet = Sheet {
+        payload: SharePayload {
+          tvault_share: PAYLOAD_VERSION,
+          vault: args.vault_name.clone(),
+          index: *index,
+          total,
+          threshold: args.threshold,
+          checksum: checksum(share).replace(' ', ""),
+          container: container.clone(),
+          share: share.clone(),
+        },
+      };
+      let page = match args.format {
+        PaperFormat::Html => sheet.html()?,
+        PaperFormat::Svg => sheet.svg()?,
+      };
+      let path = Path::new(&args.out_dir).join(format!("{stem}-share-{index}-of-{total}.{ext}"));
+      fs::write(&path, page).map_err(|e| e.to_string())?;
+      Ok(SharePage { index: *index, checksum: sheet.grouped_checksum(), path: path.display().to_string() })
+    })
+    .collect()
+}
+
+/// `(index, share)` pairs, 1-based, ordered by index.
+fn shares_of(output: &Value) -> Result<Vec<(usize, String)>, String> {
+  let listed = |items: &Vec<Value>| items.iter().enumerate().map(|(i, s)| Some((i + 1, s.as_str()?.to_string()))).collect::<Option<Vec<_>>>();
+  let found = match output {
+    Value::Array(items) => listed(items),
+    Value::Object(o) => match (o.get("token_list"), o.get("share_list")) {
+      (Some(Value::Array(items)), _) => listed(items),
+      (_, Some(Value::Object(map))) => {
+        let mut pairs = map.iter().map(|(k, v)| Some((k.parse().ok()?, v.as_str()?.to_string()))).collect::<Option<Vec<_>>>();
+        if let Some(p) = pairs.as_mut() {
+          p.sort();
+        }
+        pairs
+      }
+      _ => None,
+    },
+    _ => None,
+  };
+  found.filter(|s| !s.is_empty()).ok_or_else(|| "no shares in the seal output".into())
+}
+
+/// First 8 hex digits of the share's SHA-256, grouped for reading aloud.
+pub(crate) fn checksum(share: &str) -> String {
+  let hex: String = Sha256::digest(share.trim().as_bytes())[..4].iter().map(|b| format!("{b:02x}")).collect();
+  format!("{} {}", &hex[..4], &hex[4..])
+}
+
+fn safe_name(name: &str) -> String {
+  let s: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+  if s.is_empty() { "vault".into() } else { s }
+}
+
+fn escape(text: &str) -> String {
+  text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+struct Sheet {
+  payload: SharePayload,
+}
+
+impl Sheet {
+  fn caption(&self) -> String {
+    let p = &self.payload;
+    format!("Share {} of {} — any {} open the vault", p.index, p.total, p.threshold)
+  }
+
+  fn grouped_checksum(&self) -> String {
+    let c = &self.payload.checksum;
+    format!("{} {}", &c[..4], &c[4..])
+  }
+
+  fn qr(&self) -> Result<(String, usize), String> {
+    qr_svg(&serde_json::to_string(&self.payload).map_err(|e| e.to_string())?)
+  }
+
+  fn html(&self) -> Result<String, String> {
+    let p = &self.payload;
+    let (qr, _) = self.qr()?;
+    Ok(format!(
+      concat!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title>\n",
+        "<style>body{{font-family:sans-serif;text-align:center;margin:2cm}}",
+        "code{{display:block;word-break:break-all;font-size:10pt;margin-top:1em}}</style></head>\n",
+        "<body>\n<h1>{vault}</h1>\n<h2>{caption}</h2>\n{qr}\n<p>Checksum: <strong>{checksum}</strong></p>\n<code>{share}</code>\n</body></html>\n",
+      ),
+      title = escape(&format!("{} – share {}", p.vault, p.index)),
+      vault = escape(&p.vault),
+      caption = escape(&self.caption()),
+      qr = qr,
+      checksum = self.grouped_checksum(),
+      share = escape(&p.share),
+    ))
+  }
+
+  fn svg(&self) -> Result<String, String> {
+    let (qr, size) = self.qr()?;
+    let width = size.max(480);
+    let text = |y: usize, px: usize, s: &str| format!("<text x=\"{}\" y=\"{y}\" font-family=\"sans-serif\" font-size=\"{px}\" text-anchor=\"middle\">{}</text>\n", width / 2, escape(s));
+    let mut out = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{}\">\n", size + 140);
+    out.push_str("<rect width=\"100%\" height=\"100%\" fill=\"#fff\"/>\n");
+    out.push_str(&text(34, 24, &self.payload.vault));
+    out.push_str(&text(64, 16, &self.caption()));
+    out.push_str(&format!("<g transform=\"translate({} 80)\">{qr}</g>\n", (width - size) / 2));
+    out.push_str(&text(size + 110, 16, &format!("Checksum: {}", self.grouped_checksum())));
+    out.push_str("</svg>\n");
+    Ok(out)
+  }
+}
+
+/// The share as an inline `<svg>` and its side length in pixels.
+fn qr_svg(data: &str) -> Result<(String, usize), String> {
+  let code = QrCode::with_error_correction_level(data.as_bytes(), EcLevel::M).map_err(|e| e.to_string())?;
+  let width = code.width();
+  let size = (width + 2 * QUIET_ZONE) * MODULE_PX;
+  let mut path = String::new();
+  for (i, color) in code.to_colors().into_iter().enumerate() {
+    if color == Color::Dark {
+      let (x, y) = ((i % width + QUIET_ZONE) * MODULE_PX, (i / width + QUIET_ZONE) * MODULE_PX);
+      path.push_str(&format!("M{x} {y}h{MODULE_PX}v{MODULE_PX}h-{MODULE_PX}z"));
+    }
+  }
+  let svg = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"0 0 {size} {size}\" shape-rendering=\"crispEdges\"><rect width=\"{size}\" height=\"{size}\" fill=\"#fff\"/><path d=\"{path}\" fill=\"#000\"/></svg>"
+  );
+  Ok((svg, size))
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn args(output: Value, format: PaperFormat, out_dir: &Path) -> SharePaperArgs {
+    SharePaperArgs {
+      vault_name: "Team <box>".into(),
+      threshold: 2,
+      output: Some(output),
+      shares_path: None,
+      out_dir: out_dir.display().to_string(),
+      format,
+      container_path: None,
+    }
+  }
+
+  #[test]
+  fn reads_every_share_output_shape() {
+    let list = json!({ "token_list": ["a", "b", "c"] });
+    let map = json!({ "share_list": { "2": "b", "1": "a", "3": "c" } });
+    let expected: Vec<(usize, String)> = vec![(1, "a".into()), (2, "b".into()), (3, "c".into())];
+    assert_eq!(shares_of(&list).unwrap(), expected);
+    assert_eq!(shares_of(&map).unwrap(), expected);
+    assert_eq!(shares_of(&json!(["a", "b", "c"])).unwrap(), expected);
+    assert!(shares_of(&json!({ "master_token": "mt" })).is_err());
+  }
+
+  #[test]
+  fn writes_one_labelled_page_per_share() {
+    let dir = std::env::temp_dir().join(format!("tvault-share-paper-{}", std::process::id()));
+    let shares = json!({ "token_list": ["share-one", "share-two", "share-three"] });
+
+    let pages = write_papers(&args(shares.clone(), PaperFormat::Html, &dir)).unwrap();
+    assert_eq!(pages.len(), 3);
+    assert!(pages[1].path.ends_with("Team__box_-share-2-of-3.html"));
+    let html = fs::read_to_string(&pages[1].path).unwrap();
+    assert!(html.contains("<h1>Team &lt;box&gt;</h1>"));
+    assert!(html.contains("Share 2 of 3 — any 2 open the vault"));
+    assert!(html.contains(&pages[1].checksum));
+    assert!(html.contains("<code>share-two</code>"));
+
+    let svg = write_papers(&args(shares, PaperFormat::Svg, &dir)).unwrap();
+    assert!(fs::read_to_string(&svg[0].path).unwrap().starts_with("<svg"));
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn rejects_impossible_splits() {
+    let dir = std::env::temp_dir();
+    let mut a = args(json!(["a", "b"]), PaperFormat::Html, &dir);
+    a.threshold = 3;
+    assert_eq!(write_papers(&a).unwrap_err(), "cannot print a 3 of 2 split");
+  }
+
+  #[test]
+  fn checksum_ignores_surrounding_whitespace() {
+    assert_eq!(checksum("abc"), checksum(" abc\n"));
+    assert_eq!(checksum("abc").len(), 9);
+  }
+}
@@ -0,0 +1,339 @@
+//! sidecar.rs — locate tvault-core and verify it against what was pinned at build time
+//!
+//! A candidate passes when its SHA-256 matches the pinned checksum or, on
+//! macOS, when its code signature names the pinned Team ID (bundling re-signs
+//! the sidecar, so a signed release no longer matches the unsigned checksum).
+//!
+//! The checked file is kept open and that handle is what runs: on Linux the
+//! core is executed through `/proc/self/fd`, on Windows the handle denies
+//! writes, renames and deletes until the process has started. macOS has no
+//! exec-by-descriptor, so there the file is run by path and the kernel's code
+//! signing checks cover the window between verification and spawn.
+
+use std::{
+  env::current_exe,
+  fs::{File, OpenOptions},
+  io::{self, Read},
+  path::{Path, PathBuf},
+  process::Command,
+  sync::Arc,
+};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager, Runtime, Wry};
+
+use crate::settings;
+
+/// Environment override for the tvault-core location.
+pub const CORE_PATH_ENV: &str = "TVAULT_CORE_PATH";
+/// Settings store key for the tvault-core location.
+pub const CORE_PATH_SETTING: &str = "corePath";
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateSource {
+  Settings,
+  Env,
+  Bundled,
+  DevFallback,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Candidate {
+  pub path:     String,
+  pub source:   CandidateSource,
+  pub sha256:   Option<String>,
+  /// `None` when the candidate passed every check.
+  pub rejected: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SidecarReport {
+  pub pinned_sha256:  Option<String>,
+  pub pinned_team_id: Option<String>,
+  pub selected:       Option<String>,
+  pub candidates:     Vec<Candidate>,
+}
+
+/// What a tvault-core candidate must match. The app uses the pins build.rs
+/// injects; the integration tests manage their own for the stub core.
+#[derive(Clone, Debug)]
+pub struct CorePin {
+  sha256:  Option<String>,
+  team_id: Option<String>,
+}
+
+impl CorePin {
+  fn built_in() -> Self {
+    CorePin {
+      sha256:  option_env!("TVAULT_CORE_SHA256").map(str::to_string),
+      team_id: option_env!("TVAULT_CORE_TEAM_ID").map(str::to_string),
+    }
+  }
+
+  /// Pins a stub core by its checksum. Test support only: release builds
+  /// leave it out, so nothing but the build-time pin can be trusted there.
+  #[cfg(debug_assertions)]
+  pub fn sha256(digest: &str) -> Self {
+    CorePin { sha256: Some(digest.to_lowercase()), team_id: None }
+  }
+}
+
+/// A verified tvault-core, held open so the file that was checked is the one that runs.
+#[derive(Clone, Debug)]
+pub struct CoreBinary {
+  path: PathBuf,
+  file: Arc<File>,
+}
+
+impl CoreBinary {
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// A command running the verified file. Keep `self` alive until it has spawned.
+  pub fn command(&self) -> Command {
+    #[cfg(target_os = "linux")]
+    {
+      use std::os::fd::AsRawFd;
+      let by_fd = PathBuf::from(format!("/proc/self/fd/{}", self.file.as_raw_fd()));
+      if by_fd.exists() {
+        return Command::new(by_fd);
+      }
+    }
+    Command::new(&self.path)
+  }
+}
+
+/* ─────────── Public API ─────────── */
+
+/// Returns the first candidate that matches the pin. A configured override
+/// (settings or `TVAULT_CORE_PATH`) that fails is an error rather than a
+/// reason to fall back to the bundled core.
+pub fn locate_binary<R: Runtime>(app: &AppHandle<R>) -> Result<CoreBinary, String> {
+  let pin = pin(app);
+  let mut tried: Vec<String> = Vec::new();
+  for (path, source) in candidates(app) {
+    let (probe, file) = probe(&path, source, &pin);
+    match (probe.rejected, file) {
+      (None, Some(file)) => {
+        prepare_executable(&file, &path);
+        return Ok(CoreBinary { path, file: Arc::new(file) });
+      }
+      (Some(reason), _) if is_override(source) => {
+        return Err(format!("tvault-core at {} ({}) is rejected: {reason}", probe.path, override_name(source)));
+      }
+      (reason, _) => tried.push(format!("{} ({})", probe.path, reason.unwrap_or_default())),
+    }
+  }
+  Err(format!("tvault-core not found. Tried: {}", tried.join(", ")))
+}
+
+/// Lists every candidate with the reason it was accepted or rejected.
+#[tauri::command]
+pub fn sidecar_diagnostics(app: AppHandle<Wry>) -> SidecarReport {
+  report(&app)
+}
+
+pub fn report<R: Runtime>(app: &AppHandle<R>) -> SidecarReport {
+  let pin = pin(app);
+  let candidates: Vec<Candidate> = candidates(app)
+    .into_iter()
+    .map(|(path, source)| probe(&path, source, &pin).0)
+    .collect();
+  // the same choice locate_binary makes: a rejected override ends the search
+  let selected = candidates
+    .iter()
+    .find(|c| c.rejected.is_none() || is_override(c.source))
+    .filter(|c| c.rejected.is_none())
+    .map(|c| c.path.clone());
+  SidecarReport {
+    pinned_sha256: pin.sha256,
+    pinned_team_id: pin.team_id,
+    selected,
+    candidates,
+  }
+}
+
+fn pin<R: Runtime>(app: &AppHandle<R>) -> CorePin {
+  app.try_state::<CorePin>().map(|p| p.inner().clone()).unwrap_or_else(CorePin::built_in)
+}
+
+fn is_override(source: CandidateSource) -> bool {
+  matches!(source, CandidateSource::Settings | CandidateSource::Env)
+}
+
+fn override_name(source: CandidateSource) -> &'static str {
+  match source {
+    CandidateSource::Settings => "corePath setting",
+    _ => CORE_PATH_ENV,
+  }
+}
+
+/* ─────────── Candidates ─────────── */
+
+fn candidates<R: Runtime>(app: &AppHandle<R>) -> Vec<(PathBuf, CandidateSource)> {
+  let mut v = Vec::new();
+
+  if let Some(p) = settings::get_string(app, CORE_PATH_SETTING) {
+    v.push((PathBuf::from(p), CandidateSource::Settings));
+  }
+  if let Ok(p) = std::env::var(CORE_PATH_ENV) {
+    if !p.trim().is_empty() {
+      v.push((PathBuf::from(p), CandidateSource::Env));
+    }
+  }
+
+  let Some(dir) = current_exe().ok().and_then(|e| e.parent().map(Path::to_path_buf)) else {
+    return v;
+  };
+
+  #[cfg(target_os = "windows")]
+  v.push((dir.join("tvault-core.exe"), CandidateSource::Bundled));
+
+  #[cfg(not(target_os = "windows"))]
+  {
+    v.push((dir.join("tvault-core"), CandidateSource::Bundled));
+    v.push((dir.join("tvault-core-aarch64-apple-darwin"), CandidateSource::Bundled));
+    // dev-run fallback: ../../src-tauri/binaries/...
+    if let Some(project_root) = dir.parent().and_then(|p| p.parent()) {
+      v.push((project_root.join("src-tauri/binaries/tvault-core"), CandidateSource::DevFallback));
+      v.push((project_root.join("src-tauri/binaries/tvault-core-aarch64-apple-darwin"), CandidateSource::DevFallback));
+    }
+  }
+  v
+}
+
+/// The candidate's verdict and, when it passed, the handle it was checked through.
+fn probe(path: &Path, source: CandidateSource, pin: &CorePin) -> (Candidate, Option<File>) {
+  let mut c = Candidate {
+    path: path.display().to_string(),
+    source,
+    sha256: None,
+    rejected: None,
+  };
+  let file = match open(path) {
+    Ok(f) => f,
+    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+      c.rejected = Some("not found".into());
+      return (c, None);
+    }
+    Err(e) => {
+      c.rejected = Some(format!("cannot read: {e}"));
+      return (c, None);
+    }
+  };
+  if !file.metadata().is_ok_and(|m| m.is_file()) {
+    c.rejected = Some("not a file".into());
+    return (c, None);
+  }
+  let digest = match sha256_file(&file) {
+    Ok(d) => d,
+    Err(e) => {
+      c.rejected = Some(format!("cannot read: {e}"));
+      return (c, None);
+    }
+  };
+  c.rejected = check_pin(pin, path, &digest);
+  c.sha256 = Some(digest);
+  let passed = c.rejected.is_none();
+  (c, passed.then_some(file))
+}
+
+fn open(path: &Path) -> io::Result<File> {
+  let mut opts = OpenOptions::new();
+  opts.read(true);
+  // FILE_SHARE_READ only: nobody may write, rename or delete it while it is held
+  #[cfg(target_os = "windows")]
+  {
+    use std::os::windows::fs::OpenOptionsExt;
+    opts.share_mode(0x1);
+  }
+  opts.open(path)
+}
+
+fn check_pin(pin: &CorePin, path: &Path, digest: &str) -> Option<String> {
+  if pin.sha256.as_deref().is_some_and(|p| p.eq_ignore_ascii_case(digest)) {
+    return None;
+  }
+  if pin.team_id.as_deref().is_some_and(|team_id| signed_by(path, team_id)) {
+    return None;
+  }
+  match (&pin.sha256, &pin.team_id) {
+    // debug builds included: an unpinned build trusts no core at all
+    (None, None) => Some("tvault-core is not pinned in this build".into()),
+    (Some(_), None) => Some("checksum mismatch".into()),
+    (None, Some(_)) => Some("not signed by the pinned team".into()),
+    (Some(_), Some(_)) => Some("neither the checksum nor the code signature matches".into()),
+  }
+}
+
+/// Whether the code signature is valid and issued to `team_id` by Apple.
+#[cfg(target_os = "macos")]
+fn signed_by(path: &Path, team_id: &str) -> bool {
+  let requirement = format!("=anchor apple generic and certificate leaf[subject.OU] = \"{team_id}\"");
+  Command::new("/usr/bin/codesign")
+    .args(["--verify", "--strict", "-R"])
+    .arg(requirement)
+    .arg(path)
+    .output()
+    .is_ok_and(|out| out.status.success())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn signed_by(_path: &Path, _team_id: &str) -> bool {
+  false
+}
+
+fn sha256_file(mut file: &File) -> io::Result<String> {
+  let mut hasher = Sha256::new();
+  let mut buf = [0u8; 64 * 1024];
+  loop {
+    let n = file.read(&mut buf)?;
+    if n == 0 { break; }
+    hasher.update(&buf[..n]);
+  }
+  Ok(hasher.finalize().iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// Only called on a verified binary: restore the executable bit and drop the quarantine flag.
+fn prepare_executable(file: &File, path: &Path) {
+  #[cfg(unix)]
+  {
+    use std::os::unix::fs::PermissionsExt;
+    // through the handle, so it is the verified file that becomes executable
+    if let Ok(meta) = file.metadata() {
+      let mut perm = meta.permissions();
+      let mode = perm.mode();
+      if mode & 0o111 == 0 {
+        perm.set_mode(mode | 0o755);
+        let _ = file.set_permissions(perm);
+      }
+    }
+  }
+  #[cfg(not(unix))]
+  let _ = file;
+  // macOS Gatekeeper quarantine: try to drop attribute, best-effort
+  #[cfg(target_os = "macos")]
+  {
+    let _ = Command::new("/usr/bin/xattr").arg("-d").arg("com.apple.quarantine").arg(path).output();
+  }
+  #[cfg(not(target_os = "macos"))]
+  let _ = path;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn an_unpinned_build_rejects_every_core() {
+    let pin = CorePin { sha256: None, team_id: None };
+    assert_eq!(check_pin(&pin, Path::new("tvault-core"), "00"), Some("tvault-core is not pinned in this build".into()));
+
+    let pin = CorePin::sha256("AB");
+    assert_eq!(check_pin(&pin, Path::new("tvault-core"), "ab"), None);
+    assert_eq!(check_pin(&pin, Path::new("tvault-core"), "cd"), Some("checksum mismatch".into()));
+  }
+}
@@ -0,0 +1,328 @@
+//! signing.rs — ed25519 signing keys for sealing, kept encrypted in the app data dir
+//!
+//! Each private key is stored as its 32-byte seed, sealed with
+//! XChaCha20-Poly1305 under an Argon2id key derived from the key's passphrase.
+//! The public key is the associated data, so a sealed seed cannot be swapped
+//! under another key's entry. Keys are written as `ed25519:<base64>`; the core
+//! gets the private one on stdin, never in argv. Seeds, derived keys and the
+//! encoded private key are wiped once used; unlocking runs Argon2, so callers
+//! on the async runtime do it on a blocking thread.
+
+use std::{
+  fs,
+  path::PathBuf,
+  sync::Mutex,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use chacha20poly1305::{
+  aead::{Aead, KeyInit, Payload},
+  XChaCha20Poly1305, XNonce,
+};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager, Runtime, State, Wry};
+use zeroize::Zeroizing;
+
+use crate::data_file;
+
+pub const KEYS_FILE: &str = "signing-keys.json";
+const KEY_PREFIX: &str = "ed25519:";
+const FORMAT_VERSION: u32 = 1;
+/// Argon2id cost (OWASP minimum: 19 MiB, 2 passes).
+const KDF_MEMORY_KIB: u32 = 19 * 1024;
+const KDF_PASSES: u32 = 2;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Kdf {
+  salt:       String,
+  memory_kib: u32,
+  passes:     u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct StoredKey {
+  #[serde(flatten)]
+  info:   KeyInfo,
+  kdf:    Kdf,
+  nonce:  String,
+  /// Encrypted seed.
+  sealed: String,
+}
+
+/// What the UI sees of a key.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct KeyInfo {
+  /// First 8 bytes of the SHA-256 of the public key, hex.
+  pub id:         String,
+  pub name:       String,
+  pub public_key: String,
+  pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Stored {
+  version: u32,
+  keys:    Vec<StoredKey>,
+}
+
+pub struct KeyStore {
+  /// `None` keeps the keys in memory only.
+  file:     Option<PathBuf>,
+  keys:     Mutex<Vec<StoredKey>>,
+  /// Why the file could not be loaded nor moved aside; saving is refused.
+  unusable: Option<String>,
+}
+
+impl KeyStore {
+  /// An unusable file is moved aside (see `data_file`) rather than overwritten.
+  pub fn open(file: Option<PathBuf>) -> Self {
+    let loaded = file.as_deref().map(|f| data_file::load::<Stored>(f, FORMAT_VERSION, |s| s.version)).transpose();
+    let (stored, unusable) = match loaded {
+      Ok(stored) => (stored.flatten().unwrap_or_default(), None),
+      Err(e) => (Stored::default(), Some(e)),
+    };
+    KeyStore { file, keys: Mutex::new(stored.keys), unusable }
+  }
+
+  pub fn for_app<R: Runtime>(app: &AppHandle<R>) -> Self {
+    Self::open(app.path().app_data_dir().ok().map(|d| d.join(KEYS_FILE)))
+  }
+
+  pub fn generate(&self, name: &str, passphrase: &str) -> Result<KeyInfo, String> {
+    if passphrase.is_empty() {
+      return Err("a passphrase is required to protect the signing key".into());
+    }
+    let key = SigningKey::generate(&mut OsRng);
+    let public = key.verifying_key();
+    let kdf = Kdf { salt: B64.encode(random::<16>()), memory_kib: KDF_MEMORY_KIB, passes: KDF_PASSES };
+    let nonce = random::<24>();
+    let sealed = cipher(&kdf, passphrase)?
+      .encrypt(XNonce::from_slice(&nonce), Payload { msg: key.as_bytes(), aad: public.as_bytes() })
+      .map_err(|_| "cannot seal signing key")?;
+
+    let info = KeyInfo {
+      id: fingerprint(&public),
+      name: name.trim().to_string(),
+      public_key: encode_public(&public),
+      created_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default(),
+    };
+    let mut keys = self.keys.lock().unwrap();
+    let mut next = keys.clone();
+    next.push(StoredKey { info: info.clone(), kdf, nonce: B64.encode(nonce), sealed: B64.encode(sealed) });
+    // kept only once it is on disk
+    self.save(&next)?;
+    *keys = next;
+    Ok(info)
+  }
+
+  pub fn list(&self) -> Vec<KeyInfo> {
+    self.keys.lock().unwrap().iter().map(|k| k.info.clone()).collect()
+  }
+
+  pub fn remove(&self, id: &str) -> Result<bool, String> {
+    let mut keys = self.keys.lock().unwrap();
+    let next: Vec<StoredKey> = keys.iter().filter(|k| k.info.id != id).cloned().collect();
+    if next.len() == keys.len() {
+      return Ok(false);
+    }
+    self.save(&next)?;
+    *keys = next;
+    Ok(true)
+  }
+
+  /// Decrypts the private key; a wrong passphrase fails authentication.
+  pub fn unlock(&self, id: &str, passphrase: &str) -> Result<SigningKey, String> {
+    let stored = self.keys.lock().unwrap().iter().find(|k| k.info.id == id).cloned().ok_or_else(|| format!("unknown signing key {id}"))?;
+    let public = decode_public(&stored.info.public_key)?;
+    let nonce = B64.decode(&stored.nonce).map_err(|e| e.to_string())?;
+    let sealed = B64.decode(&stored.sealed).map_err(|e| e.to_string())?;
+    if nonce.len() != 24 {
+      return Err("corrupt signing key entry".into());
+    }
+    let seed = Zeroizing::new(
+      cipher(&stored.kdf, passphrase)?
+        .decrypt(XNonce::from_slice(&nonce), Payload { msg: &sealed, aad: public.as_bytes() })
+        .map_err(|_| "wrong passphrase for signing key")?,
+    );
+    let seed: Zeroizing<[u8; 32]> = Zeroizing::new(seed.as_slice().try_into().map_err(|_| "corrupt signing key entry")?);
+    // wipes itself on drop
+    Ok(SigningKey::from_bytes(&seed))
+  }
+
+  fn save(&self, keys: &[StoredKey]) -> Result<(), String> {
+    if let Some(e) = &self.unusable {
+      return Err(format!("signing keys cannot be saved: {e}"));
+    }
+    let Some(file) = &self.file else { return Ok(()) };
+    let raw = serde_json::to_vec_pretty(&Stored { version: FORMAT_VERSION, keys: keys.to_vec() }).map_err(|e| e.to_string())?;
+    if let Some(dir) = file.parent() {
+      fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let tmp = file.with_extension("json.tmp");
+    fs::write(&tmp, raw).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, file).map_err(|e| e.to_string())
+  }
+}
+
+fn cipher(kdf: &Kdf, passphrase: &str) -> Result<XChaCha20Poly1305, String> {
+  let salt = B64.decode(&kdf.salt).map_err(|e| e.to_string())?;
+  let params = Params::new(kdf.memory_kib, kdf.passes, 1, Some(32)).map_err(|e| e.to_string())?;
+  let mut key = Zeroizing::new([0u8; 32]);
+  Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+    .hash_password_into(passphrase.as_bytes(), &salt, key.as_mut())
+    .map_err(|e| e.to_string())?;
+  Ok(XChaCha20Poly1305::new(key.as_ref().into()))
+}
+
+fn random<const N: usize>() -> [u8; N] {
+  let mut buf = [0u8; N];
+  OsRng.fill_bytes(&mut buf);
+  buf
+}
+
+/// Short hex id of a public key; signing key ids and trust store entries use it.
+pub fn fingerprint(public: &VerifyingKey) -> String {
+  Sha256::digest(public.as_bytes())[..8].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub fn encode_public(public: &VerifyingKey) -> String {
+  format!("{KEY_PREFIX}{}", B64.encode(public.as_bytes()))
+}
+
+/// Parses `ed25519:<base64>` (the prefix is optional).
+pub fn decode_public(text: &str) -> Result<VerifyingKey, String> {
+  let raw = B64.decode(text.trim().trim_start_matches(KEY_PREFIX)).map_err(|_| "public key is not valid base64")?;
+  let bytes: [u8; 32] = raw.try_into().map_err(|_| "an ed25519 public key is 32 bytes")?;
+  VerifyingKey::from_bytes(&bytes).map_err(|_| "not a valid ed25519 public key".into())
+}
+
+/// Private key as tvault-core reads it from stdin for `-private-key=stdin`.
+/// Blocks on Argon2; the caller owns the only copy and must wipe it.
+pub(crate) fn core_private_key<R: Runtime>(app: &AppHandle<R>, id: &str, passphrase: &str) -> Result<String, String> {
+  let store = app.try_state::<KeyStore>().ok_or("signing keys are not available")?;
+  let key = store.unlock(id, passphrase)?;
+  // sized up front so encoding never reallocates and leaves a copy behind
+  let mut encoded = String::with_capacity(KEY_PREFIX.len() + 44);
+  encoded.push_str(KEY_PREFIX);
+  B64.encode_string(key.as_bytes(), &mut encoded);
+  Ok(encoded)
+}
+
+/// Public half of signing key `id`, as `ed25519:<base64>`.
+pub(crate) fn public_key<R: Runtime>(app: &AppHandle<R>, id: &str) -> Option<String> {
+  app.try_state::<KeyStore>()?.list().into_iter().find(|k| k.id == id).map(|k| k.public_key)
+}
+
+/* ─────────── Commands ─────────── */
+
+/// Runs Argon2 on a blocking thread.
+#[tauri::command]
+pub async fn generate_signing_key<R: Runtime>(app: AppHandle<R>, name: String, passphrase: String) -> Result<KeyInfo, String> {
+  let passphrase = Zeroizing::new(passphrase);
+  tauri::async_runtime::spawn_blocking(move || app.try_state::<KeyStore>().ok_or("signing keys are not available")?.generate(&name, &passphrase))
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn list_signing_keys(store: State<'_, KeyStore>) -> Vec<KeyInfo> {
+  store.list()
+}
+
+#[tauri::command]
+pub fn delete_signing_key(store: State<'_, KeyStore>, id: String) -> Result<bool, String> {
+  store.remove(&id)
+}
+
+/// Loads the key store once the app data dir is known.
+pub fn init(app: &AppHandle<Wry>) {
+  app.manage(KeyStore::for_app(app));
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ed25519_dalek::{Signer, Verifier};
+
+  #[test]
+  fn generated_key_unlocks_only_with_its_passphrase() {
+    let store = KeyStore::open(None);
+    let info = store.generate(" release ", "correct horse").unwrap();
+    assert_eq!(info.name, "release");
+    assert_eq!(info.id.len(), 16);
+
+    let key = store.unlock(&info.id, "correct horse").unwrap();
+    let public = decode_public(&info.public_key).unwrap();
+    let sig = key.sign(b"container header");
+    assert!(public.verify(b"container header", &sig).is_ok());
+
+    assert_eq!(store.unlock(&info.id, "wrong").unwrap_err(), "wrong passphrase for signing key");
+    assert!(store.generate("empty", "").is_err());
+  }
+
+  #[test]
+  fn keys_persist_encrypted() {
+    let dir = std::env::temp_dir().join(format!("tvault-signing-{}", std::process::id()));
+    let file = dir.join(KEYS_FILE);
+    let info = KeyStore::open(Some(file.clone())).generate("team", "pass").unwrap();
+
+    let raw = fs::read_to_string(&file).unwrap();
+    let key = KeyStore::open(Some(file.clone())).unlock(&info.id, "pass").unwrap();
+    assert!(!raw.contains(&B64.encode(key.as_bytes())));
+
+    let reopened = KeyStore::open(Some(file));
+    assert_eq!(reopened.list(), vec![info.clone()]);
+    assert_eq!(reopened.remove(&info.id), Ok(true));
+    assert!(reopened.list().is_empty());
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn an_unreadable_key_file_is_moved_aside_not_overwritten() {
+    let dir = std::env::temp_dir().join(format!("tvault-signing-corrupt-{}", std::process::id()));
+    let file = dir.join(KEYS_FILE);
+    fs::create_dir_all(&dir).unwrap();
+    for stale in [r#"{"version":1,"keys":[{"#, r#"{"version":99,"keys":[]}"#] {
+      fs::write(&file, stale).unwrap();
+      let store = KeyStore::open(Some(file.clone()));
+      assert!(store.list().is_empty());
+      assert!(!file.exists());
+      store.generate("fresh", "pass").unwrap();
+    }
+    assert_eq!(fs::read_to_string(dir.join(format!("{KEYS_FILE}.corrupt"))).unwrap(), r#"{"version":1,"keys":[{"#);
+    assert_eq!(fs::read_to_string(dir.join(format!("{KEYS_FILE}.corrupt.1"))).unwrap(), r#"{"version":99,"keys":[]}"#);
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn a_key_file_that_could_not_be_moved_aside_is_never_saved_over() {
+    let dir = std::env::temp_dir().join(format!("tvault-signing-stuck-{}", std::process::id()));
+    let file = dir.join(KEYS_FILE);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(&file, "unreadable").unwrap();
+    let store = KeyStore { file: Some(file.clone()), keys: Mutex::default(), unusable: Some("cannot be moved aside".into()) };
+
+    let err = store.generate("fresh", "pass").unwrap_err();
+    assert_eq!(err, "signing keys cannot be saved: cannot be moved aside");
+    assert!(store.list().is_empty());
+    assert_eq!(fs::read_to_string(&file).unwrap(), "unreadable");
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn public_keys_round_trip_and_reject_garbage() {
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let text = encode_public(&key.verifying_key());
+    assert!(text.starts_with("ed25519:"));
+    assert_eq!(decode_public(&text).unwrap(), key.verifying_key());
+    assert!(decode_public("ed25519:AAAA").is_err());
+    assert!(decode_public("not base64!").is_err());
+  }
+}
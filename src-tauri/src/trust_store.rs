@@ -0,0 +1,343 @@
+//! trust_store.rs — named signer public keys we trust, persisted in the app data dir
+//!
+//! Entries are keyed by fingerprint (see `signing::fingerprint`). `container
+//! info` only says whether a container is signed, not by whom, so a signer is
+//! "trusted" only once the core has checked the signature against a key from
+//! the store while unsealing; until then the key the user picked is merely
+//! claimed. With the `requireTrustedSigner` setting, unseal needs a trusted key
+//! to check against and reseal only writes containers signed by one.
+
+use std::{
+  fs,
+  path::{Path, PathBuf},
+  sync::Mutex,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime, State, Wry};
+
+use crate::{
+  data_file,
+  settings,
+  signing::{decode_public, encode_public, fingerprint, KeyStore},
+};
+
+pub const TRUST_FILE: &str = "trusted-keys.json";
+/// Settings store key: refuse to unseal containers not signed by a trusted key.
+pub const REQUIRE_TRUSTED_SETTING: &str = "requireTrustedSigner";
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TrustedKey {
+  pub fingerprint: String,
+  pub name:        String,
+  pub public_key:  String,
+  pub added_at:    u64,
+}
+
+/// What an exported key file holds.
+#[derive(Serialize, Deserialize)]
+struct KeyFile {
+  name:        String,
+  public_key:  String,
+  fingerprint: String,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Stored {
+  version: u32,
+  keys:    Vec<TrustedKey>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerStatus {
+  /// The core checked the signature against a trusted key.
+  Trusted,
+  /// The core checked the signature against a key not in the store.
+  Unknown,
+  /// Signed, but the signature has not been checked yet.
+  Unverified,
+  Unsigned,
+}
+
+/// Who signed a container, as far as the trust store can tell.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Signer {
+  pub status:      SignerStatus,
+  pub name:        Option<String>,
+  pub fingerprint: Option<String>,
+  pub public_key:  Option<String>,
+  /// "signed by Alice (trusted)", "signed by unknown key",
+  /// "claims signer Alice (unverified)", "signed (unverified)" or "unsigned".
+  pub label:       String,
+}
+
+pub struct TrustStore {
+  /// `None` keeps the keys in memory only.
+  file:     Option<PathBuf>,
+  keys:     Mutex<Vec<TrustedKey>>,
+  /// Why the file could not be loaded nor moved aside; saving is refused.
+  unusable: Option<String>,
+}
+
+impl TrustStore {
+  /// An unusable file is moved aside (see `data_file`) rather than overwritten.
+  pub fn open(file: Option<PathBuf>) -> Self {
+    let loaded = file.as_deref().map(|f| data_file::load::<Stored>(f, FORMAT_VERSION, |s| s.version)).transpose();
+    let (stored, unusable) = match loaded {
+      Ok(stored) => (stored.flatten().unwrap_or_default(), None),
+      Err(e) => (Stored::default(), Some(e)),
+    };
+    TrustStore { file, keys: Mutex::new(stored.keys), unusable }
+  }
+
+  pub fn for_app<R: Runtime>(app: &AppHandle<R>) -> Self {
+    Self::open(app.path().app_data_dir().ok().map(|d| d.join(TRUST_FILE)))
+  }
+
+  /// Adds the key, or renames it when it is already trusted.
+  pub fn trust(&self, name: &str, public_key: &str) -> Result<TrustedKey, String> {
+    let name = name.trim();
+    if name.is_empty() {
+      return Err("a trusted key needs a name".into());
+    }
+    let public = decode_public(public_key)?;
+    let fp = fingerprint(&public);
+    let mut keys = self.keys.lock().unwrap();
+    // kept only once it is on disk
+    let mut next = keys.clone();
+    let entry = match next.iter_mut().find(|k| k.fingerprint == fp) {
+      Some(existing) => {
+        existing.name = name.to_string();
+        existing.clone()
+      }
+      None => {
+        let added_at = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+        let key = TrustedKey { fingerprint: fp, name: name.to_string(), public_key: encode_public(&public), added_at };
+        next.push(key.clone());
+        key
+      }
+    };
+    self.save(&next)?;
+    *keys = next;
+    Ok(entry)
+  }
+
+  pub fn untrust(&self, fingerprint: &str) -> Result<bool, String> {
+    let mut keys = self.keys.lock().unwrap();
+    let next: Vec<TrustedKey> = keys.iter().filter(|k| k.fingerprint != fingerprint).cloned().collect();
+    if next.len() == keys.len() {
+      return Ok(false);
+    }
+    self.save(&next)?;
+    *keys = next;
+    Ok(true)
+  }
+
+  pub fn list(&self) -> Vec<TrustedKey> {
+    self.keys.lock().unwrap().clone()
+  }
+
+  pub fn get(&self, fingerprint: &str) -> Option<TrustedKey> {
+    self.keys.lock().unwrap().iter().find(|k| k.fingerprint == fingerprint).cloned()
+  }
+
+  /// A signature the core has checked against `public_key`.
+  pub fn verified(&self, public_key: &str) -> Signer {
+    let fp = decode_public(public_key).ok().map(|k| fingerprint(&k));
+    match fp.as_deref().and_then(|fp| self.get(fp)) {
+      Some(k) => Signer { status: SignerStatus::Trusted, label: format!("signed by {} (trusted)", k.name), name: Some(k.name), fingerprint: fp, public_key: Some(k.public_key) },
+      None => Signer { status: SignerStatus::Unknown, name: None, fingerprint: fp, public_key: Some(public_key.to_string()), label: "signed by unknown key".into() },
+    }
+  }
+
+  /// A signed container nobody has checked yet; `public_key` is the signer the user expects.
+  pub fn claimed(&self, public_key: Option<&str>) -> Signer {
+    let fp = public_key.and_then(|k| decode_public(k).ok()).map(|k| fingerprint(&k));
+    let known = fp.as_deref().and_then(|fp| self.get(fp));
+    let label = match (&known, &fp) {
+      (Some(k), _) => format!("claims signer {} (unverified)", k.name),
+      (None, Some(fp)) => format!("claims signer {fp} (unverified)"),
+      (None, None) => "signed (unverified)".into(),
+    };
+    Signer { status: SignerStatus::Unverified, name: known.map(|k| k.name), fingerprint: fp, public_key: public_key.map(str::to_string), label }
+  }
+
+  fn save(&self, keys: &[TrustedKey]) -> Result<(), String> {
+    if let Some(e) = &self.unusable {
+      return Err(format!("trusted keys cannot be saved: {e}"));
+    }
+    let Some(file) = &self.file else { return Ok(()) };
+    let raw = serde_json::to_vec_pretty(&Stored { version: FORMAT_VERSION, keys: keys.to_vec() }).map_err(|e| e.to_string())?;
+    if let Some(dir) = file.parent() {
+      fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let tmp = file.with_extension("json.tmp");
+    fs::write(&tmp, raw).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, file).map_err(|e| e.to_string())
+  }
+}
+
+fn unsigned() -> Signer {
+  Signer { status: SignerStatus::Unsigned, name: None, fingerprint: None, public_key: None, label: "unsigned".into() }
+}
+
+/// Whether `container info` data describes a signed container.
+pub fn is_signed(info: &Value) -> bool {
+  info.get("integrity_provider_type").and_then(Value::as_str) == Some("ed25519")
+}
+
+/// Signer annotation for info data, never more than "unverified"; `None`
+/// when the app has no trust store.
+pub(crate) fn annotate<R: Runtime>(app: &AppHandle<R>, info: &Value) -> Option<Signer> {
+  let store = app.try_state::<TrustStore>()?;
+  Some(if is_signed(info) { store.claimed(None) } else { unsigned() })
+}
+
+/// Whether the `requireTrustedSigner` policy is on.
+pub(crate) fn requires_trusted_signer<R: Runtime>(app: &AppHandle<R>) -> bool {
+  settings::get(app, REQUIRE_TRUSTED_SETTING).and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Checks `public_key` against the trust store for the policy; `doing` names
+/// the refused operation.
+pub(crate) fn require_trusted<R: Runtime>(app: &AppHandle<R>, public_key: Option<&str>, doing: &str) -> Result<(), String> {
+  let store = app.try_state::<TrustStore>().ok_or("trust store is not available")?;
+  let Some(key) = public_key else {
+    return Err(format!("refusing to {doing}: pick a trusted signer key"));
+  };
+  let trusted = decode_public(key).ok().is_some_and(|k| store.get(&fingerprint(&k)).is_some());
+  if trusted { Ok(()) } else { Err(format!("refusing to {doing}: the signer key is not trusted")) }
+}
+
+/// Reads an exported key file, or a bare `ed25519:<base64>` key named after the file.
+fn read_key_file(path: &Path, name: Option<String>) -> Result<(String, String), String> {
+  let raw = fs::read_to_string(path).map_err(|e| e.to_string())?;
+  if let Ok(file) = serde_json::from_str::<KeyFile>(&raw) {
+    return Ok((name.unwrap_or(file.name), file.public_key));
+  }
+  let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+  Ok((name.unwrap_or(stem), raw.trim().to_string()))
+}
+
+/* ─────────── Commands ─────────── */
+
+#[tauri::command]
+pub fn list_trusted_keys(store: State<'_, TrustStore>) -> Vec<TrustedKey> {
+  store.list()
+}
+
+#[tauri::command]
+pub fn trust_key(store: State<'_, TrustStore>, name: String, public_key: String) -> Result<TrustedKey, String> {
+  store.trust(&name, &public_key)
+}
+
+#[tauri::command]
+pub fn untrust_key(store: State<'_, TrustStore>, fingerprint: String) -> Result<bool, String> {
+  store.untrust(&fingerprint)
+}
+
+/// Trusts the key in `path`; `name` overrides the one in the file.
+#[tauri::command]
+pub fn import_trusted_key(store: State<'_, TrustStore>, path: String, name: Option<String>) -> Result<TrustedKey, String> {
+  let (name, public_key) = read_key_file(Path::new(&path), name)?;
+  store.trust(&name, &public_key)
+}
+
+/// Writes a trusted key, or the public half of one of our signing keys, to `path`.
+#[tauri::command]
+pub fn export_public_key<R: Runtime>(app: AppHandle<R>, fingerprint: String, path: String) -> Result<(), String> {
+  let trusted = app.try_state::<TrustStore>().and_then(|s| s.get(&fingerprint)).map(|k| (k.name, k.public_key));
+  let own = || app.try_state::<KeyStore>()?.list().into_iter().find(|k| k.id == fingerprint).map(|k| (k.name, k.public_key));
+  let (name, public_key) = trusted.or_else(own).ok_or_else(|| format!("unknown key {fingerprint}"))?;
+  let raw = serde_json::to_vec_pretty(&KeyFile { name, public_key, fingerprint }).map_err(|e| e.to_string())?;
+  fs::write(path, raw).map_err(|e| e.to_string())
+}
+
+/// Loads the trust store once the app data dir is known.
+pub fn init(app: &AppHandle<Wry>) {
+  app.manage(TrustStore::for_app(app));
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ed25519_dalek::SigningKey;
+  use serde_json::json;
+
+  fn public(seed: u8) -> String {
+    encode_public(&SigningKey::from_bytes(&[seed; 32]).verifying_key())
+  }
+
+  #[test]
+  fn only_a_checked_signature_is_labelled_trusted() {
+    let store = TrustStore::open(None);
+    let alice = store.trust("Alice", &public(1)).unwrap();
+
+    let checked = store.verified(&public(1));
+    assert_eq!((checked.status, checked.label.as_str()), (SignerStatus::Trusted, "signed by Alice (trusted)"));
+    let other = store.verified(&public(2));
+    assert_eq!((other.status, other.label.as_str()), (SignerStatus::Unknown, "signed by unknown key"));
+
+    let claim = store.claimed(Some(&public(1)));
+    assert_eq!((claim.status, claim.label.as_str()), (SignerStatus::Unverified, "claims signer Alice (unverified)"));
+    assert_eq!(claim.fingerprint, Some(alice.fingerprint));
+    let anonymous = store.claimed(None);
+    assert_eq!((anonymous.status, anonymous.label.as_str()), (SignerStatus::Unverified, "signed (unverified)"));
+    assert!(is_signed(&json!({ "integrity_provider_type": "ed25519" })));
+    assert!(!is_signed(&json!({ "integrity_provider_type": "hmac" })));
+  }
+
+  #[test]
+  fn trusting_again_renames_instead_of_duplicating() {
+    let store = TrustStore::open(None);
+    let first = store.trust("Alice", &public(1)).unwrap();
+    let again = store.trust("Alice (laptop)", &public(1)).unwrap();
+    assert_eq!(first.fingerprint, again.fingerprint);
+    assert_eq!(store.list().len(), 1);
+    assert_eq!(store.list()[0].name, "Alice (laptop)");
+    assert!(store.trust("Bob", "ed25519:AAAA").is_err());
+    assert_eq!(store.untrust(&first.fingerprint), Ok(true));
+    assert_eq!(store.untrust(&first.fingerprint), Ok(false));
+  }
+
+  #[test]
+  fn an_unloadable_trust_file_is_moved_aside_and_a_stuck_one_never_saved_over() {
+    let dir = std::env::temp_dir().join(format!("tvault-trust-corrupt-{}", std::process::id()));
+    let file = dir.join(TRUST_FILE);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(&file, "{ not json").unwrap();
+    let store = TrustStore::open(Some(file.clone()));
+    assert!(store.list().is_empty());
+    assert_eq!(fs::read_to_string(dir.join(format!("{TRUST_FILE}.corrupt"))).unwrap(), "{ not json");
+    store.trust("Alice", &public(1)).unwrap();
+    assert_eq!(TrustStore::open(Some(file.clone())).list().len(), 1);
+
+    let stuck = TrustStore { file: Some(file.clone()), keys: Mutex::default(), unusable: Some("cannot be moved aside".into()) };
+    assert_eq!(stuck.trust("Bob", &public(2)).unwrap_err(), "trusted keys cannot be saved: cannot be moved aside");
+    assert!(stuck.list().is_empty());
+    assert_eq!(TrustStore::open(Some(file)).list()[0].name, "Alice");
+    let _ = fs::remove_dir_all(&dir);
+  }
+
+  #[test]
+  fn imports_exported_and_bare_key_files() {
+    let dir = std::env::temp_dir().join(format!("tvault-trust-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let exported = dir.join("alice.json");
+    let bare = dir.join("bob.pub");
+    let key = decode_public(&public(1)).unwrap();
+    fs::write(&exported, serde_json::to_vec(&KeyFile { name: "Alice".into(), public_key: public(1), fingerprint: fingerprint(&key) }).unwrap()).unwrap();
+    fs::write(&bare, format!("{}\n", public(2))).unwrap();
+
+    assert_eq!(read_key_file(&exported, None).unwrap(), ("Alice".to_string(), public(1)));
+    assert_eq!(read_key_file(&bare, None).unwrap(), ("bob".to_string(), public(2)));
+    assert_eq!(read_key_file(&bare, Some("Bob".into())).unwrap().0, "Bob");
+    let _ = fs::remove_dir_all(&dir);
+  }
+}
@@ -0,0 +1,215 @@
+//! watchdog.rs — deadlines and inactivity detection for running tvault-core operations
+//!
+//! Any frame from the core (output, progress, result) counts as a sign of life.
+//! When a limit is hit the caller stops the operation (terminate, then kill
+//! after `Timeouts::grace`) and reports a `TimeoutError` naming the stalled phase.
+//! The limits are read from the settings store once, at startup.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::{sync::mpsc, time::Instant};
+
+use crate::{progress::Phase, settings};
+
+/// `{ infoIdleSecs, infoTotalSecs, bulkIdleSecs, bulkTotalSecs, graceSecs }`,
+/// any of them; a `bulkTotalSecs` or `infoTotalSecs` of 0 means unbounded.
+pub const TIMEOUTS_SETTING: &str = "coreTimeouts";
+
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+  /// Longest silence tolerated from the core.
+  pub idle:  Duration,
+  /// Wall-clock budget for the whole operation; `None` for unbounded.
+  pub total: Option<Duration>,
+}
+
+/// Limits per kind of operation. Defaults apply unless the app manages its own.
+#[derive(Clone, Debug)]
+pub struct Timeouts {
+  pub info:  Limits,
+  /// Seal, unseal and reseal; large vaults legitimately take hours.
+  pub bulk:  Limits,
+  /// Time between the graceful terminate and the kill.
+  pub grace: Duration,
+}
+
+impl Default for Timeouts {
+  fn default() -> Self {
+    Timeouts {
+      info:  Limits { idle: Duration::from_secs(15), total: Some(Duration::from_secs(60)) },
+      bulk:  Limits { idle: Duration::from_secs(120), total: None },
+      grace: Duration::from_secs(3),
+    }
+  }
+}
+
+impl Timeouts {
+  /// Defaults overridden by whatever the `coreTimeouts` setting holds.
+  pub fn from_setting(value: Option<&Value>) -> Self {
+    let mut t = Timeouts::default();
+    let secs = |key: &str| value.and_then(|v| v.get(key)).and_then(Value::as_u64).map(Duration::from_secs);
+    let total = |key: &str| secs(key).map(|d| Some(d).filter(|d| !d.is_zero()));
+    if let Some(d) = secs("infoIdleSecs").filter(|d| !d.is_zero()) {
+      t.info.idle = d;
+    }
+    if let Some(d) = total("infoTotalSecs") {
+      t.info.total = d;
+    }
+    if let Some(d) = secs("bulkIdleSecs").filter(|d| !d.is_zero()) {
+      t.bulk.idle = d;
+    }
+    if let Some(d) = total("bulkTotalSecs") {
+      t.bulk.total = d;
+    }
+    if let Some(d) = secs("graceSecs") {
+      t.grace = d;
+    }
+    t
+  }
+
+  pub fn for_prefix(&self, prefix: &str) -> Limits {
+    if prefix == "info" { self.info } else { self.bulk }
+  }
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Stall {
+  /// Nothing arrived for `Limits::idle`.
+  Inactivity,
+  /// `Limits::total` ran out.
+  Deadline,
+}
+
+/// Payload of `{prefix}-error` when a limit fires; shaped like a core error.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct TimeoutError {
+  pub code:       &'static str,
+  pub message:    String,
+  pub reason:     Stall,
+  /// Last phase the core reported, if any.
+  pub phase:      Option<Phase>,
+  pub after_secs: u64,
+}
+
+impl TimeoutError {
+  pub fn new(reason: Stall, phase: Option<Phase>, limits: Limits) -> Self {
+    let during = match phase {
+      Some(p) => format!("during {}", p.as_str()),
+      None => "before reporting progress".to_string(),
+    };
+    let (after, message) = match reason {
+      Stall::Inactivity => (limits.idle, format!("tvault-core stalled {during}: no activity for {}s", limits.idle.as_secs())),
+      Stall::Deadline => {
+        let total = limits.total.unwrap_or_default();
+        (total, format!("tvault-core timed out {during}: not finished after {}s", total.as_secs()))
+      }
+    };
+    TimeoutError { code: "timeout", message, reason, phase, after_secs: after.as_secs() }
+  }
+}
+
+pub struct Watchdog {
+  limits:   Limits,
+  deadline: Option<Instant>,
+}
+
+impl Watchdog {
+  pub fn start(limits: Limits) -> Self {
+    Watchdog { limits, deadline: limits.total.map(|t| Instant::now() + t) }
+  }
+
+  pub fn limits(&self) -> Limits {
+    self.limits
+  }
+
+  /// Next item from `rx` (`Ok(None)` once it closes), or the limit that fired first.
+  pub async fn recv<T>(&self, rx: &mut mpsc::UnboundedReceiver<T>) -> Result<Option<T>, Stall> {
+    let deadline = async {
+      match self.deadline {
+        Some(at) => tokio::time::sleep_until(at).await,
+        None => std::future::pending().await,
+      }
+    };
+    tokio::select! {
+      item = rx.recv() => Ok(item),
+      _ = tokio::time::sleep(self.limits.idle) => Err(Stall::Inactivity),
+      _ = deadline => Err(Stall::Deadline),
+    }
+  }
+}
+
+/// Manages the limits from the settings store; `cli_runner::timeouts` falls
+/// back to the defaults when this never ran.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+  app.manage(Timeouts::from_setting(settings::get(app, TIMEOUTS_SETTING).as_ref()));
+}
+
+/* ─────────── Tests ─────────── */
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rt() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread().enable_time().start_paused(true).build().unwrap()
+  }
+
+  #[test]
+  fn activity_resets_the_idle_timer_but_not_the_deadline() {
+    rt().block_on(async {
+      let limits = Limits { idle: Duration::from_secs(10), total: Some(Duration::from_secs(25)) };
+      let dog = Watchdog::start(limits);
+      let (tx, mut rx) = mpsc::unbounded_channel();
+      tokio::spawn(async move {
+        for i in 0..10 {
+          tokio::time::sleep(Duration::from_secs(8)).await;
+          if tx.send(i).is_err() { break; }
+        }
+      });
+
+      assert_eq!(dog.recv(&mut rx).await, Ok(Some(0)));
+      assert_eq!(dog.recv(&mut rx).await, Ok(Some(1)));
+      assert_eq!(dog.recv(&mut rx).await, Ok(Some(2)));
+      assert_eq!(dog.recv(&mut rx).await, Err(Stall::Deadline));
+    });
+  }
+
+  #[test]
+  fn silence_trips_the_idle_timer() {
+    rt().block_on(async {
+      let dog = Watchdog::start(Limits { idle: Duration::from_secs(5), total: None });
+      let (tx, mut rx) = mpsc::unbounded_channel::<()>();
+      assert_eq!(dog.recv(&mut rx).await, Err(Stall::Inactivity));
+      drop(tx);
+      assert_eq!(dog.recv(&mut rx).await, Ok(None));
+    });
+  }
+
+  #[test]
+  fn timeout_error_names_the_phase() {
+    let limits = Limits { idle: Duration::from_secs(120), total: Some(Duration::from_secs(600)) };
+    let idle = TimeoutError::new(Stall::Inactivity, Some(Phase::Compress), limits);
+    assert_eq!(idle.message, "tvault-core stalled during compress: no activity for 120s");
+    assert_eq!(idle.after_secs, 120);
+
+    let deadline = TimeoutError::new(Stall::Deadline, None, limits);
+    assert_eq!(deadline.message, "tvault-core timed out before reporting progress: not finished after 600s");
+    assert_eq!(serde_json::to_value(&deadline).unwrap()["reason"], "deadline");
+  }
+
+  #[test]
+  fn setting_overrides_only_the_limits_it_names() {
+    let t = Timeouts::from_setting(Some(&serde_json::json!({ "bulkIdleSecs": 600, "infoTotalSecs": 0, "graceSecs": "5" })));
+    assert_eq!(t.bulk.idle, Duration::from_secs(600));
+    assert_eq!(t.bulk.total, None);
+    assert_eq!(t.info.total, None);
+    assert_eq!(t.info.idle, Duration::from_secs(15));
+    // not a number: default kept
+    assert_eq!(t.grace, Duration::from_secs(3));
+    assert_eq!(Timeouts::from_setting(None).info.total, Some(Duration::from_secs(60)));
+  }
+}
@@ -0,0 +1,816 @@
+//! Integration tests for cli_runner against the scripted fake-tvault-core
+//! (tests/support/fake_core.rs, built by `cargo test` as an example).
+//! They pin the stub with `CorePin::sha256`, which only debug builds have.
+#![cfg(debug_assertions)]
+
+use std::{
+  path::PathBuf,
+  sync::{mpsc, Once},
+  time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tauri::{test::MockRuntime, App, AppHandle, Listener, Manager};
+use tauri_plugin_store::StoreExt;
+use tvault_client_lib::{
+  audit::AuditLog,
+  cli_runner::{cancel_operation, container_info_many, container_info_once, run_container_info, run_decrypt, run_encrypt, run_reseal},
+  info_cache::InfoCache,
+  key_file,
+  sidecar::CorePin,
+  signing::{decode_public, encode_public, KeyStore},
+  trust_store::TrustStore,
+  watchdog::{Limits, Timeouts},
+};
+
+/* ─────────── Harness ─────────── */
+
+fn fake_core() -> PathBuf {
+  // target/<profile>/deps/<test-binary> → target/<profile>/examples/fake-tvault-core
+  let exe = std::env::current_exe().unwrap();
+  let profile_dir = exe.parent().and_then(|p| p.parent()).unwrap();
+  let name = format!("fake-tvault-core{}", std::env::consts::EXE_SUFFIX);
+  let path = profile_dir.join("examples").join(name);
+  assert!(path.exists(), "{} is missing; run the whole suite with `cargo test`", path.display());
+  path
+}
+
+/// The fake core is found through `TVAULT_CORE_PATH` and trusted through its own checksum.
+fn builder() -> tauri::Builder<MockRuntime> {
+  static INIT: Once = Once::new();
+  INIT.call_once(|| std::env::set_var("TVAULT_CORE_PATH", fake_core()));
+  let digest: String = Sha256::digest(std::fs::read(fake_core()).unwrap()).iter().map(|b| format!("{b:02x}")).collect();
+  tauri::test::mock_builder().plugin(tauri_plugin_store::Builder::default().build()).manage(CorePin::sha256(&digest))
+}
+
+fn build(builder: tauri::Builder<MockRuntime>) -> App<MockRuntime> {
+  builder.build(tauri::test::mock_context(tauri::test::noop_assets())).unwrap()
+}
+
+fn mock_app() -> App<MockRuntime> {
+  build(builder())
+}
+
+/// Limits short enough to trip within a test.
+fn short_timeouts() -> Timeouts {
+  let limits = Limits { idle: Duration::from_millis(300), total: Some(Duration::from_millis(800)) };
+  Timeouts { info: limits, bulk: limits, grace: Duration::from_millis(200) }
+}
+
+/// Per-test scratch dir; the container path inside it selects the fake core script.
+struct Sandbox {
+  dir: PathBuf,
+}
+
+impl Sandbox {
+  fn new(name: &str) -> Self {
+    let dir = std::env::temp_dir().join(format!("tvault-cli-runner-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    Sandbox { dir }
+  }
+
+  fn container(&self) -> String {
+    self.dir.join("vault.tvlt").display().to_string()
+  }
+
+  fn script(&self, lines: &[&str]) {
+    std::fs::write(format!("{}.script", self.container()), lines.join("\n")).unwrap();
+  }
+
+  fn record(&self) -> Value {
+    let raw = std::fs::read_to_string(format!("{}.record.json", self.container())).expect("fake core was not run");
+    serde_json::from_str(&raw).unwrap()
+  }
+
+  fn argv(&self) -> Vec<String> {
+    serde_json::from_value(self.record()["argv"].clone()).unwrap()
+  }
+}
+
+impl Drop for Sandbox {
+  fn drop(&mut self) {
+    let _ = std::fs::remove_dir_all(&self.dir);
+  }
+}
+
+/// Subscribes to every `{prefix}-*` event before the operation starts.
+fn listen(app: &AppHandle<MockRuntime>, prefix: &str) -> mpsc::Receiver<(String, Value)> {
+  let (tx, rx) = mpsc::channel();
+  for suffix in ["queued", "stdout", "stderr", "progress", "progress-detail", "result", "error", "signer", "done"] {
+    let tx = tx.clone();
+    app.listen_any(format!("{prefix}-{suffix}"), move |e| {
+      let payload = serde_json::from_str(e.payload()).unwrap_or(Value::Null);
+      let _ = tx.send((suffix.to_string(), payload));
+    });
+  }
+  rx
+}
+
+/// Collects events until `{prefix}-done`, which cli_runner always emits last.
+fn wait_done(rx: &mpsc::Receiver<(String, Value)>) -> Vec<(String, Value)> {
+  let mut events = Vec::new();
+  loop {
+    let ev = rx.recv_timeout(Duration::from_secs(20)).expect("timed out waiting for done");
+    let done = ev.0 == "done";
+    events.push(ev);
+    if done {
+      return events;
+    }
+  }
+}
+
+fn payloads<'a>(events: &'a [(String, Value)], kind: &str) -> Vec<&'a Value> {
+  events.iter().filter(|(k, _)| k == kind).map(|(_, v)| v).collect()
+}
+
+fn args<T: serde::de::DeserializeOwned>(v: Value) -> T {
+  serde_json::from_value(v).unwrap()
+}
+
+/* ─────────── Sidecar ─────────── */
+
+#[test]
+fn rejected_core_override_is_an_error_not_a_fallback() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("override-mismatch");
+  let impostor = sb.dir.join("impostor-core");
+  std::fs::write(&impostor, "#!/bin/sh\n").unwrap();
+  handle.store("vault-settings.json").unwrap().set("corePath", json!(impostor.display().to_string()));
+
+  let err = tauri::async_runtime::block_on(container_info_once(handle, args(json!({ "path": sb.container() })))).unwrap_err();
+  assert_eq!(err, format!("tvault-core at {} (corePath setting) is rejected: checksum mismatch", impostor.display()));
+  assert!(!PathBuf::from(format!("{}.record.json", sb.container())).exists());
+}
+
+/* ─────────── Encrypt ─────────── */
+
+#[test]
+fn encrypt_streams_progress_and_result() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("encrypt-ok");
+  sb.script(&[
+    "progress 10",
+    "stderr warming up",
+    "progress 55",
+    "stdout not json",
+    r#"stdout {"master_token":"mt-1"}"#,
+    "progress 100",
+    "exit 0",
+  ]);
+
+  let rx = listen(&handle, "encrypt");
+  tauri::async_runtime::block_on(run_encrypt(handle.clone(), args(json!({
+    "container_path": sb.container(),
+    "folder_path": sb.dir.join("in").display().to_string(),
+    "compression_type": "zip",
+    "passphrase": "pw",
+    "token_type": "master",
+    "token_save_type": "stdout",
+    "integrity_provider": "none",
+  })))).unwrap();
+  let events = wait_done(&rx);
+
+  let progress: Vec<u64> = payloads(&events, "progress").iter().filter_map(|v| v.as_u64()).collect();
+  assert_eq!(progress, vec![10, 55, 100]);
+  assert_eq!(payloads(&events, "result"), vec![&json!({ "master_token": "mt-1" })]);
+  assert_eq!(payloads(&events, "stderr"), vec![&json!("warming up")]);
+  assert!(payloads(&events, "error").is_empty());
+  assert_eq!(events.last().unwrap(), &("done".to_string(), json!(true)));
+
+  let argv = sb.argv();
+  assert_eq!(&argv[..2], ["seal", "container"]);
+  assert!(argv.contains(&format!("-new-path={}", sb.container())));
+  assert!(argv.contains(&"-passphrase=pw".to_string()));
+  assert_eq!(sb.record()["stdin"], json!(""));
+}
+
+#[test]
+fn structured_progress_is_reported_as_detail() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("progress-detail");
+  sb.script(&[
+    r#"progress {"phase":"compress","bytes_done":0,"bytes_total":400,"files_done":0,"files_total":2,"current_file":"a.txt"}"#,
+    r#"progress {"phase":"encrypt","bytes_done":100,"bytes_total":400}"#,
+    r#"progress {"phase":"encrypt","bytes_done":200,"bytes_total":400}"#,
+    r#"progress {"phase":"encrypt","bytes_done":300,"bytes_total":400}"#,
+    r#"stdout {"master_token":"mt-1"}"#,
+  ]);
+
+  let rx = listen(&handle, "encrypt");
+  tauri::async_runtime::block_on(run_encrypt(handle.clone(), args(json!({
+    "container_path": sb.container(),
+    "folder_path": "in",
+    "compression_type": "zip",
+    "passphrase": "pw",
+    "token_type": "master",
+    "token_save_type": "stdout",
+    "integrity_provider": "none",
+  })))).unwrap();
+  let events = wait_done(&rx);
+
+  // legacy listeners keep getting the percentage
+  let progress: Vec<u64> = payloads(&events, "progress").iter().filter_map(|v| v.as_u64()).collect();
+  assert_eq!(progress, vec![25, 50, 75]);
+
+  // back-to-back samples are throttled, but phase changes and the final state get through
+  let details = payloads(&events, "progress-detail");
+  assert_eq!(details[0]["phase"], json!("compress"));
+  assert_eq!(details[0]["current_file"], json!("a.txt"));
+  assert_eq!(details[0]["files_total"], json!(2));
+  assert_eq!(details[1]["phase"], json!("encrypt"));
+  assert_eq!(details.last().unwrap()["bytes_done"], json!(300));
+  assert_eq!(details.last().unwrap()["percent"], json!(75));
+}
+
+#[test]
+fn signed_encrypt_hands_the_unlocked_key_to_the_core_on_stdin() {
+  let keys = KeyStore::open(None);
+  let key = keys.generate("team", "sign-pass").unwrap();
+  let app = build(builder().manage(keys));
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("encrypt-signed");
+  sb.script(&[r#"stdout {"master_token":"mt-1"}"#]);
+
+  let rx = listen(&handle, "encrypt");
+  tauri::async_runtime::block_on(run_encrypt(handle.clone(), args(json!({
+    "container_path": sb.container(),
+    "folder_path": sb.dir.join("in").display().to_string(),
+    "compression_type": "zip",
+    "passphrase": "pw",
+    "token_type": "master",
+    "token_save_type": "stdout",
+    "integrity_provider": "ed25519",
+    "signing_key_id": key.id,
+    "signing_passphrase": "sign-pass",
+  })))).unwrap();
+  wait_done(&rx);
+
+  let argv = sb.argv();
+  assert!(argv.ends_with(&["integrity-provider", "-type=ed25519", "-private-key=stdin", "log-writer", "-type=stdout", "-format=json"].map(String::from)));
+  let secret = sb.record()["stdin"].as_str().unwrap().to_string();
+  assert!(!argv.iter().any(|a| a.contains(&secret)));
+  let seed = B64.decode(secret.strip_prefix("ed25519:").unwrap()).unwrap();
+  let signing = ed25519_dalek::SigningKey::from_bytes(&seed.try_into().unwrap());
+  assert_eq!(signing.verifying_key(), decode_public(&key.public_key).unwrap());
+
+  let wrong = tauri::async_runtime::block_on(run_encrypt(handle, args(json!({
+    "container_path": sb.container(),
+    "folder_path": sb.dir.join("in").display().to_string(),
+    "compression_type": "zip",
+    "passphrase": "pw",
+    "token_type": "master",
+    "token_save_type": "stdout",
+    "integrity_provider": "ed25519",
+    "signing_key_id": key.id,
+    "signing_passphrase": "nope",
+  }))));
+  assert_eq!(wrong, Err("wrong passphrase for signing key".to_string()));
+}
+
+#[test]
+fn encrypt_rejects_invalid_args_without_spawning() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("encrypt-invalid");
+
+  let err = tauri::async_runtime::block_on(run_encrypt(handle, args(json!({
+    "container_path": sb.container(),
+    "folder_path": "in",
+    "compression_type": "zip",
+    "passphrase": "pw",
+    "token_type": "none",
+    "token_save_type": "stdout",
+    "integrity_provider": "hmac",
+  })))).unwrap_err();
+
+  assert!(err.contains("additional_password"));
+  assert!(!PathBuf::from(format!("{}.record.json", sb.container())).exists());
+}
+
+/* ─────────── Decrypt ─────────── */
+
+#[test]
+fn decrypt_reports_core_error_and_stderr_on_failure() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("decrypt-fail");
+  sb.script(&[
+    "progress 5",
+    r#"stdout {"code":3,"message":"invalid token"}"#,
+    "stderr panic: boom",
+    "exit 2",
+  ]);
+
+  let rx = listen(&handle, "decrypt");
+  tauri::async_runtime::block_on(run_decrypt(handle.clone(), args(json!({
+    "container_path": sb.container(),
+    "folder_path": sb.dir.join("out").display().to_string(),
+    "token": "pw",
+  })))).unwrap();
+  let events = wait_done(&rx);
+
+  assert_eq!(
+    payloads(&events, "error"),
+    vec![&json!({ "code": 3, "message": "invalid token" }), &json!("panic: boom\n")],
+  );
+  assert!(payloads(&events, "result").is_empty());
+  assert_eq!(events.last().unwrap(), &("done".to_string(), json!(false)));
+  assert!(sb.dir.join("out").is_dir());
+
+  let argv = sb.argv();
+  assert_eq!(&argv[..2], ["unseal", "container"]);
+  assert!(argv.contains(&"-passphrase=pw".to_string()));
+  assert!(argv.contains(&"-flag=pw".to_string()));
+}
+
+#[test]
+fn decrypt_folds_key_files_into_the_passphrase() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("decrypt-keyfile");
+  sb.script(&[r#"stdout {"ok":true}"#]);
+  let key = sb.dir.join("photo.jpg");
+  std::fs::write(&key, b"not really a photo").unwrap();
+  let key = key.display().to_string();
+
+  let rx = listen(&handle, "decrypt");
+  tauri::async_runtime::block_on(run_decrypt(handle.clone(), args(json!({
+    "container_path": sb.container(),
+    "folder_path": sb.dir.join("out").display().to_string(),
+    "token": "pw",
+    "key_files": [key.clone()],
+  })))).unwrap();
+  wait_done(&rx);
+
+  let combined = key_file::combine("pw", &[key]).unwrap();
+  let argv = sb.argv();
+  assert!(argv.contains(&format!("-passphrase={combined}")));
+  assert!(!argv.contains(&"-passphrase=pw".to_string()));
+}
+
+/* ─────────── Reseal ─────────── */
+
+#[test]
+fn reseal_joins_shares_and_reports_result() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("reseal-shares");
+  sb.script(&[r#"stdout {"ok":true}"#]);
+
+  let rx = listen(&handle, "reseal");
+  tauri::async_runtime::block_on(run_reseal(handle.clone(), args(json!({
+    "current_path": sb.container(),
+    "folder_path": sb.dir.join("mnt").display().to_string(),
+    "token_type": "share",
+    "shares": ["s1", "s2", "s3"],
+  })))).unwrap();
+  let events = wait_done(&rx);
+
+  assert_eq!(payloads(&events, "result"), vec![&json!({ "ok": true })]);
+  assert_eq!(events.last().unwrap(), &("done".to_string(), json!(true)));
+  assert!(sb.argv().contains(&"-flag=s1|s2|s3".to_string()));
+}
+
+#[test]
+fn reseal_opens_with_the_current_key_files_and_records_the_new_ones_on_success() {
+  let app = build(builder().manage(key_file::KeyFileStore::open(None)));
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("reseal-keyfiles");
+  let (old, new) = (sb.dir.join("old.key"), sb.dir.join("new.key"));
+  std::fs::write(&old, b"old key").unwrap();
+  std::fs::write(&new, b"new key").unwrap();
+  let (old, new) = (old.display().to_string(), new.display().to_string());
+  let reseal = |script: &[&str]| {
+    sb.script(script);
+    let rx = listen(&handle, "reseal");
+    tauri::async_runtime::block_on(run_reseal(handle.clone(), args(json!({
+      "current_path": sb.container(),
+      "folder_path": sb.dir.join("mnt").display().to_string(),
+      "passphrase": "pw",
+      "token_type": "none",
+      "current_key_files": [old.clone()],
+      "key_files": [new.clone()],
+    })))).unwrap();
+    wait_done(&rx)
+  };
+  let store = || app.state::<key_file::KeyFileStore>();
+
+  reseal(&["exit 1"]);
+  assert!(!store().check(&sb.container(), std::slice::from_ref(&new)).recorded);
+
+  let events = reseal(&[r#"stdout {"ok":true}"#]);
+  assert_eq!(events.last().unwrap(), &("done".to_string(), json!(true)));
+  let argv = sb.argv();
+  assert!(argv.contains(&format!("-flag={}", key_file::combine("pw", &[old]).unwrap())));
+  assert!(argv.contains(&format!("-passphrase={}", key_file::combine("pw", std::slice::from_ref(&new)).unwrap())));
+  assert!(store().check(&sb.container(), &[new]).warning.is_none());
+}
+
+/* ─────────── Container Info ─────────── */
+
+#[test]
+fn container_info_once_wraps_data_with_path() {
+  let app = mock_app();
+  let sb = Sandbox::new("info-once-ok");
+  sb.script(&["stdout log line", r#"stdout {"name":"vault","version":1}"#]);
+
+  let res = tauri::async_runtime::block_on(container_info_once(app.handle().clone(), args(json!({ "path": sb.container() })))).unwrap();
+
+  assert_eq!(res, json!({ "path": sb.container(), "data": { "name": "vault", "version": 1 } }));
+  assert_eq!(&sb.argv()[..2], ["container", "info"]);
+}
+
+#[test]
+fn container_info_once_wraps_core_error_with_path() {
+  let app = mock_app();
+  let sb = Sandbox::new("info-once-err");
+  sb.script(&[r#"stdout {"code":7,"message":"not a container"}"#, "exit 1"]);
+
+  let res = tauri::async_runtime::block_on(container_info_once(app.handle().clone(), args(json!({ "path": sb.container() })))).unwrap();
+
+  assert_eq!(res, json!({ "path": sb.container(), "error": { "code": 7, "message": "not a container" } }));
+}
+
+#[test]
+fn container_info_once_rejects_garbage_output() {
+  let app = mock_app();
+  let sb = Sandbox::new("info-once-garbage");
+  sb.script(&["stdout nothing useful"]);
+
+  let res = tauri::async_runtime::block_on(container_info_once(app.handle().clone(), args(json!({ "path": sb.container() }))));
+
+  assert_eq!(res, Err("invalid info output".to_string()));
+}
+
+#[test]
+fn container_info_once_keeps_stderr_with_the_error() {
+  let app = mock_app();
+  let sb = Sandbox::new("info-once-stderr");
+  sb.script(&["stderr open vault.tvlt: permission denied", r#"stdout {"code":67,"message":"cannot open container"}"#, "exit 1"]);
+
+  let res = tauri::async_runtime::block_on(container_info_once(app.handle().clone(), args(json!({ "path": sb.container() })))).unwrap();
+
+  assert_eq!(res, json!({
+    "path": sb.container(),
+    "error": { "code": 67, "message": "cannot open container" },
+    "stderr": "open vault.tvlt: permission denied",
+  }));
+}
+
+#[test]
+fn container_info_many_returns_results_in_order() {
+  let app = mock_app();
+  let boxes: Vec<Sandbox> = (0..5).map(|i| Sandbox::new(&format!("info-many-{i}"))).collect();
+  for (i, sb) in boxes.iter().enumerate() {
+    match i {
+      3 => sb.script(&["stderr truncated header", "stdout nothing useful", "exit 1"]),
+      // later paths finish first, so ordering comes from the batch, not completion
+      _ => sb.script(&[&format!("sleep {}", 200 - i * 40), &format!(r#"stdout {{"name":"vault-{i}"}}"#)]),
+    }
+  }
+  let paths: Vec<String> = boxes.iter().map(Sandbox::container).collect();
+
+  let res = tauri::async_runtime::block_on(container_info_many(app.handle().clone(), paths.clone())).unwrap();
+
+  assert_eq!(res.len(), 5);
+  for (i, (entry, path)) in res.iter().zip(&paths).enumerate() {
+    assert_eq!(entry["path"], json!(path));
+    if i == 3 {
+      assert_eq!(entry["error"]["message"], json!("invalid info output: truncated header"));
+    } else {
+      assert_eq!(entry["data"], json!({ "name": format!("vault-{i}") }));
+    }
+  }
+}
+
+#[test]
+fn run_container_info_emits_result_with_path() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("info-events");
+  sb.script(&[r#"stdout {"name":"vault"}"#]);
+
+  let rx = listen(&handle, "info");
+  tauri::async_runtime::block_on(run_container_info(handle.clone(), args(json!({ "path": sb.container() })))).unwrap();
+  let events = wait_done(&rx);
+
+  assert_eq!(payloads(&events, "result"), vec![&json!({ "path": sb.container(), "data": { "name": "vault" } })]);
+}
+
+#[test]
+fn container_info_is_cached_until_a_reseal_rewrites_the_container() {
+  let app = build(builder().manage(InfoCache::open(None, false)));
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("info-cache");
+  std::fs::write(sb.container(), "sealed").unwrap();
+  sb.script(&[r#"stdout {"name":"vault"}"#]);
+  let record = format!("{}.record.json", sb.container());
+  let info = || tauri::async_runtime::block_on(container_info_once(handle.clone(), args(json!({ "path": sb.container() })))).unwrap();
+
+  let first = info();
+  std::fs::remove_file(&record).unwrap();
+  assert_eq!(info(), first);
+  assert!(!std::path::Path::new(&record).exists(), "second info should not reach the core");
+
+  let rx = listen(&handle, "reseal");
+  tauri::async_runtime::block_on(run_reseal(handle.clone(), args(json!({
+    "current_path": sb.container(),
+    "folder_path": sb.dir.join("mnt").display().to_string(),
+  })))).unwrap();
+  wait_done(&rx);
+
+  std::fs::remove_file(&record).unwrap();
+  info();
+  assert!(std::path::Path::new(&record).exists(), "info after a reseal should reach the core");
+}
+
+/* ─────────── Trust ─────────── */
+
+fn public_key(seed: u8) -> String {
+  encode_public(&ed25519_dalek::SigningKey::from_bytes(&[seed; 32]).verifying_key())
+}
+
+#[test]
+fn container_info_never_calls_a_signer_trusted() {
+  let alice = public_key(1);
+  let trust = TrustStore::open(None);
+  trust.trust("Alice", &alice).unwrap();
+  let app = build(builder().manage(trust));
+  let sb = Sandbox::new("trust-info");
+  sb.script(&[r#"stdout {"integrity_provider_type":"ed25519"}"#]);
+
+  let res = tauri::async_runtime::block_on(container_info_once(app.handle().clone(), args(json!({ "path": sb.container() })))).unwrap();
+
+  assert_eq!(res["signer"]["status"], json!("unverified"));
+  assert_eq!(res["signer"]["label"], json!("signed (unverified)"));
+}
+
+#[test]
+fn unseal_policy_needs_a_trusted_key_and_announces_the_checked_signer() {
+  let (alice, mallory) = (public_key(1), public_key(2));
+  let trust = TrustStore::open(None);
+  trust.trust("Alice", &alice).unwrap();
+  let app = build(builder().manage(trust));
+  let handle = app.handle().clone();
+  handle.store("vault-settings.json").unwrap().set("requireTrustedSigner", json!(true));
+  let unseal = |sb: &Sandbox, key: Option<&str>| {
+    tauri::async_runtime::block_on(run_decrypt(handle.clone(), args(json!({
+      "container_path": sb.container(),
+      "folder_path": sb.dir.join("out").display().to_string(),
+      "token": "pw",
+      "public_key": key,
+    }))))
+  };
+
+  let unsigned = Sandbox::new("trust-unsigned");
+  unsigned.script(&[r#"stdout {"integrity_provider_type":"hmac"}"#]);
+  assert_eq!(unseal(&unsigned, Some(&alice)), Err("refusing to unseal: container is not signed".to_string()));
+
+  let signed = Sandbox::new("trust-signed");
+  signed.script(&[r#"stdout {"integrity_provider_type":"ed25519"}"#]);
+  assert_eq!(unseal(&signed, None), Err("refusing to unseal: pick a trusted signer key".to_string()));
+  assert_eq!(unseal(&signed, Some(&mallory)), Err("refusing to unseal: the signer key is not trusted".to_string()));
+  assert_eq!(&signed.argv()[..2], ["container", "info"]);
+  assert!(!signed.dir.join("out").exists());
+
+  let rx = listen(&handle, "decrypt");
+  unseal(&signed, Some(&alice)).unwrap();
+  let events = wait_done(&rx);
+  let argv = signed.argv();
+  assert_eq!(&argv[..2], ["unseal", "container"]);
+  assert!(argv.contains(&format!("-public-key={alice}")));
+  let signer = payloads(&events, "signer");
+  assert_eq!(signer.len(), 1);
+  assert_eq!(signer[0]["status"], json!("trusted"));
+  assert_eq!(signer[0]["label"], json!("signed by Alice (trusted)"));
+}
+
+#[test]
+fn reseal_policy_refuses_to_write_unsigned_containers() {
+  let app = build(builder().manage(TrustStore::open(None)));
+  let handle = app.handle().clone();
+  handle.store("vault-settings.json").unwrap().set("requireTrustedSigner", json!(true));
+  let sb = Sandbox::new("trust-reseal");
+
+  let res = tauri::async_runtime::block_on(run_reseal(handle.clone(), args(json!({
+    "current_path": sb.container(),
+    "folder_path": sb.dir.join("plain").display().to_string(),
+    "passphrase": "pw",
+    "integrity_provider": "none",
+  }))));
+
+  assert_eq!(res, Err("refusing to reseal: pick a trusted signer key".to_string()));
+  assert!(!std::path::Path::new(&format!("{}.record.json", sb.container())).exists());
+}
+
+#[test]
+fn refusals_before_the_core_runs_are_audited() {
+  let sb = Sandbox::new("audit-refused");
+  let log = sb.dir.join("audit.jsonl");
+  let app = build(builder().manage(AuditLog::open(Some(log.clone()), Some(sb.dir.join("audit-head.json")))));
+  let handle = app.handle().clone();
+  handle.store("vault-settings.json").unwrap().set("minPasswordScore", json!(4));
+
+  let res = tauri::async_runtime::block_on(run_encrypt(handle.clone(), args(json!({
+    "container_path": sb.container(),
+    "folder_path": sb.dir.join("plain").display().to_string(),
+    "compression_type": "zip",
+    "passphrase": "password",
+    "token_type": "none",
+    "token_save_type": "stdout",
+    "integrity_provider": "none",
+  }))));
+
+  assert!(res.unwrap_err().starts_with("passphrase is too weak"));
+  let entry: Value = serde_json::from_str(std::fs::read_to_string(&log).unwrap().lines().last().unwrap()).unwrap();
+  assert_eq!((entry["op"].as_str(), entry["outcome"].as_str()), (Some("seal"), Some("refused")));
+  assert_eq!(entry["container"], json!(sb.container()));
+  assert!(app.state::<AuditLog>().verify().intact);
+}
+
+#[test]
+fn reseal_holds_only_newly_chosen_passwords_to_the_policy() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  handle.store("vault-settings.json").unwrap().set("minPasswordScore", json!(3));
+  let sb = Sandbox::new("policy-reseal");
+  sb.script(&["exit 0"]);
+  let reseal = |extra: Value| {
+    let mut a = json!({
+      "current_path": sb.container(),
+      "folder_path": sb.dir.join("plain").display().to_string(),
+      "passphrase": "pw",
+      "token_type": "none",
+      "integrity_provider": "hmac",
+      "current_integrity_password": "password",
+    });
+    a.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+    tauri::async_runtime::block_on(run_reseal(handle.clone(), args(a)))
+  };
+
+  // the passphrase and integrity password it was opened with are kept as they are
+  assert!(reseal(json!({ "new_integrity_password": "password" })).is_ok());
+  let weak = reseal(json!({ "new_integrity_password": "qwerty123" })).unwrap_err();
+  assert!(weak.starts_with("integrity password is too weak"), "{weak}");
+  let weak = reseal(json!({ "token_type": "master", "master_token": "tok" })).unwrap_err();
+  assert!(weak.starts_with("passphrase is too weak"), "{weak}");
+}
+
+/* ─────────── Scheduling ─────────── */
+
+#[test]
+fn operations_on_the_same_container_do_not_overlap() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("exclusive");
+  sb.script(&["sleep 300", r#"stdout {"name":"vault"}"#]);
+
+  let reseal_rx = listen(&handle, "reseal");
+  let info_rx = listen(&handle, "info");
+  tauri::async_runtime::block_on(run_reseal(handle.clone(), args(json!({
+    "current_path": sb.container(),
+    "folder_path": sb.dir.join("mnt").display().to_string(),
+  })))).unwrap();
+  let info = tauri::async_runtime::spawn(run_container_info(handle.clone(), args(json!({ "path": sb.container() }))));
+
+  let info_events = wait_done(&info_rx);
+  tauri::async_runtime::block_on(info).unwrap().unwrap();
+  // the reseal held the container, so info waited in the queue until it was done
+  let queued = payloads(&info_events, "queued");
+  assert!(!queued.is_empty());
+  assert_eq!(queued[0]["path"], json!(sb.container()));
+  assert!(reseal_rx.try_iter().any(|(kind, _)| kind == "done"));
+  assert_eq!(payloads(&info_events, "result"), vec![&json!({ "path": sb.container(), "data": { "name": "vault" } })]);
+}
+
+#[test]
+fn cancel_operation_removes_a_queued_operation() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("cancel-queued");
+  sb.script(&["sleep 500"]);
+
+  let reseal_rx = listen(&handle, "reseal");
+  let decrypt_rx = listen(&handle, "decrypt");
+  tauri::async_runtime::block_on(run_reseal(handle.clone(), args(json!({
+    "current_path": sb.container(),
+    "folder_path": sb.dir.join("mnt").display().to_string(),
+  })))).unwrap();
+  let decrypt = tauri::async_runtime::spawn(run_decrypt(handle.clone(), args(json!({
+    "container_path": sb.container(),
+    "folder_path": sb.dir.join("out").display().to_string(),
+    "token": "pw",
+  }))));
+  let (kind, queued) = decrypt_rx.recv_timeout(Duration::from_secs(20)).unwrap();
+  assert_eq!(kind, "queued");
+
+  assert!(tauri::async_runtime::block_on(cancel_operation(queued["seq"].as_u64().unwrap())).unwrap());
+  tauri::async_runtime::block_on(decrypt).unwrap().unwrap();
+  let events = wait_done(&decrypt_rx);
+  assert_eq!(events.last().unwrap(), &("done".to_string(), json!(false)));
+  assert!(payloads(&events, "stdout").is_empty());
+  // the decrypt never reached the core, so the reseal's invocation is the last one recorded
+  wait_done(&reseal_rx);
+  assert_eq!(sb.argv()[0], "reseal");
+}
+
+/* ─────────── Cancellation ─────────── */
+
+#[test]
+fn cancel_operation_kills_running_process() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("cancel-process");
+  sb.script(&["progress 1", "sleep 10000", r#"stdout {"ok":true}"#]);
+
+  let rx = listen(&handle, "reseal");
+  let seq = tauri::async_runtime::block_on(run_reseal(handle.clone(), args(json!({
+    "current_path": sb.container(),
+    "folder_path": sb.dir.join("mnt").display().to_string(),
+  })))).unwrap();
+  assert_eq!(rx.recv_timeout(Duration::from_secs(20)).unwrap(), ("stdout".to_string(), json!("PROGRESS 1")));
+
+  assert!(tauri::async_runtime::block_on(cancel_operation(seq)).unwrap());
+  let events = wait_done(&rx);
+
+  assert!(payloads(&events, "result").is_empty());
+  assert_eq!(events.last().unwrap(), &("done".to_string(), json!(false)));
+  assert!(!tauri::async_runtime::block_on(cancel_operation(seq)).unwrap());
+}
+
+#[test]
+fn cancel_operation_stops_only_the_given_operation_of_a_kind() {
+  let app = mock_app();
+  let handle = app.handle().clone();
+  let slow = Sandbox::new("cancel-slow");
+  slow.script(&["sleep 10000", r#"stdout {"ok":true}"#]);
+  let quick = Sandbox::new("cancel-quick");
+  quick.script(&["sleep 300", r#"stdout {"ok":true}"#]);
+
+  let rx = listen(&handle, "reseal");
+  let reseal = |sb: &Sandbox| {
+    tauri::async_runtime::block_on(run_reseal(handle.clone(), args(json!({
+      "current_path": sb.container(),
+      "folder_path": sb.dir.join("mnt").display().to_string(),
+    })))).unwrap()
+  };
+  let slow_seq = reseal(&slow);
+  let quick_seq = reseal(&quick);
+  assert_ne!(slow_seq, quick_seq);
+
+  assert!(tauri::async_runtime::block_on(cancel_operation(slow_seq)).unwrap());
+  let mut done = vec![wait_done(&rx).last().unwrap().1.clone(), wait_done(&rx).last().unwrap().1.clone()];
+  done.sort_by_key(|ok| ok.as_bool());
+  assert_eq!(done, vec![json!(false), json!(true)]);
+}
+
+/* ─────────── Timeouts ─────────── */
+
+#[test]
+fn stalled_process_is_stopped_with_a_timeout_naming_the_phase() {
+  let app = build(builder().manage(short_timeouts()));
+  let handle = app.handle().clone();
+  let sb = Sandbox::new("stall-process");
+  sb.script(&[r#"progress {"phase":"compress","files_done":1,"files_total":3}"#, "hang"]);
+
+  let rx = listen(&handle, "encrypt");
+  tauri::async_runtime::block_on(run_encrypt(handle.clone(), args(json!({
+    "container_path": sb.container(),
+    "folder_path": "in",
+    "compression_type": "zip",
+    "passphrase": "pw",
+    "token_type": "master",
+    "token_save_type": "stdout",
+    "integrity_provider": "none",
+  })))).unwrap();
+  let events = wait_done(&rx);
+
+  let errors = payloads(&events, "error");
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0]["code"], json!("timeout"));
+  assert_eq!(errors[0]["reason"], json!("inactivity"));
+  assert_eq!(errors[0]["phase"], json!("compress"));
+  assert_eq!(events.last().unwrap(), &("done".to_string(), json!(false)));
+}
+
+#[test]
+fn chatty_info_still_hits_the_deadline() {
+  let app = build(builder().manage(short_timeouts()));
+  let sb = Sandbox::new("deadline-info");
+  let mut script = Vec::new();
+  for _ in 0..30 {
+    script.extend(["stderr still reading", "sleep 100"]);
+  }
+  sb.script(&script);
+
+  let res = tauri::async_runtime::block_on(container_info_once(app.handle().clone(), args(json!({ "path": sb.container() })))).unwrap();
+  assert_eq!(res["path"], json!(sb.container()));
+  assert_eq!(res["error"]["code"], json!("timeout"));
+  assert_eq!(res["error"]["reason"], json!("deadline"));
+}
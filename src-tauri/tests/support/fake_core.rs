@@ -0,0 +1,68 @@
+//! fake_core.rs — scripted stand-in for tvault-core used by the cli_runner tests
+//!
+//! The container path in argv (`-current-path=`, `-new-path=` or `-path=`)
+//! selects the script: `<path>.script` is replayed line by line and argv plus
+//! stdin are recorded into `<path>.record.json`.
+//!
+//! Script lines:
+//!   progress <n>    prints `PROGRESS <n>`; `<n>` may also be a progress JSON object
+//!   stdout <text>   prints text to stdout
+//!   stderr <text>   prints text to stderr
+//!   sleep <ms>      pauses
+//!   exit <code>     exits with the code (default 0)
+//!   hang            blocks forever
+
+use std::{
+  fs,
+  io::{Read, Write},
+  thread,
+  time::Duration,
+};
+
+use serde_json::json;
+
+fn main() {
+  let argv: Vec<String> = std::env::args().skip(1).collect();
+  let mut stdin = String::new();
+  let _ = std::io::stdin().read_to_string(&mut stdin);
+  let Some(target) = prepare(&argv, &stdin) else {
+    eprintln!("fake-tvault-core: no container path in argv");
+    std::process::exit(64);
+  };
+
+  let code = replay(&target);
+  let _ = std::io::stdout().flush();
+  std::process::exit(code);
+}
+
+/* ─────────── Script Replay ─────────── */
+
+/// Records the invocation and returns the container path that names the script.
+fn prepare(argv: &[String], stdin: &str) -> Option<String> {
+  let target = ["-current-path=", "-new-path=", "-path="]
+    .iter()
+    .find_map(|flag| argv.iter().find_map(|a| a.strip_prefix(flag)))?
+    .to_string();
+  let record = json!({ "argv": argv, "stdin": stdin });
+  fs::write(format!("{target}.record.json"), record.to_string()).expect("write record");
+  Some(target)
+}
+
+/// Replays `<target>.script`; returns the exit code.
+fn replay(target: &str) -> i32 {
+  let script = fs::read_to_string(format!("{target}.script")).unwrap_or_default();
+  for line in script.lines() {
+    let (cmd, rest) = line.split_once(' ').unwrap_or((line, ""));
+    match cmd {
+      "progress" => println!("PROGRESS {rest}"),
+      "stdout" => println!("{rest}"),
+      "stderr" => eprintln!("{rest}"),
+      "sleep" => thread::sleep(Duration::from_millis(rest.parse().unwrap_or(0))),
+      "hang" => loop { thread::sleep(Duration::from_secs(60)); },
+      "exit" => return rest.parse().unwrap_or(0),
+      _ => {}
+    }
+    let _ = std::io::stdout().flush();
+  }
+  0
+}
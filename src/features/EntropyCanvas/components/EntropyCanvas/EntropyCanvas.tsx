@@ -1,14 +1,30 @@
 import { invoke } from "@tauri-apps/api/core";
 import { useEffect, useRef, useState } from "react";
 import { useIntl } from "react-intl";
+import { toast } from "react-toastify";
+import { devError, extractErrorMessage } from "utils";
 
 const TARGET_BITS = 512;
 const BATCH_SIZE = 48;
 
-const EntropyCanvas = ({ onReady }: { onReady: () => void }) => {
+/** Reply to `entropy_batch`; the passphrase comes once the pool is full. */
+interface EntropyStatus {
+	bits: number;
+	target: number;
+	passphrase: string | null;
+	/** Set when the batch looked scripted and earned nothing. */
+	warning: "repetition" | "proportion" | "duplicate_batch" | null;
+}
+
+const EntropyCanvas = ({
+	onReady,
+}: {
+	onReady: (passphrase: string) => void;
+}) => {
 	const { formatMessage } = useIntl();
 	const canvasRef = useRef<HTMLCanvasElement>(null);
 	const [bits, setBits] = useState(0);
+	const [synthetic, setSynthetic] = useState(false);
 	const buffer = useRef<number[]>([]);
 
 	/* ─────────────────────  Mouse-tracking  ───────────────────── */
@@ -20,6 +36,9 @@ const EntropyCanvas = ({ onReady }: { onReady: () => void }) => {
 		ctx.lineWidth = 2;
 		ctx.strokeStyle = "#3361D8";
 		let last: [number, number] | null = null;
+		/* every mount collects into its own backend session */
+		let session: Promise<string> = invoke("start_entropy_session");
+		let finished = false;
 
 		const onMove = (e: MouseEvent) => {
 			if (!canvasRef.current) return;
@@ -40,18 +59,50 @@ const EntropyCanvas = ({ onReady }: { onReady: () => void }) => {
 		};
 
 		const flushBatch = async () => {
-			const totalBits: number = await invoke("entropy_batch", {
-				bytes: [...buffer.current],
-			});
+			if (finished) return;
+			const bytes = [...buffer.current];
 			buffer.current = [];
-			setBits(totalBits);
-			if (totalBits >= TARGET_BITS) onReady();
+			const current = session;
+			let status: EntropyStatus;
+			try {
+				status = await invoke<EntropyStatus>("entropy_batch", {
+					session: await current,
+					bytes,
+				});
+			} catch (e) {
+				const message = extractErrorMessage(e);
+				/* the backend dropped the pool; what was gathered is gone */
+				if (
+					!finished &&
+					current === session &&
+					message.includes("entropy session expired")
+				) {
+					session = invoke("start_entropy_session");
+					setBits(0);
+					toast.warn(formatMessage({ id: "entropy.sessionExpired" }));
+				} else {
+					devError("entropy batch failed:", message);
+				}
+				return;
+			}
+			if (finished) return;
+			setBits(status.bits);
+			setSynthetic(status.warning !== null);
+			if (status.passphrase) {
+				finished = true;
+				onReady(status.passphrase);
+			}
 		};
 
-		canvasRef.current?.addEventListener("mousemove", onMove);
-		return () =>
-			canvasRef.current?.removeEventListener("mousemove", onMove);
-	}, [onReady]);
+		const canvas = canvasRef.current;
+		canvas?.addEventListener("mousemove", onMove);
+		return () => {
+			canvas?.removeEventListener("mousemove", onMove);
+			if (!finished) {
+				session.then(id => invoke("end_entropy_session", { session: id }));
+			}
+		};
+	}, [onReady, formatMessage]);
 
 	return (
 		<div className="flex flex-col items-center gap-[20px]">
@@ -73,6 +124,11 @@ const EntropyCanvas = ({ onReady }: { onReady: () => void }) => {
 					{ count: bits, total: TARGET_BITS },
 				)}
 			</p>
+			{synthetic && (
+				<p className="text-red-400 text-sm text-center w-[400px]">
+					{formatMessage({ id: "entropy.synthetic" })}
+				</p>
+			)}
 		</div>
 	);
 };
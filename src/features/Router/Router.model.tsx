@@ -23,6 +23,7 @@ import {
 	VaultShamirFileStep,
 	VaultShamirMethodStep,
 	VaultShamirStep,
+	VaultSigningKeyStep,
 	VaultSplitConfigStep,
 	VaultSplitToggleStep,
 	VaultSummaryStep,
@@ -73,6 +74,10 @@ export const routes: NonIndexRouteObject[] = [
 				path: RouteTypes.VaultCreateIntegrityPassword,
 				element: <VaultIntegrityPasswordStep />,
 			},
+			{
+				path: RouteTypes.VaultCreateSigningKey,
+				element: <VaultSigningKeyStep />,
+			},
 			{
 				path: RouteTypes.VaultCreateOutput,
 				element: <VaultOutputStep />,
@@ -7,9 +7,23 @@ import { RouteTypes } from "interfaces";
 import { appChangeLocale } from "features/App/state/App.actions";
 import { selectAppLocale } from "features/App/state/App.selectors";
 import { LocalizationTypes } from "features/Localization/Localization.model";
-import { useUpdater } from "features/Settings/hooks";
+import { SettingsDiagnosticsReview } from "features/Settings/components/SettingsDiagnosticsReview";
+import { SettingsSigningKeys } from "features/Settings/components/SettingsSigningKeys";
+import { SettingsTrustedKeys } from "features/Settings/components/SettingsTrustedKeys";
+import {
+	useAuditLog,
+	useDiagnostics,
+	usePasswordPolicy,
+	useUpdater,
+} from "features/Settings/hooks";
 import { useAppDispatch } from "features/Store";
-import { UIButton, UIInput, UISectionHeading, UISelect } from "features/UI";
+import {
+	UIButton,
+	UICheckbox,
+	UIInput,
+	UISectionHeading,
+	UISelect,
+} from "features/UI";
 import {
 	vaultChangeContainersPath,
 	vaultScanContainersDirectory,
@@ -40,11 +54,20 @@ const Settings = () => {
 		installUpdate,
 	} = useUpdater();
 
+	const diagnostics = useDiagnostics();
+	const audit = useAuditLog();
+	const passwordPolicy = usePasswordPolicy();
+
 	const languageOptions = [
 		{ value: LocalizationTypes.Russian, label: "Русский" },
 		{ value: LocalizationTypes.English, label: "English" },
 	];
 
+	const minScoreOptions = [0, 1, 2, 3, 4].map(score => ({
+		value: String(score),
+		label: formatMessage({ id: `settings.minPasswordScore.${score}` }),
+	}));
+
 	useEffect(() => {
 		initializeVersion();
 	}, [initializeVersion]);
@@ -224,6 +247,107 @@ const Settings = () => {
 						)}
 					</div>
 				</div>
+				<div className="flex flex-col gap-[10px]">
+					<p className="text-[20px] text-white text-medium">
+						{formatMessage({ id: "settings.minPasswordScore" })}:
+					</p>
+					<UISelect
+						value={String(passwordPolicy.minScore)}
+						onChange={v => passwordPolicy.changeMinScore(Number(v))}
+						options={minScoreOptions}
+						style={{ maxWidth: "50%" }}
+					/>
+				</div>
+				<SettingsSigningKeys />
+				<SettingsTrustedKeys />
+				<div className="flex flex-col gap-[10px]">
+					<p className="text-[20px] text-white text-medium">
+						{formatMessage({ id: "settings.diagnostics" })}:
+					</p>
+					<UICheckbox
+						checked={diagnostics.verbose}
+						onChange={diagnostics.changeVerbose}
+						label={formatMessage({ id: "settings.verboseLogging" })}
+					/>
+					<UIButton
+						icon={icons.download}
+						text={formatMessage({ id: "settings.exportLog" })}
+						onClick={diagnostics.exportLog}
+						style={{ width: "fit-content" }}
+					/>
+					<UIButton
+						icon={icons.download}
+						text={formatMessage({ id: "settings.exportDiagnostics" })}
+						onClick={diagnostics.previewBundle}
+						style={{ width: "fit-content" }}
+					/>
+					{diagnostics.bundle && (
+						<SettingsDiagnosticsReview
+							bundle={diagnostics.bundle}
+							omitted={diagnostics.omitted}
+							onToggle={diagnostics.toggleEntry}
+							onSave={diagnostics.saveBundle}
+							onClose={diagnostics.closeBundle}
+						/>
+					)}
+					{diagnostics.error && (
+						<div className="text-red-400 text-sm">
+							{diagnostics.error}
+						</div>
+					)}
+				</div>
+				<div className="flex flex-col gap-[10px]">
+					<p className="text-[20px] text-white text-medium">
+						{formatMessage({ id: "settings.auditLog" })}:
+					</p>
+					<div className="flex items-center gap-[10px]">
+						<UIButton
+							icon={icons.check}
+							text={formatMessage({ id: "settings.verifyAuditLog" })}
+							onClick={audit.verify}
+							style={{ width: "fit-content" }}
+						/>
+						<UIButton
+							icon={icons.download}
+							text={formatMessage({ id: "settings.exportAuditJsonl" })}
+							onClick={() => audit.exportLog("jsonl")}
+							style={{ width: "fit-content" }}
+						/>
+						<UIButton
+							icon={icons.download}
+							text={formatMessage({ id: "settings.exportAuditCsv" })}
+							onClick={() => audit.exportLog("csv")}
+							style={{ width: "fit-content" }}
+						/>
+					</div>
+					{audit.verification?.intact && (
+						<div className="text-green-400 text-sm">
+							{formatMessage(
+								{ id: "settings.auditIntact" },
+								{ entries: audit.verification.entries },
+							)}
+						</div>
+					)}
+					{audit.verification && !audit.verification.intact && (
+						<div className="text-red-400 text-sm">
+							{formatMessage(
+								{
+									id:
+										audit.verification.broken_line === null
+											? "settings.auditBrokenHead"
+											: "settings.auditBroken",
+								},
+								{
+									line: audit.verification.broken_line,
+									reason: audit.verification.reason,
+								},
+							)}
+						</div>
+					)}
+					{audit.error && (
+						<div className="text-red-400 text-sm">{audit.error}</div>
+					)}
+				</div>
 			</div>
 			<div className="flex items-center gap-[10px] mt-[20px]">
 				<UIButton
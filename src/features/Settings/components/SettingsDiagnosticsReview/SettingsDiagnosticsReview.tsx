@@ -0,0 +1,70 @@
+import { useState } from "react";
+import { useIntl } from "react-intl";
+import type { DiagnosticsPreview } from "interfaces";
+import { cn } from "utils";
+import { UIButton, UICheckbox } from "features/UI";
+import { icons } from "assets";
+
+export interface SettingsDiagnosticsReviewProps {
+	bundle: DiagnosticsPreview;
+	omitted: string[];
+	onToggle: (name: string) => void;
+	onSave: () => void;
+	onClose: () => void;
+}
+
+export const SettingsDiagnosticsReview: React.FC<
+	SettingsDiagnosticsReviewProps
+> = ({ bundle, omitted, onToggle, onSave, onClose }) => {
+	const { formatMessage } = useIntl();
+	const [shown, setShown] = useState(bundle.entries[0]?.name);
+	const content = bundle.entries.find(e => e.name === shown)?.content;
+
+	return (
+		<div className="flex flex-col gap-[10px]">
+			<p className="text-gray-400 text-sm">
+				{formatMessage({ id: "settings.diagnosticsReview" })}
+			</p>
+			<div className="flex gap-[20px]">
+				<div className="flex flex-col gap-[8px] min-w-[220px]">
+					{bundle.entries.map(entry => (
+						<div
+							key={entry.name}
+							className={cn("flex items-center gap-[10px]", {
+								"bg-white/10 rounded-[4px]": entry.name === shown,
+							})}>
+							<UICheckbox
+								checked={!omitted.includes(entry.name)}
+								onChange={() => onToggle(entry.name)}
+								label=""
+							/>
+							<span
+								className="text-white text-[14px] cursor-pointer"
+								onClick={() => setShown(entry.name)}>
+								{entry.name}
+							</span>
+						</div>
+					))}
+				</div>
+				<pre className="flex-1 max-h-[320px] overflow-auto text-gray-300 text-xs whitespace-pre-wrap break-all bg-black/30 rounded-[8px] p-[10px]">
+					{content}
+				</pre>
+			</div>
+			<div className="flex items-center gap-[10px]">
+				<UIButton
+					icon={icons.save}
+					text={formatMessage({ id: "settings.saveDiagnostics" })}
+					onClick={onSave}
+					disabled={omitted.length === bundle.entries.length}
+					style={{ width: "fit-content" }}
+				/>
+				<UIButton
+					icon={icons.close}
+					text={formatMessage({ id: "common.close" })}
+					onClick={onClose}
+					style={{ width: "fit-content" }}
+				/>
+			</div>
+		</div>
+	);
+};
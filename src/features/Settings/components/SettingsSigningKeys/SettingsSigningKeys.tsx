@@ -0,0 +1,120 @@
+import { useState } from "react";
+import { useIntl } from "react-intl";
+import { toast } from "react-toastify";
+import { formatLocalDateTime } from "utils";
+import { useSigningKeys } from "features/Settings/hooks";
+import {
+	UIButton,
+	UIIconButton,
+	UIInput,
+	UIPasswordField,
+} from "features/UI";
+import { icons } from "assets";
+
+/** Lists, generates and deletes the ed25519 keys used to sign containers. */
+export const SettingsSigningKeys = () => {
+	const { formatMessage } = useIntl();
+	const { keys, generating, generate, remove, exportPublicKey, error } =
+		useSigningKeys();
+	const [name, setName] = useState("");
+	const [p1, setP1] = useState("");
+	const [p2, setP2] = useState("");
+
+	const submit = async () => {
+		if (p1 !== p2) {
+			toast.error(formatMessage({ id: "common.passwordError" }));
+			return;
+		}
+		const key = await generate(name, p1);
+		if (key) {
+			setName("");
+			setP1("");
+			setP2("");
+		}
+	};
+
+	const copyPublicKey = async (publicKey: string) => {
+		await navigator.clipboard.writeText(publicKey);
+		toast.success(formatMessage({ id: "common.copied" }));
+	};
+
+	return (
+		<div className="flex flex-col gap-[10px]">
+			<p className="text-[20px] text-white text-medium">
+				{formatMessage({ id: "settings.signingKeys" })}:
+			</p>
+			{keys.length === 0 && (
+				<p className="text-gray-400 text-sm">
+					{formatMessage({ id: "settings.signingKeys.empty" })}
+				</p>
+			)}
+			{keys.map(key => (
+				<div key={key.id} className="flex items-center gap-[10px]">
+					<span className="text-white text-[16px]">{key.name}</span>
+					<span className="text-white/50 text-[14px] font-mono">
+						{key.id}
+					</span>
+					<span className="text-white/50 text-[14px]">
+						{formatLocalDateTime(
+							new Date(key.created_at * 1000).toISOString(),
+						)}
+					</span>
+					<UIIconButton
+						icon={icons.copy}
+						title={formatMessage({
+							id: "settings.signingKeys.copyPublicKey",
+						})}
+						onClick={() => copyPublicKey(key.public_key)}
+					/>
+					<UIIconButton
+						icon={icons.download}
+						title={formatMessage({
+							id: "settings.signingKeys.exportPublicKey",
+						})}
+						onClick={() => exportPublicKey(key.id, key.name)}
+					/>
+					<UIIconButton
+						icon={icons.close}
+						title={formatMessage({
+							id: "settings.signingKeys.delete",
+						})}
+						onClick={() => remove(key.id)}
+					/>
+				</div>
+			))}
+			<div className="flex items-center gap-[10px]">
+				<UIInput
+					value={name}
+					onChange={e => setName(e.target.value)}
+					placeholder={formatMessage({
+						id: "common.namePlaceholder",
+					})}
+				/>
+				<UIPasswordField
+					value={p1}
+					onChange={e => setP1(e.target.value)}
+					placeholder={formatMessage({
+						id: "common.passwordPlaceholder",
+					})}
+				/>
+				<UIPasswordField
+					value={p2}
+					onChange={e => setP2(e.target.value)}
+					placeholder={formatMessage({
+						id: "common.passwordConfirmPlaceholder",
+					})}
+				/>
+				<UIButton
+					icon={icons.lock}
+					text={formatMessage({
+						id: "settings.signingKeys.generate",
+					})}
+					onClick={submit}
+					disabled={generating || !name.trim() || !p1}
+					style={{ width: "fit-content" }}
+				/>
+			</div>
+			{error && <div className="text-red-400 text-sm">{error}</div>}
+		</div>
+	);
+};
@@ -0,0 +1,107 @@
+import { useState } from "react";
+import { useIntl } from "react-intl";
+import { formatLocalDateTime } from "utils";
+import { useTrustedKeys } from "features/Settings/hooks";
+import { UIButton, UICheckbox, UIIconButton, UIInput } from "features/UI";
+import { icons } from "assets";
+
+/** Trusted signer keys, and the policy that requires one. */
+export const SettingsTrustedKeys = () => {
+	const { formatMessage } = useIntl();
+	const {
+		keys,
+		requireTrusted,
+		trust,
+		untrust,
+		importFile,
+		exportKey,
+		changeRequireTrusted,
+		error,
+	} = useTrustedKeys();
+	const [name, setName] = useState("");
+	const [publicKey, setPublicKey] = useState("");
+
+	const submit = async () => {
+		if (await trust(name, publicKey)) {
+			setName("");
+			setPublicKey("");
+		}
+	};
+
+	return (
+		<div className="flex flex-col gap-[10px]">
+			<p className="text-[20px] text-white text-medium">
+				{formatMessage({ id: "settings.trustedKeys" })}:
+			</p>
+			<UICheckbox
+				checked={requireTrusted}
+				onChange={changeRequireTrusted}
+				label={formatMessage({ id: "settings.requireTrustedSigner" })}
+			/>
+			{keys.length === 0 && (
+				<p className="text-gray-400 text-sm">
+					{formatMessage({ id: "settings.trustedKeys.empty" })}
+				</p>
+			)}
+			{keys.map(key => (
+				<div
+					key={key.fingerprint}
+					className="flex items-center gap-[10px]">
+					<span className="text-white text-[16px]">{key.name}</span>
+					<span className="text-white/50 text-[14px] font-mono">
+						{key.fingerprint}
+					</span>
+					<span className="text-white/50 text-[14px]">
+						{formatLocalDateTime(
+							new Date(key.added_at * 1000).toISOString(),
+						)}
+					</span>
+					<UIIconButton
+						icon={icons.download}
+						title={formatMessage({
+							id: "settings.trustedKeys.export",
+						})}
+						onClick={() => exportKey(key.fingerprint, key.name)}
+					/>
+					<UIIconButton
+						icon={icons.close}
+						title={formatMessage({
+							id: "settings.trustedKeys.remove",
+						})}
+						onClick={() => untrust(key.fingerprint)}
+					/>
+				</div>
+			))}
+			<div className="flex items-center gap-[10px]">
+				<UIInput
+					value={name}
+					onChange={e => setName(e.target.value)}
+					placeholder={formatMessage({
+						id: "common.namePlaceholder",
+					})}
+				/>
+				<UIInput
+					value={publicKey}
+					onChange={e => setPublicKey(e.target.value)}
+					placeholder={formatMessage({
+						id: "settings.trustedKeys.publicKeyPlaceholder",
+					})}
+				/>
+				<UIButton
+					icon={icons.check}
+					text={formatMessage({ id: "settings.trustedKeys.add" })}
+					onClick={submit}
+					disabled={!name.trim() || !publicKey.trim()}
+					style={{ width: "fit-content" }}
+				/>
+				<UIButton
+					icon={icons.folder}
+					text={formatMessage({ id: "settings.trustedKeys.import" })}
+					onClick={importFile}
+					style={{ width: "fit-content" }}
+				/>
+			</div>
+			{error && <div className="text-red-400 text-sm">{error}</div>}
+		</div>
+	);
+};
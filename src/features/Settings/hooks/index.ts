@@ -1 +1,6 @@
 export * from "./useUpdater";
+export * from "./useDiagnostics";
+export * from "./useAuditLog";
+export * from "./useSigningKeys";
+export * from "./usePasswordPolicy";
+export * from "./useTrustedKeys";
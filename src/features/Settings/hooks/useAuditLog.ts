@@ -0,0 +1,31 @@
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { useCallback, useState } from "react";
+import { AuditExportFormat, AuditVerification } from "interfaces";
+
+export const useAuditLog = () => {
+	const [verification, setVerification] = useState<AuditVerification>();
+	const [error, setError] = useState<string>();
+
+	const verify = useCallback(async () => {
+		try {
+			setError(undefined);
+			setVerification(await invoke<AuditVerification>("verify_audit_log"));
+		} catch (e) {
+			setError(String(e));
+		}
+	}, []);
+
+	const exportLog = useCallback(async (format: AuditExportFormat) => {
+		const path = await save({ defaultPath: `tvault-audit.${format}` });
+		if (!path) return;
+		try {
+			setError(undefined);
+			await invoke("export_audit_log", { path, format });
+		} catch (e) {
+			setError(String(e));
+		}
+	}, []);
+
+	return { verification, verify, exportLog, error };
+};
@@ -0,0 +1,94 @@
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Store } from "@tauri-apps/plugin-store";
+import { useCallback, useEffect, useState } from "react";
+import { DiagnosticsPreview } from "interfaces";
+import { devError } from "utils";
+
+const VAULT_SETTINGS_STORE_KEY = "vault-settings.json";
+const VERBOSE_LOGGING_KEY = "verboseLogging";
+
+export const useDiagnostics = () => {
+	const [verbose, setVerbose] = useState(false);
+	const [error, setError] = useState<string>();
+	const [bundle, setBundle] = useState<DiagnosticsPreview>();
+	const [omitted, setOmitted] = useState<string[]>([]);
+
+	useEffect(() => {
+		Store.load(VAULT_SETTINGS_STORE_KEY)
+			.then(store => store.get<boolean>(VERBOSE_LOGGING_KEY))
+			.then(value => setVerbose(Boolean(value)))
+			.catch(e => devError("Failed to load logging settings:", e));
+	}, []);
+
+	const changeVerbose = useCallback(async (enabled: boolean) => {
+		setVerbose(enabled);
+		try {
+			const store = await Store.load(VAULT_SETTINGS_STORE_KEY);
+			await store.set(VERBOSE_LOGGING_KEY, enabled);
+			await store.save();
+			await invoke("set_verbose_logging", { enabled });
+		} catch (e) {
+			devError("Failed to change logging settings:", e);
+		}
+	}, []);
+
+	const exportLog = useCallback(async () => {
+		const path = await save({ defaultPath: "tvault.log" });
+		if (!path) return;
+		try {
+			setError(undefined);
+			await invoke("export_log", { path });
+		} catch (e) {
+			setError(String(e));
+		}
+	}, []);
+
+	const previewBundle = useCallback(async () => {
+		try {
+			setError(undefined);
+			setOmitted([]);
+			setBundle(await invoke<DiagnosticsPreview>("preview_diagnostics"));
+		} catch (e) {
+			setError(String(e));
+		}
+	}, []);
+
+	const toggleEntry = useCallback((name: string) => {
+		setOmitted(prev =>
+			prev.includes(name) ? prev.filter(n => n !== name) : [...prev, name],
+		);
+	}, []);
+
+	const closeBundle = useCallback(() => setBundle(undefined), []);
+
+	const saveBundle = useCallback(async () => {
+		if (!bundle) return;
+		const path = await save({ defaultPath: "tvault-diagnostics.zip" });
+		if (!path) return;
+		try {
+			setError(undefined);
+			await invoke("export_diagnostics", {
+				path,
+				id: bundle.id,
+				omit: omitted,
+			});
+			setBundle(undefined);
+		} catch (e) {
+			setError(String(e));
+		}
+	}, [bundle, omitted]);
+
+	return {
+		verbose,
+		changeVerbose,
+		exportLog,
+		error,
+		bundle,
+		omitted,
+		previewBundle,
+		toggleEntry,
+		closeBundle,
+		saveBundle,
+	};
+};
@@ -0,0 +1,30 @@
+import { Store } from "@tauri-apps/plugin-store";
+import { useCallback, useEffect, useState } from "react";
+import { devError } from "utils";
+
+const VAULT_SETTINGS_STORE_KEY = "vault-settings.json";
+const MIN_PASSWORD_SCORE_KEY = "minPasswordScore";
+
+export const usePasswordPolicy = () => {
+	const [minScore, setMinScore] = useState(0);
+
+	useEffect(() => {
+		Store.load(VAULT_SETTINGS_STORE_KEY)
+			.then(store => store.get<number>(MIN_PASSWORD_SCORE_KEY))
+			.then(value => setMinScore(Number(value ?? 0)))
+			.catch(e => devError("Failed to load password policy:", e));
+	}, []);
+
+	const changeMinScore = useCallback(async (score: number) => {
+		setMinScore(score);
+		try {
+			const store = await Store.load(VAULT_SETTINGS_STORE_KEY);
+			await store.set(MIN_PASSWORD_SCORE_KEY, score);
+			await store.save();
+		} catch (e) {
+			devError("Failed to change password policy:", e);
+		}
+	}, []);
+
+	return { minScore, changeMinScore };
+};
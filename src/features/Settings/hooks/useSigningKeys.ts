@@ -0,0 +1,71 @@
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { useCallback, useEffect, useState } from "react";
+import { SigningKeyInfo } from "interfaces";
+
+/** ed25519 signing keys in the backend store; private halves never leave it. */
+export const useSigningKeys = () => {
+	const [keys, setKeys] = useState<SigningKeyInfo[]>([]);
+	const [generating, setGenerating] = useState(false);
+	const [error, setError] = useState<string>();
+
+	const reload = useCallback(async () => {
+		try {
+			setKeys(await invoke<SigningKeyInfo[]>("list_signing_keys"));
+		} catch (e) {
+			setError(String(e));
+		}
+	}, []);
+
+	useEffect(() => {
+		reload();
+	}, [reload]);
+
+	const generate = useCallback(
+		async (name: string, passphrase: string) => {
+			setGenerating(true);
+			setError(undefined);
+			try {
+				const key = await invoke<SigningKeyInfo>(
+					"generate_signing_key",
+					{ name, passphrase },
+				);
+				await reload();
+				return key;
+			} catch (e) {
+				setError(String(e));
+				return undefined;
+			} finally {
+				setGenerating(false);
+			}
+		},
+		[reload],
+	);
+
+	const remove = useCallback(
+		async (id: string) => {
+			try {
+				setError(undefined);
+				await invoke("delete_signing_key", { id });
+				await reload();
+			} catch (e) {
+				setError(String(e));
+			}
+		},
+		[reload],
+	);
+
+	/** Writes the public half to a key file others can import as trusted. */
+	const exportPublicKey = useCallback(async (id: string, name: string) => {
+		const path = await save({ defaultPath: `${name}.pub.json` });
+		if (!path) return;
+		try {
+			setError(undefined);
+			await invoke("export_public_key", { fingerprint: id, path });
+		} catch (e) {
+			setError(String(e));
+		}
+	}, []);
+
+	return { keys, generating, generate, remove, exportPublicKey, error };
+};
@@ -0,0 +1,101 @@
+import { invoke } from "@tauri-apps/api/core";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { Store } from "@tauri-apps/plugin-store";
+import { useCallback, useEffect, useState } from "react";
+import { TrustedKey } from "interfaces";
+import { devError } from "utils";
+
+const VAULT_SETTINGS_STORE_KEY = "vault-settings.json";
+const REQUIRE_TRUSTED_SIGNER_KEY = "requireTrustedSigner";
+
+/** Signer keys in the backend trust store and the policy requiring one. */
+export const useTrustedKeys = () => {
+	const [keys, setKeys] = useState<TrustedKey[]>([]);
+	const [requireTrusted, setRequireTrusted] = useState(false);
+	const [error, setError] = useState<string>();
+
+	const reload = useCallback(async () => {
+		try {
+			setKeys(await invoke<TrustedKey[]>("list_trusted_keys"));
+		} catch (e) {
+			setError(String(e));
+		}
+	}, []);
+
+	useEffect(() => {
+		reload();
+		Store.load(VAULT_SETTINGS_STORE_KEY)
+			.then(store => store.get<boolean>(REQUIRE_TRUSTED_SIGNER_KEY))
+			.then(value => setRequireTrusted(value === true))
+			.catch(e => devError("Failed to load signer policy:", e));
+	}, [reload]);
+
+	const run = useCallback(
+		async (command: string, args: Record<string, unknown>) => {
+			try {
+				setError(undefined);
+				await invoke(command, args);
+				await reload();
+				return true;
+			} catch (e) {
+				setError(String(e));
+				return false;
+			}
+		},
+		[reload],
+	);
+
+	const trust = useCallback(
+		(name: string, publicKey: string) =>
+			run("trust_key", { name, publicKey }),
+		[run],
+	);
+
+	const untrust = useCallback(
+		(fingerprint: string) => run("untrust_key", { fingerprint }),
+		[run],
+	);
+
+	const importFile = useCallback(async () => {
+		const path = await open({ multiple: false, directory: false });
+		if (typeof path !== "string") return;
+		await run("import_trusted_key", { path });
+	}, [run]);
+
+	/** Saves a trusted key, or the public half of a signing key, to a file. */
+	const exportKey = useCallback(
+		async (fingerprint: string, name: string) => {
+			const path = await save({ defaultPath: `${name}.pub.json` });
+			if (!path) return;
+			try {
+				setError(undefined);
+				await invoke("export_public_key", { fingerprint, path });
+			} catch (e) {
+				setError(String(e));
+			}
+		},
+		[],
+	);
+
+	const changeRequireTrusted = useCallback(async (value: boolean) => {
+		setRequireTrusted(value);
+		try {
+			const store = await Store.load(VAULT_SETTINGS_STORE_KEY);
+			await store.set(REQUIRE_TRUSTED_SIGNER_KEY, value);
+			await store.save();
+		} catch (e) {
+			devError("Failed to change signer policy:", e);
+		}
+	}, []);
+
+	return {
+		keys,
+		requireTrusted,
+		trust,
+		untrust,
+		importFile,
+		exportKey,
+		changeRequireTrusted,
+		error,
+	};
+};
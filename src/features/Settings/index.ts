@@ -1 +1,4 @@
 export * from "./components/Settings";
+export * from "./components/SettingsDiagnosticsReview";
+export * from "./components/SettingsSigningKeys";
+export * from "./components/SettingsTrustedKeys";
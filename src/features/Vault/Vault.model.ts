@@ -5,7 +5,7 @@ export type KeySource = "generated" | "manual";
 export type ShareEncoding = "base64" | "hex";
 export type CompressionType = "zip" | "none";
 export type ShareDestination = "file" | "stdout";
-export type IntegrityProvider = "none" | "hmac";
+export type IntegrityProvider = "none" | "hmac" | "ed25519";
 export type DecryptMethod = "password" | "shamir";
 export type TokenType = "master" | "share" | "none";
 
@@ -29,6 +29,7 @@ export interface VaultWizardState {
 	/* key */
 	keySource: KeySource;
 	password?: string; // if manual
+	keyFiles?: string[]; // if manual; their contents join the password
 	generatedKey?: string; // base64
 
 	/* shamir */
@@ -52,6 +53,9 @@ export interface VaultWizardState {
 	/* integrity */
 	integrityProvider: IntegrityProvider;
 	additionalPassword?: string;
+	/* ed25519: key from the signing key store and the passphrase that unlocks it */
+	signingKeyId?: string;
+	signingPassphrase?: string;
 
 	/* last step tracking */
 	lastStep?: RouteTypes;
@@ -82,12 +86,17 @@ export interface VaultOpenWizardState {
 
 	/* credentials */
 	password?: string;
+	keyFiles?: string[];
 	masterToken?: string;
 	shares?: string[];
 	tokenJsonPath?: string;
 
 	/* integrity */
 	additionalPassword?: string;
+	/* ed25519: signer public key to check, and the key to reseal with */
+	publicKey?: string;
+	signingKeyId?: string;
+	signingPassphrase?: string;
 
 	/* quick open flow: skip selection/summary and jump between only required steps */
 	quickOpen?: boolean;
@@ -108,11 +117,14 @@ export interface ResealData {
 	mountDir: string;
 	containerInfo: ContainerInfoData;
 	passphrase?: string;
+	keyFiles?: string[];
 	masterToken?: string;
 	shares?: string[];
 	tokenJsonPath?: string;
 	additionalPassword?: string;
 	originalAdditionalPassword?: string;
+	signingKeyId?: string;
+	signingPassphrase?: string;
 	method: DecryptMethod;
 	tokenType: TokenType;
 	integrityProvider: IntegrityProvider;
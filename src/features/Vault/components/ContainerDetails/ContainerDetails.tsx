@@ -1,10 +1,16 @@
+import { ask } from "@tauri-apps/plugin-dialog";
 import { Fragment, useMemo } from "react";
 import { useIntl } from "react-intl";
 import { useSelector } from "react-redux";
 import { useNavigate, useParams } from "react-router-dom";
 import { toast } from "react-toastify";
 import { RouteTypes } from "interfaces";
-import { formatLocalDateTime, useRequestGuard } from "utils";
+import {
+	extractErrorMessage,
+	formatLocalDateTime,
+	useRequestGuard,
+} from "utils";
+import { useAppDispatch } from "features/Store";
 import {
 	UIButton,
 	UIContainerRow,
@@ -13,6 +19,7 @@ import {
 } from "features/UI";
 import { icons } from "assets";
 import { useContainerEdit, useContainerInfo, useVault } from "../../hooks";
+import { vaultDeleteContainer } from "../../state/Vault.actions";
 import {
 	selectVaultContainers,
 	selectVaultRecent,
@@ -31,6 +38,7 @@ const ContainerDetails = () => {
 	const { formatMessage } = useIntl();
 	const { id } = useParams<{ id: string }>();
 	const navigate = useNavigate();
+	const dispatch = useAppDispatch();
 	const containers = useSelector(selectVaultContainers);
 	const recent = useSelector(selectVaultRecent);
 	const {
@@ -48,7 +56,7 @@ const ContainerDetails = () => {
 
 	const mountDir = containerPath ? containers[containerPath] : undefined;
 	const isOpened = !!mountDir;
-	const { run, result } = useContainerInfo();
+	const { run, refresh, result } = useContainerInfo();
 
 	const {
 		isEditing,
@@ -102,6 +110,24 @@ const ContainerDetails = () => {
 		}
 	};
 
+	const handleDelete = async () => {
+		const confirmed = await ask(
+			formatMessage(
+				{ id: "container.delete.confirm" },
+				{ name: containerName },
+			),
+			{ kind: "warning" },
+		);
+		if (!confirmed) return;
+		try {
+			await dispatch(vaultDeleteContainer(containerPath));
+			toast.success(formatMessage({ id: "container.delete.success" }));
+			navigate(RouteTypes.Dashboard);
+		} catch (e) {
+			toast.error(extractErrorMessage(e));
+		}
+	};
+
 	return (
 		<section className="flex flex-col gap-[20px]">
 			<div className="flex items-center gap-[20px]">
@@ -273,6 +299,12 @@ const ContainerDetails = () => {
 					onClick={handleBack}
 					style={{ width: "fit-content" }}
 				/>
+				<UIButton
+					icon={icons.refresh}
+					text={formatMessage({ id: "common.refresh" })}
+					onClick={() => refresh(containerPath).catch(() => {})}
+					style={{ width: "fit-content" }}
+				/>
 				{isOpened ? (
 					<Fragment>
 						<UIButton
@@ -304,12 +336,22 @@ const ContainerDetails = () => {
 						/>
 					</Fragment>
 				) : (
-					<UIButton
-						icon={icons.lock}
-						text={formatMessage({ id: "common.open" })}
-						onClick={() => handleOpenClosedContainer(containerPath)}
-						style={{ width: "fit-content" }}
-					/>
+					<Fragment>
+						<UIButton
+							icon={icons.lock}
+							text={formatMessage({ id: "common.open" })}
+							onClick={() =>
+								handleOpenClosedContainer(containerPath)
+							}
+							style={{ width: "fit-content" }}
+						/>
+						<UIButton
+							icon={icons.close}
+							text={formatMessage({ id: "container.delete" })}
+							onClick={handleDelete}
+							style={{ width: "fit-content" }}
+						/>
+					</Fragment>
 				)}
 			</div>
 		</section>
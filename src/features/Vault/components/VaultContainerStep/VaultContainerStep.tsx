@@ -26,6 +26,7 @@ import {
 	selectVaultOpenWizardState,
 	selectVaultRecent,
 } from "../../state/Vault.selectors";
+import type { IntegrityProvider } from "../../Vault.model";
 
 const saveRecentData = createAsyncOnceGuard(
 	async (path: string, dispatch: any) => {
@@ -210,7 +211,7 @@ const VaultContainerStep = () => {
 				(containerInfo.token_type as "master" | "share" | "none") ||
 				"none";
 			const integrityProvider =
-				(containerInfo.integrity_provider_type as "none" | "hmac") ||
+				(containerInfo.integrity_provider_type as IntegrityProvider) ||
 				"none";
 
 			dispatch(
@@ -73,7 +73,7 @@ const VaultDecryptRunStep = () => {
 		(state: any) => state.vault.containerInfo[savedContainerPath],
 	);
 
-	const { progress, done, error, run } = useDecrypt();
+	const { progress, done, error, signer, run, cancel } = useDecrypt();
 
 	const isCompleted = wizard.decryptCompleted;
 	const finalDone = done || isCompleted;
@@ -139,35 +139,47 @@ const VaultDecryptRunStep = () => {
 				return;
 			}
 
+			if (
+				wizard.integrityProvider === "ed25519" &&
+				!wizard.publicKey
+			) {
+				devError("Signer public key is required for ed25519");
+				return;
+			}
+
 			if (progress > 0) {
 				devLog("[tvault] decrypt already in progress, skipping");
 				return;
 			}
 
+			const integrity = {
+				additionalPassword:
+					wizard.integrityProvider === "hmac"
+						? wizard.additionalPassword
+						: undefined,
+				publicKey:
+					wizard.integrityProvider === "ed25519"
+						? wizard.publicKey
+						: undefined,
+			};
+
 			if (isPasswordMethod) {
 				guardedRun({
 					containerPath: savedContainerPath,
 					folderPath: savedMountDir,
 					passphrase: wizard.password!,
+					keyFiles: wizard.keyFiles,
 					tokenReaderType: "flag",
 					tokenFormat: "plaintext",
 					tokenFlag: wizard.password!,
-					additionalPassword:
-						wizard.integrityProvider === "hmac"
-							? wizard.additionalPassword
-							: undefined,
+					...integrity,
 				});
 			} else if (isMasterMethod) {
 				guardedRun({
 					containerPath: savedContainerPath,
 					folderPath: savedMountDir,
-					tokenReaderType: "flag",
-					tokenFormat: "plaintext",
-					tokenFlag: wizard.masterToken!,
-					additionalPassword:
-						wizard.integrityProvider === "hmac"
-							? wizard.additionalPassword
-							: undefined,
+					masterToken: wizard.masterToken!,
+					...integrity,
 				});
 			} else {
 				if (wizard.tokenJsonPath) {
@@ -177,10 +189,7 @@ const VaultDecryptRunStep = () => {
 						tokenReaderType: "file",
 						tokenFormat: "json",
 						tokenPath: wizard.tokenJsonPath,
-						additionalPassword:
-							wizard.integrityProvider === "hmac"
-								? wizard.additionalPassword
-								: undefined,
+						...integrity,
 					});
 				} else {
 					const filtered =
@@ -191,10 +200,7 @@ const VaultDecryptRunStep = () => {
 						tokenReaderType: "flag",
 						tokenFormat: "plaintext",
 						tokenFlag: filtered.join("|"),
-						additionalPassword:
-							wizard.integrityProvider === "hmac"
-								? wizard.additionalPassword
-								: undefined,
+						...integrity,
 					});
 				}
 			}
@@ -228,11 +234,14 @@ const VaultDecryptRunStep = () => {
 				mountDir: savedMountDir,
 				containerInfo: containerInfo || {},
 				passphrase: wizard.password,
+				keyFiles: wizard.keyFiles,
 				masterToken: wizard.masterToken,
 				shares: wizard.shares,
 				tokenJsonPath: wizard.tokenJsonPath,
 				additionalPassword: wizard.additionalPassword,
 				originalAdditionalPassword: wizard.additionalPassword,
+				signingKeyId: wizard.signingKeyId,
+				signingPassphrase: wizard.signingPassphrase,
 				method: wizard.method,
 				tokenType: wizard.tokenType,
 				integrityProvider: wizard.integrityProvider,
@@ -371,6 +380,16 @@ const VaultDecryptRunStep = () => {
 						})}
 						: {progress}%
 					</p>
+					<UIButton
+						icon={icons.close}
+						text={formatMessage({ id: "common.cancel" })}
+						onClick={() =>
+							cancel()
+								.then(() => navigate(-1))
+								.catch(devError)
+						}
+						style={{ width: "fit-content" }}
+					/>
 				</div>
 			</div>
 		);
@@ -430,6 +449,17 @@ const VaultDecryptRunStep = () => {
 						/>
 					</div>
 				</div>
+				{signer && (
+					<div className="flex items-center p-[15px] gap-[10px] bg-white/5 rounded-[10px] text-white">
+						<p className="opacity-50 whitespace-nowrap">
+							{formatMessage({
+								id: "vault.decryptRun.step.signer",
+							})}
+							:
+						</p>
+						<p className="text-[16px] text-medium">{signer.label}</p>
+					</div>
+				)}
 			</div>
 		</div>
 	);
@@ -8,7 +8,11 @@ import { selectVaultOpenWizardState } from "features/Vault/state/Vault.selectors
 import { icons } from "assets";
 import { useContainerInfo } from "../../hooks/useContainerInfo";
 
-const humanIntegrity = { none: "None", hmac: "HMAC-SHA256" } as const;
+const humanIntegrity = {
+	none: "None",
+	hmac: "HMAC-SHA256",
+	ed25519: "Ed25519 signature",
+} as const;
 
 const VaultDecryptSummaryStep = () => {
 	const { formatMessage } = useIntl();
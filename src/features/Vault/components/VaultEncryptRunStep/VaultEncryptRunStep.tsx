@@ -1,3 +1,4 @@
+import { invoke } from "@tauri-apps/api/core";
 import { openPath } from "@tauri-apps/plugin-opener";
 import { Store } from "@tauri-apps/plugin-store";
 import { useState } from "react";
@@ -7,7 +8,7 @@ import { useNavigate } from "react-router-dom";
 import { toast } from "react-toastify";
 import { useEffectOnce } from "react-use";
 import { RouteTypes } from "interfaces";
-import { devError, devLog } from "utils";
+import { devError, devLog, extractErrorMessage } from "utils";
 import { useAppDispatch } from "features/Store";
 import { UIButton, UISectionHeading } from "features/UI";
 import { icons } from "assets";
@@ -35,8 +36,10 @@ const VaultEncryptRunStep = () => {
 	const [savedTokenType, setSavedTokenType] = useState<string>("");
 	const [savedAdditionalPassword, setSavedAdditionalPassword] =
 		useState<string>("");
+	/* master token as BIP39 English words, for writing down */
+	const [masterWords, setMasterWords] = useState<string | null>(null);
 
-	const { progress, done, result, error, run } = useEncrypt(wizard);
+	const { progress, done, result, error, run, cancel } = useEncrypt(wizard);
 
 	const isCompleted = wizard.encryptCompleted;
 	const finalDone = done || isCompleted;
@@ -179,6 +182,17 @@ const VaultEncryptRunStep = () => {
 		}
 	};
 
+	const showMasterWords = async (token: string) => {
+		try {
+			setMasterWords(
+				await invoke<string>("master_token_to_mnemonic", { token }),
+			);
+		} catch (e) {
+			devError(e);
+			toast.error(extractErrorMessage(e));
+		}
+	};
+
 	const openFileFolder = async () => {
 		if (!savedSharePath) return;
 		try {
@@ -249,6 +263,16 @@ const VaultEncryptRunStep = () => {
 					<p className="text-[16px] text-white/50 text-medium">
 						{formatMessage({ id: "common.progress" })} {progress}%
 					</p>
+					<UIButton
+						icon={icons.close}
+						text={formatMessage({ id: "common.cancel" })}
+						onClick={() =>
+							cancel()
+								.then(() => navigate(-1))
+								.catch(devError)
+						}
+						style={{ width: "fit-content" }}
+					/>
 				</div>
 			</div>
 		);
@@ -343,6 +367,33 @@ const VaultEncryptRunStep = () => {
 										}}
 									/>
 								</div>
+								{masterWords ? (
+									<div className="grid grid-cols-[1fr_auto] items-center gap-[10px] p-[15px] bg-[#3361D8]/10 border-[#2E68C4]/50 rounded-[10px] max-w-full">
+										<p className="text-[16px] text-medium break-words">
+											{masterWords}
+										</p>
+										<button
+											type="button"
+											onClick={() => copy(masterWords)}
+											className="w-[20px] h-[20px] flex items-center justify-center cursor-pointer mask-size-[16px] bg-white/50 hover:bg-white/70 transition-all duration-300"
+											style={{
+												WebkitMask: `url("${icons.copy}") no-repeat center`,
+												mask: `url("${icons.copy}") no-repeat center`,
+											}}
+										/>
+									</div>
+								) : (
+									<UIButton
+										icon={icons.pencil}
+										text={formatMessage({
+											id: "vault.encryptRun.showWords",
+										})}
+										onClick={() =>
+											showMasterWords(res.masterToken!)
+										}
+										style={{ width: "fit-content" }}
+									/>
+								)}
 							</>
 						)}
 						{savedTokenType === "none" &&
@@ -29,13 +29,8 @@ const VaultEntropyGenStep = () => {
 					{formatMessage({ id: "vault.entropyGen.description" })}:
 				</p>
 				<EntropyCanvas
-					onReady={() => {
-						const bytes = new Uint8Array(32);
-						crypto.getRandomValues(bytes);
-						const b64 = btoa(String.fromCharCode(...bytes));
-						dispatch(
-							vaultSetWizardState({ ...wizard, passphrase: b64 }),
-						);
+					onReady={passphrase => {
+						dispatch(vaultSetWizardState({ ...wizard, passphrase }));
 						navigate(RouteTypes.VaultCreateIntegrity);
 					}}
 				/>
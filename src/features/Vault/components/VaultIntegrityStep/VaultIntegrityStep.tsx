@@ -9,7 +9,7 @@ import { icons } from "assets";
 import { vaultSetWizardState } from "../../state/Vault.actions";
 import { selectVaultWizardState } from "../../state/Vault.selectors";
 
-export type IntegrityProvider = "none" | "hmac";
+export type IntegrityProvider = "none" | "hmac" | "ed25519";
 
 const VaultIntegrityStep = () => {
 	const { formatMessage } = useIntl();
@@ -27,6 +27,8 @@ const VaultIntegrityStep = () => {
 		);
 		if (provider === "hmac") {
 			navigate(RouteTypes.VaultCreateIntegrityPassword);
+		} else if (provider === "ed25519") {
+			navigate(RouteTypes.VaultCreateSigningKey);
 		} else {
 			navigate(RouteTypes.VaultCreateSplitToggle);
 		}
@@ -41,7 +43,7 @@ const VaultIntegrityStep = () => {
 			<p className="text-[20px] text-medium text-white text-center mt-[10px]">
 				{formatMessage({ id: "vault.integrity.step" })}
 			</p>
-			<div className="grid grid-cols-[260px_260px] justify-center p-[20px] bg-white/5 rounded-[10px] mt-[20px] gap-[40px]">
+			<div className="grid grid-cols-[260px_260px_260px] justify-center p-[20px] bg-white/5 rounded-[10px] mt-[20px] gap-[40px]">
 				<UIRadioCard
 					title={formatMessage({ id: "vault.integrity.none" })}
 					subtitle={formatMessage({
@@ -58,6 +60,14 @@ const VaultIntegrityStep = () => {
 					selected={provider === "hmac"}
 					onClick={() => setProvider("hmac")}
 				/>
+				<UIRadioCard
+					title={formatMessage({ id: "vault.integrity.ed25519" })}
+					subtitle={formatMessage({
+						id: "vault.integrity.ed25519Description",
+					})}
+					selected={provider === "ed25519"}
+					onClick={() => setProvider("ed25519")}
+				/>
 			</div>
 			<div className="flex items-center gap-[10px] mt-[20px]">
 				<UIButton
@@ -0,0 +1,92 @@
+import { invoke } from "@tauri-apps/api/core";
+import { open, save } from "@tauri-apps/plugin-dialog";
+import { useCallback } from "react";
+import { useIntl } from "react-intl";
+import { toast } from "react-toastify";
+import { extractErrorMessage } from "utils";
+import { UIButton, UIIconButton } from "features/UI";
+import { icons } from "assets";
+
+const fileName = (path: string) => path.split(/[\\/]/).pop() || path;
+
+/**
+ * Key files picked for a container; their contents join the password.
+ * `canGenerate` offers writing a new random key file, for sealing.
+ */
+const VaultKeyFiles = ({
+	files,
+	onChange,
+	canGenerate,
+}: {
+	files: string[];
+	onChange: (files: string[]) => void;
+	canGenerate?: boolean;
+}) => {
+	const { formatMessage } = useIntl();
+
+	const add = useCallback(
+		(picked: string[]) =>
+			onChange([...files, ...picked.filter(p => !files.includes(p))]),
+		[files, onChange],
+	);
+
+	const pick = useCallback(async () => {
+		const picked = await open({ multiple: true });
+		if (Array.isArray(picked)) add(picked);
+		else if (typeof picked === "string") add([picked]);
+	}, [add]);
+
+	const generate = useCallback(async () => {
+		const path = await save({ defaultPath: "tvault.key" });
+		if (!path) return;
+		try {
+			await invoke("generate_key_file", { path });
+			add([path]);
+			toast.warn(formatMessage({ id: "vault.keyFiles.generated" }));
+		} catch (e) {
+			toast.error(extractErrorMessage(e));
+		}
+	}, [add, formatMessage]);
+
+	return (
+		<div className="flex flex-col gap-[10px]">
+			<p className="text-[20px] text-white text-medium">
+				{formatMessage({ id: "vault.keyFiles.title" })}:
+			</p>
+			<p className="text-white/50 text-sm">
+				{formatMessage({ id: "vault.keyFiles.hint" })}
+			</p>
+			{files.map(path => (
+				<div key={path} className="flex items-center gap-[10px]">
+					<span className="text-white text-[16px]">
+						{fileName(path)}
+					</span>
+					<span className="text-white/50 text-[14px]">{path}</span>
+					<UIIconButton
+						icon={icons.close}
+						title={formatMessage({ id: "vault.keyFiles.remove" })}
+						onClick={() => onChange(files.filter(f => f !== path))}
+					/>
+				</div>
+			))}
+			<div className="flex items-center gap-[10px]">
+				<UIButton
+					icon={icons.folder}
+					text={formatMessage({ id: "vault.keyFiles.add" })}
+					onClick={pick}
+					style={{ width: "fit-content" }}
+				/>
+				{canGenerate && (
+					<UIButton
+						icon={icons.refresh}
+						text={formatMessage({ id: "vault.keyFiles.generate" })}
+						onClick={generate}
+						style={{ width: "fit-content" }}
+					/>
+				)}
+			</div>
+		</div>
+	);
+};
+
+export { VaultKeyFiles };
@@ -5,9 +5,12 @@ import { useNavigate } from "react-router-dom";
 import { toast } from "react-toastify";
 import { z } from "zod";
 import { RouteTypes } from "interfaces";
+import { devError, extractErrorMessage } from "utils";
 import { useAppDispatch } from "features/Store";
 import { UIButton, UIPasswordField, UISectionHeading } from "features/UI";
+import { usePasswordStrength } from "features/Vault/hooks";
 import { icons } from "assets";
+import { VaultKeyFiles } from "../VaultKeyFiles";
 import { vaultSetWizardState } from "../../state/Vault.actions";
 import { selectVaultWizardState } from "../../state/Vault.selectors";
 
@@ -33,6 +36,22 @@ const VaultKeyManualStep = () => {
 
 	const [p1, setP1] = useState("");
 	const [p2, setP2] = useState("");
+	const [keyFiles, setKeyFiles] = useState<string[]>(wizard.keyFiles ?? []);
+	const { strength, minScore, tooWeak, generate } = usePasswordStrength(
+		p1,
+		[wizard.name, ...wizard.tags.split(",")].filter(Boolean),
+	);
+
+	const generatePassphrase = async () => {
+		try {
+			const phrase = await generate();
+			setP1(phrase);
+			setP2(phrase);
+		} catch (e) {
+			devError("generate_passphrase failed", extractErrorMessage(e));
+			toast.error(extractErrorMessage(e));
+		}
+	};
 
 	const submit = () => {
 		const chk = formSchema.safeParse({ p1, p2 });
@@ -40,8 +59,23 @@ const VaultKeyManualStep = () => {
 			toast.error(formatMessage({ id: chk.error.issues[0].message }));
 			return;
 		}
+		// a key file carries the strength, whatever the password is
+		if (tooWeak && keyFiles.length === 0) {
+			toast.error(
+				formatMessage(
+					{ id: "vault.keyManual.tooWeak" },
+					{ min: minScore },
+				),
+			);
+			return;
+		}
 		dispatch(
-			vaultSetWizardState({ ...wizard, password: p1, passphrase: p1 }),
+			vaultSetWizardState({
+				...wizard,
+				password: p1,
+				passphrase: p1,
+				keyFiles,
+			}),
 		);
 		navigate(RouteTypes.VaultCreateIntegrity);
 	};
@@ -60,14 +94,56 @@ const VaultKeyManualStep = () => {
 					<p className="text-[20px] text-white text-medium">
 						{formatMessage({ id: "vault.keyManual.password" })}:
 					</p>
-					<UIPasswordField
-						value={p1}
-						onChange={e => setP1(e.target.value)}
-						placeholder={formatMessage({
-							id: "common.passwordPlaceholder",
-						})}
-						style={{ maxWidth: "50%" }}
-					/>
+					<div className="flex items-center gap-[10px]">
+						<UIPasswordField
+							value={p1}
+							onChange={e => setP1(e.target.value)}
+							placeholder={formatMessage({
+								id: "common.passwordPlaceholder",
+							})}
+							style={{ maxWidth: "50%" }}
+						/>
+						<UIButton
+							icon={icons.refresh}
+							text={formatMessage({
+								id: "vault.keyManual.generate",
+							})}
+							onClick={generatePassphrase}
+							style={{ width: "fit-content" }}
+						/>
+					</div>
+					{strength && (
+						<div className="flex flex-col gap-[5px] text-sm">
+							<span
+								className={
+									tooWeak ? "text-red-400" : "text-white/70"
+								}>
+								{formatMessage(
+									{ id: "vault.keyManual.strength" },
+									{
+										score: strength.score,
+										time: strength.crack_times.offline_slow
+											.display,
+									},
+								)}
+								{minScore > 0 &&
+									` · ${formatMessage(
+										{ id: "vault.keyManual.minScore" },
+										{ min: minScore },
+									)}`}
+							</span>
+							{strength.warning && (
+								<span className="text-yellow-400">
+									{strength.warning}
+								</span>
+							)}
+							{strength.suggestions.map(s => (
+								<span key={s} className="text-white/50">
+									{s}
+								</span>
+							))}
+						</div>
+					)}
 				</div>
 				<div className="flex flex-col gap-[10px]">
 					<p className="text-[20px] text-white text-medium">
@@ -85,6 +161,11 @@ const VaultKeyManualStep = () => {
 						style={{ maxWidth: "50%" }}
 					/>
 				</div>
+				<VaultKeyFiles
+					files={keyFiles}
+					onChange={setKeyFiles}
+					canGenerate
+				/>
 			</div>
 			<div className="flex items-center gap-[10px] mt-[20px]">
 				<UIButton
@@ -1,11 +1,20 @@
-import { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect, useState } from "react";
 import { useIntl } from "react-intl";
 import { useSelector } from "react-redux";
 import { useNavigate } from "react-router-dom";
 import { toast } from "react-toastify";
-import { RouteTypes } from "interfaces";
+import { RouteTypes, TrustedKey } from "interfaces";
+import { devError } from "utils";
+import { useSigningKeys } from "features/Settings/hooks";
 import { useAppDispatch } from "features/Store";
-import { UIButton, UIInput, UISectionHeading } from "features/UI";
+import {
+	UIButton,
+	UIInput,
+	UIPasswordField,
+	UISectionHeading,
+	UISelect,
+} from "features/UI";
 import { vaultSetOpenWizardState } from "features/Vault/state/Vault.actions";
 import { selectVaultOpenWizardState } from "features/Vault/state/Vault.selectors";
 import { icons } from "assets";
@@ -16,17 +25,45 @@ const VaultOpenIntegrityStep = () => {
 	const dispatch = useAppDispatch();
 	const navigate = useNavigate();
 
+	const signed = wizard.integrityProvider === "ed25519";
+	const { keys } = useSigningKeys();
+	const [trusted, setTrusted] = useState<TrustedKey[]>([]);
+
 	const [pwd, setPwd] = useState<string>(wizard.additionalPassword || "");
+	const [publicKey, setPublicKey] = useState(wizard.publicKey || "");
+	const [keyId, setKeyId] = useState(wizard.signingKeyId || "");
+	const [signingPassphrase, setSigningPassphrase] = useState(
+		wizard.signingPassphrase || "",
+	);
+
+	useEffect(() => {
+		if (!signed) return;
+		invoke<TrustedKey[]>("list_trusted_keys")
+			.then(setTrusted)
+			.catch(e => devError("Failed to load trusted keys:", e));
+	}, [signed]);
 
 	const next = () => {
-		if (!pwd.trim()) {
-			toast.error(formatMessage({ id: "vault.integrityStep.error" }));
+		if (signed ? !publicKey.trim() : !pwd.trim()) {
+			toast.error(
+				formatMessage({
+					id: signed
+						? "vault.integrityStep.publicKeyError"
+						: "vault.integrityStep.error",
+				}),
+			);
 			return;
 		}
 		dispatch(
 			vaultSetOpenWizardState({
 				...wizard,
-				additionalPassword: pwd,
+				...(signed
+					? {
+							publicKey: publicKey.trim(),
+							signingKeyId: keyId || undefined,
+							signingPassphrase: signingPassphrase || undefined,
+						}
+					: { additionalPassword: pwd }),
 			}),
 		);
 
@@ -40,24 +77,97 @@ const VaultOpenIntegrityStep = () => {
 				text={formatMessage({ id: "title.open" })}
 			/>
 			<p className="text-[20px] text-medium text-white text-center mt-[10px]">
-				{formatMessage({ id: "vault.integrityStep.step" })}
+				{formatMessage({
+					id: signed
+						? "vault.integrityStep.stepSigned"
+						: "vault.integrityStep.step",
+				})}
 			</p>
-			<div className="flex flex-col gap-[20px] p-[20px] bg-white/5 rounded-[10px] mt-[20px]">
-				<div className="flex flex-col gap-[10px]">
-					<p className="text-[20px] text-white text-medium">
-						{formatMessage({ id: "vault.integrityStep.password" })}:
-					</p>
-					<UIInput
-						type="password"
-						placeholder={formatMessage({
-							id: "common.passwordPlaceholder",
-						})}
-						value={pwd}
-						onChange={e => setPwd(e.target.value)}
-						style={{ maxWidth: "50%" }}
-					/>
+			{signed ? (
+				<div className="flex flex-col gap-[20px] p-[20px] bg-white/5 rounded-[10px] mt-[20px]">
+					<div className="flex flex-col gap-[10px]">
+						<p className="text-[20px] text-white text-medium">
+							{formatMessage({
+								id: "vault.integrityStep.publicKey",
+							})}
+							:
+						</p>
+						{trusted.length > 0 && (
+							<UISelect
+								value={publicKey}
+								onChange={setPublicKey}
+								options={trusted.map(k => ({
+									value: k.public_key,
+									label: `${k.name} (${k.fingerprint})`,
+								}))}
+								placeholder={formatMessage({
+									id: "vault.integrityStep.trustedKey",
+								})}
+								style={{ maxWidth: "50%" }}
+							/>
+						)}
+						<UIInput
+							placeholder="ed25519:…"
+							value={publicKey}
+							onChange={e => setPublicKey(e.target.value)}
+							style={{ maxWidth: "50%" }}
+						/>
+					</div>
+					<div className="flex flex-col gap-[10px]">
+						<p className="text-[20px] text-white text-medium">
+							{formatMessage({
+								id: "vault.integrityStep.resealKey",
+							})}
+							:
+						</p>
+						<p className="text-white/50 text-[14px]">
+							{formatMessage({
+								id: "vault.integrityStep.resealKeyHint",
+							})}
+						</p>
+						<UISelect
+							value={keyId}
+							onChange={setKeyId}
+							options={keys.map(k => ({
+								value: k.id,
+								label: `${k.name} (${k.id})`,
+							}))}
+							placeholder={formatMessage({
+								id: "vault.signingKey.select",
+							})}
+							style={{ maxWidth: "50%" }}
+						/>
+						<UIPasswordField
+							value={signingPassphrase}
+							onChange={e => setSigningPassphrase(e.target.value)}
+							placeholder={formatMessage({
+								id: "vault.signingKey.passphrase",
+							})}
+							style={{ maxWidth: "50%" }}
+						/>
+					</div>
 				</div>
-			</div>
+			) : (
+				<div className="flex flex-col gap-[20px] p-[20px] bg-white/5 rounded-[10px] mt-[20px]">
+					<div className="flex flex-col gap-[10px]">
+						<p className="text-[20px] text-white text-medium">
+							{formatMessage({
+								id: "vault.integrityStep.password",
+							})}
+							:
+						</p>
+						<UIInput
+							type="password"
+							placeholder={formatMessage({
+								id: "common.passwordPlaceholder",
+							})}
+							value={pwd}
+							onChange={e => setPwd(e.target.value)}
+							style={{ maxWidth: "50%" }}
+						/>
+					</div>
+				</div>
+			)}
 			<div className="flex items-center gap-[10px] mt-[20px]">
 				<UIButton
 					icon={icons.back}
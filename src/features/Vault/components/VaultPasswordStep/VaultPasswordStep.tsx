@@ -1,13 +1,22 @@
+import { invoke } from "@tauri-apps/api/core";
 import { useCallback, useState } from "react";
 import { useIntl } from "react-intl";
 import { useSelector } from "react-redux";
 import { useNavigate } from "react-router-dom";
 import { toast } from "react-toastify";
-import { RouteTypes } from "interfaces";
+import { KeyFileCheck, RouteTypes } from "interfaces";
+import { extractErrorMessage } from "utils";
 import { LocalizationTypes, useLocale } from "features/Localization";
 import { useAppDispatch } from "features/Store";
-import { UIButton, UIPasswordField, UISectionHeading } from "features/UI";
+import {
+	UIButton,
+	UICheckbox,
+	UIInput,
+	UIPasswordField,
+	UISectionHeading,
+} from "features/UI";
 import { icons } from "assets";
+import { VaultKeyFiles } from "../VaultKeyFiles";
 import { vaultSetOpenWizardState } from "../../state/Vault.actions";
 import { selectVaultOpenWizardState } from "../../state/Vault.selectors";
 
@@ -20,28 +29,62 @@ const VaultPasswordStep = () => {
 
 	const [pwd, setPwd] = useState(wizard.password || "");
 	const [masterToken, setMasterToken] = useState(wizard.masterToken || "");
+	/* the master token written down as BIP39 English words */
+	const [asWords, setAsWords] = useState(false);
+	const [keyFiles, setKeyFiles] = useState<string[]>(wizard.keyFiles ?? []);
+	/* shown once before unsealing: an edited key file locks the container */
+	const [keyFileWarning, setKeyFileWarning] = useState<string | null>(null);
 
-	const next = useCallback(() => {
+	const changeKeyFiles = useCallback((files: string[]) => {
+		setKeyFiles(files);
+		setKeyFileWarning(null);
+	}, []);
+
+	const next = useCallback(async () => {
+		let token = masterToken.trim();
 		if (wizard.tokenType === "master") {
-			if (!masterToken.trim()) {
+			if (!token) {
 				toast.error("Enter master token");
 				return;
 			}
+			if (asWords) {
+				try {
+					token = await invoke<string>("mnemonic_to_master_token", {
+						words: token,
+					});
+				} catch (e) {
+					toast.error(extractErrorMessage(e));
+					return;
+				}
+			}
 		} else {
 			if (!pwd.length) {
 				toast.error("Enter password");
 				return;
 			}
+			if (keyFileWarning === null) {
+				try {
+					const check = await invoke<KeyFileCheck>(
+						"check_key_files",
+						{ containerPath: wizard.containerPath, keyFiles },
+					);
+					if (check.warning) {
+						setKeyFileWarning(check.warning);
+						return;
+					}
+				} catch (e) {
+					toast.error(extractErrorMessage(e));
+					return;
+				}
+			}
 		}
 
 		dispatch(
 			vaultSetOpenWizardState({
 				...wizard,
 				password: wizard.tokenType === "master" ? undefined : pwd,
-				masterToken:
-					wizard.tokenType === "master"
-						? masterToken.trim()
-						: undefined,
+				keyFiles: wizard.tokenType === "master" ? undefined : keyFiles,
+				masterToken: wizard.tokenType === "master" ? token : undefined,
 			}),
 		);
 		if (wizard.integrityProvider === "none") {
@@ -49,7 +92,16 @@ const VaultPasswordStep = () => {
 		} else {
 			navigate(RouteTypes.VaultOpenIntegrity);
 		}
-	}, [pwd, masterToken, dispatch, wizard, navigate]);
+	}, [
+		pwd,
+		masterToken,
+		asWords,
+		keyFiles,
+		keyFileWarning,
+		dispatch,
+		wizard,
+		navigate,
+	]);
 
 	return (
 		<div>
@@ -68,16 +120,34 @@ const VaultPasswordStep = () => {
 							})}
 							:
 						</p>
-						<UIPasswordField
-							value={masterToken}
-							onChange={e => setMasterToken(e.target.value)}
-							placeholder="Master token"
-							style={{
-								maxWidth:
-									locale === LocalizationTypes.Russian
-										? "75%"
-										: "50%",
-							}}
+						{asWords ? (
+							<UIInput
+								value={masterToken}
+								onChange={e => setMasterToken(e.target.value)}
+								placeholder={formatMessage({
+									id: "vault.passwordStep.wordsPlaceholder",
+								})}
+								style={{ maxWidth: "100%" }}
+							/>
+						) : (
+							<UIPasswordField
+								value={masterToken}
+								onChange={e => setMasterToken(e.target.value)}
+								placeholder="Master token"
+								style={{
+									maxWidth:
+										locale === LocalizationTypes.Russian
+											? "75%"
+											: "50%",
+								}}
+							/>
+						)}
+						<UICheckbox
+							checked={asWords}
+							onChange={setAsWords}
+							label={formatMessage({
+								id: "vault.passwordStep.asWords",
+							})}
 						/>
 					</div>
 				) : (
@@ -98,6 +168,17 @@ const VaultPasswordStep = () => {
 						/>
 					</div>
 				)}
+				{wizard.tokenType !== "master" && (
+					<VaultKeyFiles files={keyFiles} onChange={changeKeyFiles} />
+				)}
+				{keyFileWarning && (
+					<p className="text-yellow-400 text-sm">
+						{formatMessage(
+							{ id: "vault.keyFiles.warning" },
+							{ warning: keyFileWarning },
+						)}
+					</p>
+				)}
 			</div>
 			<div className="flex items-center gap-[10px] mt-[20px]">
 				<UIButton
@@ -108,7 +189,11 @@ const VaultPasswordStep = () => {
 				/>
 				<UIButton
 					icon={icons.arrow_right}
-					text={formatMessage({ id: "common.next" })}
+					text={formatMessage({
+						id: keyFileWarning
+							? "vault.keyFiles.continueAnyway"
+							: "common.next",
+					})}
 					onClick={next}
 					disabled={
 						wizard.tokenType === "master"
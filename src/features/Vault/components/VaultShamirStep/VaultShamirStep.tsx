@@ -1,9 +1,18 @@
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
 import { useCallback, useEffect, useMemo, useState } from "react";
 import { useIntl } from "react-intl";
 import { useSelector } from "react-redux";
 import { useNavigate } from "react-router-dom";
 import { toast } from "react-toastify";
-import { RouteTypes } from "interfaces";
+import {
+	RouteTypes,
+	type ScannedImage,
+	type ShareCheck,
+	type ShareInput,
+	type SharePayload,
+} from "interfaces";
+import { devError, extractErrorMessage } from "utils";
 import { useAppDispatch } from "features/Store";
 import { UIButton, UIInput, UISectionHeading } from "features/UI";
 import { icons } from "assets";
@@ -26,8 +35,46 @@ const VaultShamirStep = () => {
 		}
 	}, [shares]);
 
+	/* pages scanned by QR, by share text, so check_shares gets their ids */
+	const [scanned, setScanned] = useState<Record<string, SharePayload>>({});
+	const [check, setCheck] = useState<ShareCheck | null>(null);
+
 	const update = useCallback((idx: number, val: string) => {
 		setShares(prev => prev.map((s, i) => (i === idx ? val : s)));
+		setCheck(null);
+	}, []);
+
+	const scanPages = useCallback(async () => {
+		const files = await open({
+			multiple: true,
+			filters: [{ name: "Images", extensions: ["png", "jpg", "jpeg"] }],
+		});
+		if (!Array.isArray(files) || files.length === 0) return;
+		try {
+			const images = await invoke<ScannedImage[]>("scan_share_images", {
+				paths: files,
+			});
+			const found: Record<string, SharePayload> = {};
+			const texts: string[] = [];
+			for (const image of images) {
+				if (image.error) toast.error(image.error);
+				for (const input of image.shares) {
+					texts.push(input.share.trim());
+					if ("tvault_share" in input) {
+						found[input.share.trim()] = input;
+					}
+				}
+			}
+			setScanned(prev => ({ ...prev, ...found }));
+			setShares(prev => [
+				...prev.filter(s => s.trim().length > 0),
+				...texts.filter(t => !prev.some(s => s.trim() === t)),
+			]);
+			setCheck(null);
+		} catch (e) {
+			devError("Failed to scan share pages:", e);
+			toast.error(extractErrorMessage(e));
+		}
 	}, []);
 
 	const readyShares = useMemo(
@@ -39,15 +86,50 @@ const VaultShamirStep = () => {
 		[readyShares],
 	);
 
-	const next = useCallback(() => {
+	/* unverified shares may still open it; a second click goes ahead */
+	const canProceedUnverified = useMemo(
+		() =>
+			!!check &&
+			!check.ready &&
+			check.unverified > 0 &&
+			check.usable + check.unverified >= (check.threshold ?? 2),
+		[check],
+	);
+
+	const next = useCallback(async () => {
 		if (!hasEnoughShares) {
 			toast.error(formatMessage({ id: "vault.shamirStep.error" }));
 			return;
 		}
+		let result = check;
+		if (!canProceedUnverified || !result) {
+			const inputs: ShareInput[] = readyShares.map(
+				s => scanned[s.trim()] ?? { share: s },
+			);
+			try {
+				result = await invoke<ShareCheck>("check_shares", {
+					args: {
+						shares: inputs,
+						container_path: wizard.containerPath || undefined,
+					},
+				});
+			} catch (e) {
+				devError("Failed to check shares:", e);
+				toast.error(extractErrorMessage(e));
+				return;
+			}
+			setCheck(result);
+			if (!result.ready) return;
+		}
+		/* bad checksums and repeats would only make the core fail */
+		const accepted = readyShares.filter((_, idx) => {
+			const state = result?.shares[idx]?.state;
+			return state === "ok" || state === "unverified";
+		});
 		dispatch(
 			vaultSetOpenWizardState({
 				...wizard,
-				shares: readyShares,
+				shares: accepted,
 			}),
 		);
 		if (wizard.integrityProvider === "none") {
@@ -60,6 +142,9 @@ const VaultShamirStep = () => {
 		wizard,
 		readyShares,
 		hasEnoughShares,
+		canProceedUnverified,
+		check,
+		scanned,
 		navigate,
 		formatMessage,
 	]);
@@ -90,7 +175,38 @@ const VaultShamirStep = () => {
 							style={{ maxWidth: "100%" }}
 						/>
 					))}
+					<UIButton
+						icon={icons.folder}
+						text={formatMessage({ id: "vault.shamirStep.scan" })}
+						onClick={scanPages}
+						style={{ width: "fit-content" }}
+					/>
 				</div>
+				{check && (
+					<div className="flex flex-col gap-[6px] text-[15px]">
+						<p className="text-white">{check.summary}</p>
+						{check.shares.map(
+							(s, idx) =>
+								s.state !== "ok" && (
+									<p key={idx} className="text-red-400">
+										{formatMessage(
+											{
+												id: `vault.shamirStep.${s.state}`,
+											},
+											{ index: idx + 1 },
+										)}
+									</p>
+								),
+						)}
+						{canProceedUnverified && (
+							<p className="text-yellow-400">
+								{formatMessage({
+									id: "vault.shamirStep.unverifiedWarning",
+								})}
+							</p>
+						)}
+					</div>
+				)}
 			</div>
 			<div className="flex items-center gap-[10px] mt-[20px]">
 				<UIButton
@@ -101,7 +217,11 @@ const VaultShamirStep = () => {
 				/>
 				<UIButton
 					icon={icons.arrow_right}
-					text={formatMessage({ id: "common.next" })}
+					text={formatMessage({
+						id: canProceedUnverified
+							? "vault.shamirStep.continueAnyway"
+							: "common.next",
+					})}
 					onClick={next}
 					style={{ width: "fit-content" }}
 				/>
@@ -0,0 +1,122 @@
+import { useState } from "react";
+import { useIntl } from "react-intl";
+import { useSelector } from "react-redux";
+import { useNavigate } from "react-router-dom";
+import { RouteTypes } from "interfaces";
+import { useSigningKeys } from "features/Settings/hooks";
+import { useAppDispatch } from "features/Store";
+import {
+	UIButton,
+	UIPasswordField,
+	UISectionHeading,
+	UISelect,
+} from "features/UI";
+import { icons } from "assets";
+import { vaultSetWizardState } from "../../state/Vault.actions";
+import { selectVaultWizardState } from "../../state/Vault.selectors";
+
+const VaultSigningKeyStep = () => {
+	const { formatMessage } = useIntl();
+	const wizard = useSelector(selectVaultWizardState);
+	const dispatch = useAppDispatch();
+	const navigate = useNavigate();
+	const { keys } = useSigningKeys();
+
+	const [keyId, setKeyId] = useState(wizard.signingKeyId || "");
+	const [passphrase, setPassphrase] = useState(
+		wizard.signingPassphrase || "",
+	);
+
+	const next = () => {
+		dispatch(
+			vaultSetWizardState({
+				...wizard,
+				signingKeyId: keyId,
+				signingPassphrase: passphrase,
+			}),
+		);
+		navigate(RouteTypes.VaultCreateSplitToggle);
+	};
+
+	return (
+		<div>
+			<UISectionHeading
+				icon={icons.lock}
+				text={formatMessage({ id: "title.create" })}
+			/>
+			<p className="text-[20px] text-medium text-white text-center mt-[10px]">
+				{formatMessage({ id: "vault.signingKey.step" })}
+			</p>
+			<div className="flex flex-col gap-[20px] p-[20px] bg-white/5 rounded-[10px] mt-[20px]">
+				{keys.length === 0 ? (
+					<div className="flex flex-col gap-[10px]">
+						<p className="text-white/70">
+							{formatMessage({
+								id: "settings.signingKeys.empty",
+							})}
+						</p>
+						<UIButton
+							icon={icons.settings}
+							text={formatMessage({ id: "settings.title" })}
+							onClick={() => navigate(RouteTypes.Settings)}
+							style={{ width: "fit-content" }}
+						/>
+					</div>
+				) : (
+					<>
+						<div className="flex flex-col gap-[10px]">
+							<p className="text-[20px] text-white text-medium">
+								{formatMessage({ id: "vault.signingKey.key" })}:
+							</p>
+							<UISelect
+								value={keyId}
+								onChange={setKeyId}
+								options={keys.map(k => ({
+									value: k.id,
+									label: `${k.name} (${k.id})`,
+								}))}
+								placeholder={formatMessage({
+									id: "vault.signingKey.select",
+								})}
+								style={{ maxWidth: "50%" }}
+							/>
+						</div>
+						<div className="flex flex-col gap-[10px]">
+							<p className="text-[20px] text-white text-medium">
+								{formatMessage({
+									id: "vault.signingKey.passphrase",
+								})}
+								:
+							</p>
+							<UIPasswordField
+								value={passphrase}
+								onChange={e => setPassphrase(e.target.value)}
+								placeholder={formatMessage({
+									id: "common.passwordPlaceholder",
+								})}
+								style={{ maxWidth: "50%" }}
+							/>
+						</div>
+					</>
+				)}
+			</div>
+			<div className="flex items-center gap-[10px] mt-[20px]">
+				<UIButton
+					icon={icons.back}
+					text={formatMessage({ id: "common.back" })}
+					onClick={() => navigate(-1)}
+					style={{ width: "fit-content" }}
+				/>
+				<UIButton
+					icon={icons.arrow_right}
+					text={formatMessage({ id: "common.next" })}
+					onClick={next}
+					disabled={!keyId || !passphrase}
+					style={{ width: "fit-content" }}
+				/>
+			</div>
+		</div>
+	);
+};
+
+export { VaultSigningKeyStep };
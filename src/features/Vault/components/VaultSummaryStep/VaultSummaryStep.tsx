@@ -11,7 +11,11 @@ import { selectVaultWizardState } from "../../state/Vault.selectors";
 const CONTAINER_VERSION = 1;
 
 const humanCompression = { zip: "ZIP", none: "none" } as const;
-const humanIntegrity = { none: "none", hmac: "HMAC-SHA256" } as const;
+const humanIntegrity = {
+	none: "none",
+	hmac: "HMAC-SHA256",
+	ed25519: "Ed25519 signature",
+} as const;
 
 const VaultSummaryStep = () => {
 	const { formatMessage } = useIntl();
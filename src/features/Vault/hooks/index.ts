@@ -8,3 +8,4 @@ export { useWizardStepTracking } from "./useWizardStepTracking";
 export { useWizardNavigation } from "./useWizardNavigation";
 export { useReseal } from "./useReseal";
 export { useBackgroundContainerScan } from "./useBackgroundContainerScan";
+export { usePasswordStrength } from "./usePasswordStrength";
@@ -49,5 +49,14 @@ export function useContainerInfo() {
 		}
 	}, []);
 
-	return { done, result, error, run };
+	/** Reads the info again instead of serving it from the backend cache. */
+	const refresh = useCallback(
+		async (path: string) => {
+			await invoke("invalidate_container_info", { path });
+			await run(path);
+		},
+		[run],
+	);
+
+	return { done, result, error, run, refresh };
 }
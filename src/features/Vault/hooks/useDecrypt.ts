@@ -1,42 +1,72 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { useEffect, useRef, useState } from "react";
+import type { ProgressDetail, SignerInfo } from "interfaces";
 import { devError, devLog, extractErrorMessage } from "utils";
 
 export interface DecryptArgs {
 	containerPath: string;
 	folderPath: string;
 	passphrase?: string;
+	/** Combined with the passphrase by the backend */
+	keyFiles?: string[];
+	/** Decoded master token; words go through `mnemonic_to_master_token` first */
 	masterToken?: string;
 	tokenReaderType?: "flag" | "file";
 	tokenFormat?: "json" | "plaintext";
 	tokenFlag?: string;
 	tokenPath?: string;
 	additionalPassword?: string;
+	/** ed25519: the signer's public key the signature must verify against */
+	publicKey?: string;
 }
 
 const useDecrypt = () => {
 	const [progress, setProgress] = useState(0);
+	const [detail, setDetail] = useState<ProgressDetail | null>(null);
+	const [queuePosition, setQueuePosition] = useState<number | null>(null);
 	const [done, setDone] = useState(false);
 	const [error, setError] = useState<unknown | null>(null);
+	const [signer, setSigner] = useState<SignerInfo | null>(null);
 	const runningRef = useRef(false);
+	const seqRef = useRef<number | null>(null);
 
 	useEffect(() => {
 		const un1 = listen<number>("decrypt-progress", e => {
 			devLog("[tvault] decrypt progress", e.payload);
 			setProgress(e.payload);
+			setQueuePosition(null);
 		});
 		const un2 = listen<boolean>("decrypt-done", e => {
 			devLog("[tvault] decrypt done", e.payload);
 			setDone(e.payload);
+			setQueuePosition(null);
 			if (e.payload) {
 				runningRef.current = false;
 			}
 		});
+		const un3 = listen<ProgressDetail>("decrypt-progress-detail", e => {
+			setDetail(e.payload);
+		});
+		const un4 = listen<{ position: number; seq: number }>(
+			"decrypt-queued",
+			e => {
+				seqRef.current = e.payload.seq;
+				setQueuePosition(e.payload.position);
+			},
+		);
+
+		// only sent once the core checked the signature against the given key
+		const un5 = listen<SignerInfo>("decrypt-signer", e => {
+			setSigner(e.payload);
+		});
 
 		return () => {
 			un1.then(f => f());
 			un2.then(f => f());
+			un3.then(f => f());
+			un4.then(f => f());
+			un5.then(f => f());
 		};
 	}, []);
 
@@ -48,8 +78,11 @@ const useDecrypt = () => {
 
 		runningRef.current = true;
 		setProgress(0);
+		setDetail(null);
+		setQueuePosition(null);
 		setDone(false);
 		setError(null);
+		setSigner(null);
 
 		const payload: Record<string, unknown> = {
 			container_path: args.containerPath,
@@ -59,11 +92,19 @@ const useDecrypt = () => {
 		if (args.additionalPassword) {
 			payload.additional_password = args.additionalPassword;
 		}
+		if (args.publicKey) {
+			payload.public_key = args.publicKey;
+		}
+
+		// alone or with the passphrase; the backend passes it as the token flag
+		if (args.masterToken) {
+			payload.master_token = args.masterToken;
+		}
 
 		if (args.passphrase) {
 			payload.token = args.passphrase;
-			if (args.masterToken) {
-				payload.master_token = args.masterToken;
+			if (args.keyFiles?.length) {
+				payload.key_files = args.keyFiles;
 			}
 		} else if (args.tokenReaderType) {
 			payload.token_reader_type = args.tokenReaderType;
@@ -80,7 +121,9 @@ const useDecrypt = () => {
 		}
 		devLog("[tvault] invoking run_decrypt with", payload);
 		try {
-			await invoke("run_decrypt", { args: payload });
+			seqRef.current = await invoke<number>("run_decrypt", {
+				args: payload,
+			});
 			devLog("[tvault] run_decrypt invoke returned OK");
 		} catch (err) {
 			devError("[tvault] run_decrypt failed", extractErrorMessage(err));
@@ -90,7 +133,21 @@ const useDecrypt = () => {
 		}
 	};
 
-	return { progress, done, error, run };
+	const cancel = async () => {
+		if (seqRef.current === null) return false;
+		return invoke<boolean>("cancel_operation", { seq: seqRef.current });
+	};
+
+	return {
+		progress,
+		detail,
+		queuePosition,
+		done,
+		error,
+		signer,
+		run,
+		cancel,
+	};
 };
 
 export { useDecrypt };
@@ -1,24 +1,30 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { useEffect, useRef, useState } from "react";
+import type { ProgressDetail } from "interfaces";
 import { devError, devLog, extractErrorMessage } from "utils";
 import type { VaultWizardState } from "../Vault.model";
 
 const useEncrypt = (wizardState: VaultWizardState) => {
 	const [progress, setProgress] = useState(0);
+	const [detail, setDetail] = useState<ProgressDetail | null>(null);
+	const [queuePosition, setQueuePosition] = useState<number | null>(null);
 	const [done, setDone] = useState(false);
 	const [result, setResult] = useState<Record<string, unknown> | null>(null);
 	const [error, setError] = useState<unknown | null>(null);
 	const runningRef = useRef(false);
+	const seqRef = useRef<number | null>(null);
 
 	useEffect(() => {
 		const un1 = listen<number>("encrypt-progress", e => {
 			devLog("[tvault] progress", e.payload);
 			setProgress(e.payload);
+			setQueuePosition(null);
 		});
 		const un2 = listen<boolean>("encrypt-done", e => {
 			devLog("[tvault] done", e.payload);
 			setDone(e.payload);
+			setQueuePosition(null);
 			if (e.payload) {
 				runningRef.current = false;
 			}
@@ -35,12 +41,24 @@ const useEncrypt = (wizardState: VaultWizardState) => {
 		const un5 = listen<string>("encrypt-stdout", e => {
 			console.log("[tvault] stdout:", e.payload);
 		});
+		const un6 = listen<ProgressDetail>("encrypt-progress-detail", e => {
+			setDetail(e.payload);
+		});
+		const un7 = listen<{ position: number; seq: number }>(
+			"encrypt-queued",
+			e => {
+				seqRef.current = e.payload.seq;
+				setQueuePosition(e.payload.position);
+			},
+		);
 		return () => {
 			un1.then(f => f());
 			un2.then(f => f());
 			un3.then(f => f());
 			un4.then(f => f());
 			un5.then(f => f());
+			un6.then(f => f());
+			un7.then(f => f());
 		};
 	}, []);
 
@@ -52,6 +70,8 @@ const useEncrypt = (wizardState: VaultWizardState) => {
 
 		runningRef.current = true;
 		setProgress(0);
+		setDetail(null);
+		setQueuePosition(null);
 		setDone(false);
 		setError(null);
 		setResult(null);
@@ -62,6 +82,10 @@ const useEncrypt = (wizardState: VaultWizardState) => {
 			folder_path: wizardState.inputPath,
 			compression_type: wizardState.compression,
 			passphrase: wizardState.passphrase,
+			key_files:
+				wizardState.keySource === "manual"
+					? wizardState.keyFiles
+					: undefined,
 			token_type: wizardState.tokenType || "share",
 			token_save_type: wizardState.shareDest,
 			token_save_path: wizardState.sharePath,
@@ -73,6 +97,14 @@ const useEncrypt = (wizardState: VaultWizardState) => {
 				wizardState.integrityProvider === "hmac"
 					? wizardState.additionalPassword
 					: undefined,
+			signing_key_id:
+				wizardState.integrityProvider === "ed25519"
+					? wizardState.signingKeyId
+					: undefined,
+			signing_passphrase:
+				wizardState.integrityProvider === "ed25519"
+					? wizardState.signingPassphrase
+					: undefined,
 			comment: wizardState.comment || undefined,
 			tags: wizardState.tags || undefined,
 		};
@@ -81,7 +113,7 @@ const useEncrypt = (wizardState: VaultWizardState) => {
 		devLog("[tvault] tags:", wizardState.tags);
 
 		try {
-			await invoke("run_encrypt", { args });
+			seqRef.current = await invoke<number>("run_encrypt", { args });
 			devLog("[tvault] invoke returned OK");
 		} catch (err) {
 			devError("[tvault] invoke failed", err);
@@ -105,7 +137,21 @@ const useEncrypt = (wizardState: VaultWizardState) => {
 		return { masterToken, shares };
 	})();
 
-	return { progress, done, result: normalized, error, run };
+	const cancel = async () => {
+		if (seqRef.current === null) return false;
+		return invoke<boolean>("cancel_operation", { seq: seqRef.current });
+	};
+
+	return {
+		progress,
+		detail,
+		queuePosition,
+		done,
+		result: normalized,
+		error,
+		run,
+		cancel,
+	};
 };
 
 export { useEncrypt };
@@ -0,0 +1,60 @@
+import { invoke } from "@tauri-apps/api/core";
+import { Store } from "@tauri-apps/plugin-store";
+import { useCallback, useEffect, useState } from "react";
+import type { PasswordStrength } from "interfaces";
+import { devError, extractErrorMessage } from "utils";
+
+const VAULT_SETTINGS_STORE_KEY = "vault-settings.json";
+const MIN_PASSWORD_SCORE_KEY = "minPasswordScore";
+const ESTIMATE_DELAY_MS = 250;
+
+/**
+ * Strength of a password being chosen, re-estimated shortly after typing
+ * stops, against the `minPasswordScore` setting the backend enforces.
+ */
+export function usePasswordStrength(password: string, userInputs?: string[]) {
+	const [strength, setStrength] = useState<PasswordStrength>();
+	const [minScore, setMinScore] = useState(0);
+	const inputs = JSON.stringify(userInputs ?? []);
+
+	useEffect(() => {
+		Store.load(VAULT_SETTINGS_STORE_KEY)
+			.then(store => store.get<number>(MIN_PASSWORD_SCORE_KEY))
+			.then(value => setMinScore(Number(value ?? 0)))
+			.catch(e => devError("Failed to load password policy:", e));
+	}, []);
+
+	useEffect(() => {
+		if (!password) {
+			setStrength(undefined);
+			return;
+		}
+		let stale = false;
+		const timer = setTimeout(() => {
+			invoke<PasswordStrength>("estimate_strength", {
+				password,
+				userInputs: JSON.parse(inputs),
+			})
+				.then(s => !stale && setStrength(s))
+				.catch(e =>
+					devError(
+						"estimate_strength failed",
+						extractErrorMessage(e),
+					),
+				);
+		}, ESTIMATE_DELAY_MS);
+		return () => {
+			stale = true;
+			clearTimeout(timer);
+		};
+	}, [password, inputs]);
+
+	const generate = useCallback(
+		() => invoke<string>("generate_passphrase", {}),
+		[],
+	);
+
+	const tooWeak = strength !== undefined && strength.score < minScore;
+
+	return { strength, minScore, tooWeak, generate };
+}
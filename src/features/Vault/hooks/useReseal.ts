@@ -1,6 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { useEffect, useRef, useState } from "react";
+import type { ProgressDetail } from "interfaces";
 import { devError, devLog, extractErrorMessage } from "utils";
 
 export interface ResealArgs {
@@ -9,11 +10,15 @@ export interface ResealArgs {
 	folderPath: string;
 	name?: string;
 	passphrase?: string;
+	/** Key files the container was opened with; it is resealed with them too */
+	currentKeyFiles?: string[];
 	comment?: string;
 	tags?: string;
 	integrityProvider?: string;
 	currentIntegrityPassword?: string;
 	newIntegrityPassword?: string;
+	signingKeyId?: string;
+	signingPassphrase?: string;
 	masterToken?: string;
 	shares?: string[];
 	tokenType?: string;
@@ -22,19 +27,24 @@ export interface ResealArgs {
 
 const useReseal = () => {
 	const [progress, setProgress] = useState(0);
+	const [detail, setDetail] = useState<ProgressDetail | null>(null);
+	const [queuePosition, setQueuePosition] = useState<number | null>(null);
 	const [done, setDone] = useState(false);
 	const [error, setError] = useState<unknown | null>(null);
 	const [result, setResult] = useState<unknown | null>(null);
 	const runningRef = useRef(false);
+	const seqRef = useRef<number | null>(null);
 
 	useEffect(() => {
 		const un1 = listen<number>("reseal-progress", e => {
 			devLog("[tvault] reseal progress", e.payload);
 			setProgress(e.payload);
+			setQueuePosition(null);
 		});
 		const un2 = listen<boolean>("reseal-done", e => {
 			devLog("[tvault] reseal done", e.payload);
 			setDone(e.payload);
+			setQueuePosition(null);
 			if (e.payload) {
 				runningRef.current = false;
 			}
@@ -47,12 +57,24 @@ const useReseal = () => {
 			devLog("[tvault] reseal error", extractErrorMessage(e.payload));
 			setError(e.payload);
 		});
+		const un5 = listen<ProgressDetail>("reseal-progress-detail", e => {
+			setDetail(e.payload);
+		});
+		const un6 = listen<{ position: number; seq: number }>(
+			"reseal-queued",
+			e => {
+				seqRef.current = e.payload.seq;
+				setQueuePosition(e.payload.position);
+			},
+		);
 
 		return () => {
 			un1.then(f => f());
 			un2.then(f => f());
 			un3.then(f => f());
 			un4.then(f => f());
+			un5.then(f => f());
+			un6.then(f => f());
 		};
 	}, []);
 
@@ -64,6 +86,8 @@ const useReseal = () => {
 
 		runningRef.current = true;
 		setProgress(0);
+		setDetail(null);
+		setQueuePosition(null);
 		setDone(false);
 		setError(null);
 		setResult(null);
@@ -82,6 +106,9 @@ const useReseal = () => {
 		if (args.passphrase) {
 			payload.passphrase = args.passphrase;
 		}
+		if (args.currentKeyFiles) {
+			payload.current_key_files = args.currentKeyFiles;
+		}
 		if (args.comment) {
 			payload.comment = args.comment;
 		}
@@ -97,6 +124,12 @@ const useReseal = () => {
 		if (args.newIntegrityPassword) {
 			payload.new_integrity_password = args.newIntegrityPassword;
 		}
+		if (args.signingKeyId) {
+			payload.signing_key_id = args.signingKeyId;
+		}
+		if (args.signingPassphrase) {
+			payload.signing_passphrase = args.signingPassphrase;
+		}
 		if (args.masterToken) {
 			payload.master_token = args.masterToken;
 		}
@@ -142,7 +175,9 @@ const useReseal = () => {
 				);
 			});
 
-			await invoke("run_reseal", { args: payload });
+			seqRef.current = await invoke<number>("run_reseal", {
+				args: payload,
+			});
 			devLog("[tvault] run_reseal invoke returned OK");
 
 			await resealPromise;
@@ -155,7 +190,21 @@ const useReseal = () => {
 		}
 	};
 
-	return { progress, done, error, result, run };
+	const cancel = async () => {
+		if (seqRef.current === null) return false;
+		return invoke<boolean>("cancel_operation", { seq: seqRef.current });
+	};
+
+	return {
+		progress,
+		detail,
+		queuePosition,
+		done,
+		error,
+		result,
+		run,
+		cancel,
+	};
 };
 
 export { useReseal };
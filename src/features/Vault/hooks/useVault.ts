@@ -143,6 +143,8 @@ export const useVault = (
 									completeResealData.passphrase;
 								resealArgs.masterToken =
 									completeResealData.passphrase;
+								resealArgs.currentKeyFiles =
+									completeResealData.keyFiles ?? [];
 							}
 						}
 
@@ -175,6 +177,15 @@ export const useVault = (
 									changed: currentPassword !== newPassword,
 								});
 							}
+							if (
+								completeResealData.integrityProvider ===
+								"ed25519"
+							) {
+								resealArgs.signingKeyId =
+									completeResealData.signingKeyId;
+								resealArgs.signingPassphrase =
+									completeResealData.signingPassphrase;
+							}
 						}
 
 						devLog("[tvault] Final reseal args:", resealArgs);
@@ -19,5 +19,6 @@ export * from "./components/VaultDecryptRunStep";
 export * from "./components/ContainerDetails";
 export * from "./components/VaultCommentStep";
 export * from "./components/VaultIntegrityPasswordStep";
+export * from "./components/VaultSigningKeyStep";
 export * from "./components/VaultOutputTokensFile";
 export * from "./hooks";
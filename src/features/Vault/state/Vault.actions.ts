@@ -95,6 +95,19 @@ export const isContainerAccessible = async (path: string): Promise<boolean> => {
 	}
 };
 
+/** Deletes a closed container file and forgets it; throws if refused. */
+export const vaultDeleteContainer = (path: string) => {
+	return async (dispatch: AppDispatch) => {
+		const { invoke } = await import("@tauri-apps/api/core");
+		await invoke("delete_container", { path });
+		dispatch(vaultRemoveRecent(path));
+		dispatch(vaultRemoveResealData(path));
+		const store = await Store.load("recent-containers.json");
+		const recent = (await store.get<any[]>("recent")) || [];
+		await saveRecentToStore(recent.filter(r => r.path !== path));
+	};
+};
+
 export const cleanupNonExistentContainers = async (dispatch: AppDispatch) => {
 	try {
 		devInfo("Starting cleanup of non-existent containers");
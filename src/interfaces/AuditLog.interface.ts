@@ -0,0 +1,11 @@
+/** Result of `verify_audit_log`. */
+export interface AuditVerification {
+	intact: boolean;
+	/** Entries that chain correctly before the first break. */
+	entries: number;
+	/** 1-based line of the first entry that breaks the chain. */
+	broken_line: number | null;
+	reason: string | null;
+}
+
+export type AuditExportFormat = "jsonl" | "csv";
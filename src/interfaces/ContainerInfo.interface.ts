@@ -1,3 +1,5 @@
+import type { SignerInfo } from "./SigningKey.interface";
+
 export interface ContainerInfoData {
 	name?: string;
 	version?: number;
@@ -15,4 +17,5 @@ export interface ContainerInfoData {
 export interface ContainerInfoPayload {
 	path?: string;
 	data?: ContainerInfoData;
+	signer?: SignerInfo;
 }
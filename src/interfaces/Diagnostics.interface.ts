@@ -0,0 +1,11 @@
+export interface DiagnosticsEntry {
+	/** Path inside the zip, e.g. "logs/tvault.log". */
+	name: string;
+	content: string;
+}
+
+/** Result of `preview_diagnostics`; pass `id` back to `export_diagnostics`. */
+export interface DiagnosticsPreview {
+	id: number;
+	entries: DiagnosticsEntry[];
+}
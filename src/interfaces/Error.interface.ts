@@ -1,3 +1,5 @@
+import type { ProgressPhase } from "./Progress.interface";
+
 export interface TvaultStackFrame {
 	function: string;
 	file: string;
@@ -19,3 +21,12 @@ export interface TvaultErrorWithPath {
 	path: string;
 	error: TvaultError;
 }
+
+/** Emitted by the client when tvault-core stalls or runs past its deadline. */
+export interface TvaultTimeoutError {
+	code: "timeout";
+	message: string;
+	reason: "inactivity" | "deadline";
+	phase: ProgressPhase | null;
+	after_secs: number;
+}
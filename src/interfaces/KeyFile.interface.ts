@@ -0,0 +1,17 @@
+export type KeyFileState = "unchanged" | "changed" | "unknown" | "missing" | "unreadable";
+
+export interface KeyFileStatus {
+	/** Path as given; null for a file the vault was sealed with but not given now. */
+	path: string | null;
+	name: string;
+	state: KeyFileState;
+}
+
+/** Result of `check_key_files`. */
+export interface KeyFileCheck {
+	/** Whether the key files were recorded when the vault was sealed. */
+	recorded: boolean;
+	files: KeyFileStatus[];
+	/** "backup.key has changed since the vault was sealed". */
+	warning: string | null;
+}
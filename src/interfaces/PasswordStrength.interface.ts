@@ -0,0 +1,21 @@
+export interface CrackTime {
+	seconds: number;
+	/** "3 hours", "centuries". */
+	display: string;
+}
+
+/** Result of `estimate_strength`, shaped like zxcvbn's. */
+export interface PasswordStrength {
+	/** 0 (too guessable) to 4 (very unguessable). */
+	score: number;
+	guesses: number;
+	guesses_log10: number;
+	crack_times: {
+		online_throttled: CrackTime;
+		online_unthrottled: CrackTime;
+		offline_slow: CrackTime;
+		offline_fast: CrackTime;
+	};
+	warning?: string | null;
+	suggestions: string[];
+}
@@ -0,0 +1,21 @@
+export type ProgressPhase =
+	| "scan"
+	| "compress"
+	| "encrypt"
+	| "write"
+	| "verify"
+	| "extract";
+
+/** Payload of `{encrypt|decrypt|reseal}-progress-detail`; null fields are unknown. */
+export interface ProgressDetail {
+	percent: number;
+	phase: ProgressPhase | null;
+	bytes_done: number | null;
+	bytes_total: number | null;
+	files_done: number | null;
+	files_total: number | null;
+	current_file: string | null;
+	/** Bytes per second. */
+	throughput: number | null;
+	eta_secs: number | null;
+}
@@ -10,6 +10,7 @@ export enum RouteTypes {
 	VaultCreateSplitConfig = "/create/split-config",
 	VaultCreateIntegrity = "/create/integrity",
 	VaultCreateIntegrityPassword = "/create/integrity-password",
+	VaultCreateSigningKey = "/create/signing-key",
 	VaultCreateOutput = "/create/output",
 	VaultCreateOutputFile = "/create/output-file",
 	VaultCreateSummary = "/create/summary",
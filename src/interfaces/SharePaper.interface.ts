@@ -0,0 +1,52 @@
+/** One printed share page written by `export_share_papers`. */
+export interface SharePage {
+	index: number;
+	/** First 8 hex digits of the share's SHA-256, as "abcd ef01". */
+	checksum: string;
+	path: string;
+}
+
+/** What a share QR code decodes to. */
+export interface SharePayload {
+	tvault_share: number;
+	vault: string;
+	index: number;
+	total: number;
+	threshold: number;
+	/** `checksum` without the space. */
+	checksum: string;
+	/** Header hash of the container when printed; a reseal changes it. */
+	container?: string;
+	share: string;
+}
+
+/** A share handed to `check_shares`: scanned from a page, or typed. */
+export type ShareInput = SharePayload | { share: string; checksum?: string };
+
+/** QR codes found in one image by `scan_share_images`. */
+export interface ScannedImage {
+	path: string;
+	shares: ShareInput[];
+	error: string | null;
+}
+
+export type ShareState = "ok" | "unverified" | "bad_checksum" | "duplicate";
+
+export interface ShareStatus {
+	index: number | null;
+	checksum: string;
+	state: ShareState;
+}
+
+/** Result of `check_shares`. */
+export interface ShareCheck {
+	shares: ShareStatus[];
+	/** Checked shares; only these count towards `ready`. */
+	usable: number;
+	/** Typed shares, and pages printed before the last reseal. */
+	unverified: number;
+	threshold: number | null;
+	ready: boolean;
+	/** "you have 2 of the required 3 shares" */
+	summary: string;
+}
@@ -0,0 +1,37 @@
+/** ed25519 key as listed by `list_signing_keys`; the private half never leaves the backend. */
+export interface SigningKeyInfo {
+	id: string;
+	name: string;
+	/** `ed25519:<base64>` */
+	public_key: string;
+	/** Unix seconds. */
+	created_at: number;
+}
+
+/** Public key in the local trust store. */
+export interface TrustedKey {
+	fingerprint: string;
+	name: string;
+	public_key: string;
+	/** Unix seconds. */
+	added_at: number;
+}
+
+export type SignerStatus = "trusted" | "unknown" | "unverified" | "unsigned";
+
+/**
+ * Who signed a container. Container info never goes past "unverified";
+ * "trusted" only comes from `decrypt-signer`, after the core checked the
+ * signature.
+ */
+export interface SignerInfo {
+	status: SignerStatus;
+	name: string | null;
+	fingerprint: string | null;
+	public_key: string | null;
+	/**
+	 * "signed by Alice (trusted)", "signed by unknown key",
+	 * "claims signer Alice (unverified)", "signed (unverified)" or "unsigned".
+	 */
+	label: string;
+}
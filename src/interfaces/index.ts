@@ -1,3 +1,10 @@
 export * from "./Router.interface";
 export * from "./ContainerInfo.interface";
 export * from "./Error.interface";
+export * from "./Progress.interface";
+export * from "./SigningKey.interface";
+export * from "./SharePaper.interface";
+export * from "./PasswordStrength.interface";
+export * from "./KeyFile.interface";
+export * from "./Diagnostics.interface";
+export * from "./AuditLog.interface";